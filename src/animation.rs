@@ -0,0 +1,507 @@
+//! Non-blocking LED animations for the Modulino Pixels.
+//!
+//! An [`Animation`] computes frames from a caller-supplied millisecond
+//! timestamp, in the same way a [`Sequencer`](crate::Sequencer) plays tunes.
+//! Call [`tick`](Animation::tick) from the main loop and it updates the LEDs
+//! only when a new frame is due and differs from the last one.
+
+use crate::pixels::NUM_LEDS;
+use crate::{Color, PixelStrip, Pixels, Result};
+use embedded_hal::i2c::I2c;
+
+const DEFAULT_BRIGHTNESS: u8 = 50;
+const BREATH_STEPS: u32 = 128;
+const COMET_TAIL: u32 = 4;
+
+/// The effect an [`Animation`] plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AnimationKind {
+    /// The palette scrolls along the strip.
+    Rainbow,
+    /// Every third LED is lit and the pattern crawls along.
+    TheaterChase,
+    /// All LEDs fade in and out together, one palette color per breath.
+    Breathe,
+    /// A bright head with a fading tail runs along the strip and wraps around.
+    Comet,
+    /// A bright head with a fading tail bounces between the ends (Larson scanner).
+    Scanner,
+    /// LEDs light up at random and fade out.
+    Twinkle,
+    /// Flickering flames rising from the first LED.
+    Fire,
+    /// LEDs fill one by one with each palette color in turn, then black.
+    ColorWipe,
+}
+
+/// Which way an [`Animation`] moves along the strip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Direction {
+    /// From LED 0 towards the last LED.
+    #[default]
+    Forward,
+    /// From the last LED towards LED 0.
+    Reverse,
+}
+
+/// The colors an [`Animation`] draws with.
+///
+/// Positions 0-255 are spread evenly over the colors, with linear blending
+/// between neighbours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette<'a> {
+    colors: Colors<'a>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Colors<'a> {
+    Solid(Color),
+    Slice(&'a [Color]),
+}
+
+impl<'a> Palette<'a> {
+    /// A palette of `colors`. An empty slice draws black.
+    pub const fn new(colors: &'a [Color]) -> Self {
+        Self {
+            colors: Colors::Slice(colors),
+        }
+    }
+
+    /// A palette with a single color.
+    pub const fn solid(color: Color) -> Self {
+        Self {
+            colors: Colors::Solid(color),
+        }
+    }
+
+    /// Number of colors.
+    pub fn len(&self) -> usize {
+        match self.colors {
+            Colors::Solid(_) => 1,
+            Colors::Slice(colors) => colors.len(),
+        }
+    }
+
+    /// Check if the palette has no colors.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Color number `index`, wrapping around.
+    pub fn color(&self, index: usize) -> Color {
+        match self.colors {
+            Colors::Solid(color) => color,
+            Colors::Slice([]) => Color::BLACK,
+            Colors::Slice(colors) => colors[index % colors.len()],
+        }
+    }
+
+    /// The color at `position`, from the first color (0) to the last (255).
+    pub fn sample(&self, position: u8) -> Color {
+        let len = self.len();
+        if len < 2 {
+            return self.color(0);
+        }
+        let scaled = position as usize * (len - 1);
+        let index = scaled / 255;
+        if index >= len - 1 {
+            return self.color(len - 1);
+        }
+        let t = (scaled % 255) as f32 / 255.0;
+        self.color(index).lerp(self.color(index + 1), t)
+    }
+}
+
+impl Palette<'static> {
+    /// Fully saturated hues, ending where they started.
+    pub const RAINBOW: Palette<'static> = Palette::new(&[
+        Color::RED,
+        Color::YELLOW,
+        Color::GREEN,
+        Color::CYAN,
+        Color::BLUE,
+        Color::MAGENTA,
+        Color::RED,
+    ]);
+
+    /// Black through red and orange to yellow and white.
+    pub const FIRE: Palette<'static> = Palette::new(&[
+        Color::BLACK,
+        Color::new(160, 0, 0),
+        Color::new(255, 64, 0),
+        Color::new(255, 160, 0),
+        Color::new(255, 255, 64),
+        Color::WHITE,
+    ]);
+
+    /// Blues and greens.
+    pub const OCEAN: Palette<'static> = Palette::new(&[
+        Color::new(0, 0, 64),
+        Color::BLUE,
+        Color::new(0, 128, 255),
+        Color::CYAN,
+        Color::new(0, 255, 128),
+    ]);
+
+    /// Green through yellow to red, for levels and meters.
+    pub const LEVEL: Palette<'static> = Palette::new(&[Color::GREEN, Color::YELLOW, Color::RED]);
+}
+
+impl From<Color> for Palette<'_> {
+    fn from(color: Color) -> Self {
+        Self::solid(color)
+    }
+}
+
+impl<'a> From<&'a [Color]> for Palette<'a> {
+    fn from(colors: &'a [Color]) -> Self {
+        Self::new(colors)
+    }
+}
+
+/// A time-driven animation for the Modulino [`Pixels`].
+///
+/// Animations are sized for one module by default. For a longer
+/// [`PixelStrip`](crate::PixelStrip), create one with
+/// [`for_strip`](Self::for_strip) and the strip's length.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use modulino::{Animation, AnimationKind, Color, Direction, Palette, Pixels};
+///
+/// let mut pixels = Pixels::new(i2c)?;
+///
+/// let mut scanner = Animation::new(AnimationKind::Scanner)
+///     .with_palette(Color::RED)
+///     .with_speed(60)
+///     .with_brightness(30);
+///
+/// let mut rainbow = Animation::new(AnimationKind::Rainbow)
+///     .with_palette(Palette::OCEAN)
+///     .with_direction(Direction::Reverse);
+///
+/// loop {
+///     scanner.tick(&mut pixels, now_ms())?;
+///     // ... other work ...
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Animation<'a, const N: usize = NUM_LEDS> {
+    kind: AnimationKind,
+    palette: Palette<'a>,
+    step_ms: u16,
+    direction: Direction,
+    brightness: u8,
+    seed: u32,
+    rng: u32,
+    start: Option<u32>,
+    last_step: Option<u32>,
+    levels: [u8; N],
+    positions: [u8; N],
+}
+
+impl Animation<'_> {
+    /// Default brightness (0-100).
+    pub const DEFAULT_BRIGHTNESS: u8 = DEFAULT_BRIGHTNESS;
+
+    /// Number of steps in one breath of [`AnimationKind::Breathe`].
+    pub const BREATH_STEPS: u32 = BREATH_STEPS;
+
+    /// Length of the tail of [`AnimationKind::Comet`], in LEDs.
+    pub const COMET_TAIL: u32 = COMET_TAIL;
+
+    /// Create an animation for one Pixels module with the default palette and
+    /// speed for `kind`.
+    pub fn new(kind: AnimationKind) -> Self {
+        Self::for_strip(kind)
+    }
+
+    /// Draw the next frame on `pixels` if one is due at `now_ms`, and show it
+    /// if it differs from the one on the LEDs.
+    ///
+    /// Returns `true` if the LEDs were written.
+    pub fn tick<I2C, E>(&mut self, pixels: &mut Pixels<I2C>, now_ms: u32) -> Result<bool, E>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let Some(frame) = self.poll(now_ms) else {
+            return Ok(false);
+        };
+        for (index, color) in frame.iter().enumerate() {
+            pixels.set_color(index, *color, self.brightness)?;
+        }
+        pixels.show_if_changed()
+    }
+}
+
+impl<'a, const N: usize> Animation<'a, N> {
+    /// Create an animation for `N` LEDs with the default palette and speed
+    /// for `kind`.
+    pub fn for_strip(kind: AnimationKind) -> Self {
+        let (palette, step_ms) = match kind {
+            AnimationKind::Rainbow => (Palette::RAINBOW, 10),
+            AnimationKind::TheaterChase => (Palette::RAINBOW, 120),
+            AnimationKind::Breathe => (Palette::solid(Color::WHITE), 20),
+            AnimationKind::Comet => (Palette::solid(Color::WHITE), 80),
+            AnimationKind::Scanner => (Palette::solid(Color::RED), 80),
+            AnimationKind::Twinkle => (Palette::RAINBOW, 50),
+            AnimationKind::Fire => (Palette::FIRE, 30),
+            AnimationKind::ColorWipe => {
+                (Palette::new(&[Color::RED, Color::GREEN, Color::BLUE]), 60)
+            }
+        };
+        let seed = 0x2545_F491;
+        Self {
+            kind,
+            palette,
+            step_ms,
+            direction: Direction::Forward,
+            brightness: DEFAULT_BRIGHTNESS,
+            seed,
+            rng: seed,
+            start: None,
+            last_step: None,
+            levels: [0; N],
+            positions: [0; N],
+        }
+    }
+
+    /// Set the palette: a [`Palette`], a single [`Color`] or a slice of colors.
+    pub fn with_palette(mut self, palette: impl Into<Palette<'a>>) -> Self {
+        self.palette = palette.into();
+        self
+    }
+
+    /// Set the time per animation step in milliseconds (lower is faster).
+    pub fn with_speed(mut self, step_ms: u16) -> Self {
+        self.step_ms = step_ms.max(1);
+        self
+    }
+
+    /// Set the direction of travel.
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Set the brightness passed to the LEDs (0-100).
+    pub fn with_brightness(mut self, brightness: u8) -> Self {
+        self.brightness = brightness.min(100);
+        self
+    }
+
+    /// Seed the random generator used by [`AnimationKind::Twinkle`] and
+    /// [`AnimationKind::Fire`].
+    pub fn with_seed(mut self, seed: u32) -> Self {
+        // Xorshift never leaves zero
+        self.seed = if seed == 0 { 1 } else { seed };
+        self.rng = self.seed;
+        self
+    }
+
+    /// The effect.
+    pub fn kind(&self) -> AnimationKind {
+        self.kind
+    }
+
+    /// The palette.
+    pub fn palette(&self) -> &Palette<'a> {
+        &self.palette
+    }
+
+    /// Time per step in milliseconds.
+    pub fn speed(&self) -> u16 {
+        self.step_ms
+    }
+
+    /// The direction of travel.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// The brightness (0-100).
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Start again from the first frame on the next poll.
+    pub fn restart(&mut self) {
+        self.rng = self.seed;
+        self.start = None;
+        self.last_step = None;
+        self.levels = [0; N];
+        self.positions = [0; N];
+    }
+
+    /// Return the frame to show if a new one is due at `now_ms`.
+    ///
+    /// The first call starts the animation. `now_ms` may wrap around. When
+    /// calls are late, steps are skipped rather than slowing the animation down.
+    pub fn poll(&mut self, now_ms: u32) -> Option<[Color; N]> {
+        let start = *self.start.get_or_insert(now_ms);
+        let step = now_ms.wrapping_sub(start) / self.step_ms.max(1) as u32;
+        if self.last_step == Some(step) {
+            return None;
+        }
+        self.last_step = Some(step);
+
+        let frame = self.render(step);
+        Some(match self.direction {
+            Direction::Forward => frame,
+            Direction::Reverse => {
+                let mut reversed = frame;
+                reversed.reverse();
+                reversed
+            }
+        })
+    }
+
+    /// Draw the next frame on `strip` if one is due at `now_ms`, and show the
+    /// modules whose LEDs changed.
+    ///
+    /// Returns `true` if any module was written. LEDs beyond the shorter of the
+    /// animation and the strip are left alone.
+    pub fn tick_strip<I2C, E, const M: usize>(
+        &mut self,
+        strip: &mut PixelStrip<I2C, M>,
+        now_ms: u32,
+    ) -> Result<bool, E>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let Some(frame) = self.poll(now_ms) else {
+            return Ok(false);
+        };
+        strip.set_frame(&frame, self.brightness);
+        strip.show_if_changed()
+    }
+
+    fn render(&mut self, step: u32) -> [Color; N] {
+        let mut frame = [Color::BLACK; N];
+        if N == 0 {
+            return frame;
+        }
+        let n = N as u32;
+        match self.kind {
+            AnimationKind::Rainbow => {
+                for (i, led) in frame.iter_mut().enumerate() {
+                    let offset = i as u32 * 256 / n;
+                    *led = self.palette.sample(step.wrapping_add(offset) as u8);
+                }
+            }
+            AnimationKind::TheaterChase => {
+                let color = self.palette.sample(step.wrapping_mul(8) as u8);
+                for (i, led) in frame.iter_mut().enumerate() {
+                    if (i as u32 + 3 - step % 3) % 3 == 0 {
+                        *led = color;
+                    }
+                }
+            }
+            AnimationKind::Breathe => {
+                let breath = step / BREATH_STEPS;
+                let phase = (step % BREATH_STEPS) as f32 / BREATH_STEPS as f32;
+                let level = (1.0 - libm::cosf(phase * 2.0 * core::f32::consts::PI)) / 2.0;
+                let color = self.palette.color(breath as usize);
+                frame = [color.scale(libm::roundf(level * 255.0) as u8); N];
+            }
+            AnimationKind::Comet => {
+                let lap = n + COMET_TAIL;
+                let head = step % lap;
+                let color = self.palette.color((step / lap) as usize);
+                for (i, led) in frame.iter_mut().enumerate() {
+                    let behind = head.wrapping_sub(i as u32);
+                    if behind < COMET_TAIL {
+                        *led = color.scale((255 >> (2 * behind)) as u8);
+                    }
+                }
+            }
+            AnimationKind::Scanner => {
+                let period = (2 * (n - 1)).max(1);
+                let pos = step % period;
+                let head = if pos < n { pos } else { period - pos };
+                self.fade(2);
+                self.levels[head as usize] = 255;
+                let color = self.palette.color((step / period) as usize);
+                for (led, level) in frame.iter_mut().zip(self.levels) {
+                    *led = color.scale(level);
+                }
+            }
+            AnimationKind::Twinkle => {
+                self.fade(1);
+                if self.random() % 3 == 0 {
+                    let i = self.random() as usize % N;
+                    if self.levels[i] == 0 {
+                        self.levels[i] = 255;
+                        self.positions[i] = self.random() as u8;
+                    }
+                }
+                for (i, led) in frame.iter_mut().enumerate() {
+                    *led = self.palette.sample(self.positions[i]).scale(self.levels[i]);
+                }
+            }
+            AnimationKind::Fire => {
+                // Fire2012 by Mark Kriegsman, scaled down to a short strip
+                for heat in self.levels.iter_mut() {
+                    let cooling = (self.rng % 40) as u8;
+                    self.rng = xorshift(self.rng);
+                    *heat = heat.saturating_sub(cooling);
+                }
+                for k in (2..N).rev() {
+                    let rising = self.levels[k - 1] as u16 + 2 * self.levels[k - 2] as u16;
+                    self.levels[k] = (rising / 3) as u8;
+                }
+                if self.random() % 3 != 0 {
+                    let i = self.random() as usize % N.min(2);
+                    let spark = 160 + (self.random() % 96) as u8;
+                    self.levels[i] = self.levels[i].saturating_add(spark);
+                }
+                for (led, heat) in frame.iter_mut().zip(self.levels) {
+                    *led = self.palette.sample(heat);
+                }
+            }
+            AnimationKind::ColorWipe => {
+                // The palette colors in turn, then black
+                let colors = self.palette.len() as u32 + 1;
+                let wipe = step / n;
+                let color_at = |wipe: u32| match wipe % colors {
+                    c if c + 1 == colors => Color::BLACK,
+                    c => self.palette.color(c as usize),
+                };
+                let filled = step % n;
+                for (i, led) in frame.iter_mut().enumerate() {
+                    *led = if (i as u32) <= filled {
+                        color_at(wipe)
+                    } else if wipe == 0 {
+                        Color::BLACK
+                    } else {
+                        color_at(wipe - 1)
+                    };
+                }
+            }
+        }
+        frame
+    }
+
+    /// Divide every level by 2 to the power of `shift`.
+    fn fade(&mut self, shift: u32) {
+        for level in self.levels.iter_mut() {
+            *level >>= shift;
+        }
+    }
+
+    fn random(&mut self) -> u32 {
+        self.rng = xorshift(self.rng);
+        self.rng
+    }
+}
+
+/// One step of Marsaglia's xorshift32 generator.
+fn xorshift(mut x: u32) -> u32 {
+    x ^= x << 13;
+    x ^= x >> 17;
+    x ^= x << 5;
+    x
+}
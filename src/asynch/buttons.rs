@@ -0,0 +1,157 @@
+//! Async Modulino Buttons driver.
+
+use super::{probe_addresses, I2cDevice};
+use crate::buttons::led_payload;
+use crate::{
+    addresses, ButtonEventList, ButtonEvents, ButtonLed, ButtonState, ButtonTimings, Result,
+};
+use embedded_hal_async::i2c::I2c;
+
+/// Async driver for the Modulino Buttons module.
+///
+/// See [`crate::Buttons`] for the blocking version.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use modulino::asynch::Buttons;
+///
+/// let mut buttons = Buttons::new(i2c).await?;
+///
+/// let state = buttons.read().await?;
+/// buttons.led_a.set(state.a);
+/// buttons.update_leds().await?;
+/// ```
+pub struct Buttons<I2C> {
+    device: I2cDevice<I2C>,
+    /// LED A state
+    pub led_a: ButtonLed,
+    /// LED B state
+    pub led_b: ButtonLed,
+    /// LED C state
+    pub led_c: ButtonLed,
+    current_state: ButtonState,
+    events: ButtonEvents,
+}
+
+impl<I2C, E> Buttons<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Create a new Buttons instance with the default address.
+    pub async fn new(i2c: I2C) -> Result<Self, E> {
+        Self::new_with_address(i2c, addresses::BUTTONS).await
+    }
+
+    /// Discover if a Buttons module is connected.
+    ///
+    /// Probes the default/match addresses and returns the first one that ACKs.
+    ///
+    /// > [!WARNING]
+    /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
+    pub async fn discover(i2c: &mut I2C) -> Result<u8, E> {
+        probe_addresses(i2c, &[addresses::BUTTONS]).await
+    }
+
+    /// Create a new Buttons instance with a custom address.
+    pub async fn new_with_address(i2c: I2C, address: u8) -> Result<Self, E> {
+        let mut buttons = Self {
+            device: I2cDevice::new(i2c, address),
+            led_a: ButtonLed::new(),
+            led_b: ButtonLed::new(),
+            led_c: ButtonLed::new(),
+            current_state: ButtonState::default(),
+            events: ButtonEvents::new(),
+        };
+
+        // Verify device is present
+        buttons.read().await?;
+
+        Ok(buttons)
+    }
+
+    /// Get the I2C address.
+    pub fn address(&self) -> u8 {
+        self.device.address
+    }
+
+    /// Read the current button states.
+    pub async fn read(&mut self) -> Result<ButtonState, E> {
+        let mut buf = [0u8; 4]; // 1 pinstrap + 3 button states
+        self.device.read(&mut buf).await?;
+
+        self.current_state = ButtonState::from_bytes(&buf);
+
+        Ok(self.current_state)
+    }
+
+    /// Get the last read button state without performing I2C communication.
+    pub fn state(&self) -> ButtonState {
+        self.current_state
+    }
+
+    /// Check if button A is pressed (uses cached state).
+    pub fn button_a_pressed(&self) -> bool {
+        self.current_state.a
+    }
+
+    /// Check if button B is pressed (uses cached state).
+    pub fn button_b_pressed(&self) -> bool {
+        self.current_state.b
+    }
+
+    /// Check if button C is pressed (uses cached state).
+    pub fn button_c_pressed(&self) -> bool {
+        self.current_state.c
+    }
+
+    /// Read the buttons and return the events since the last poll.
+    ///
+    /// `now_ms` is a millisecond timestamp from the caller's clock (it may wrap
+    /// around). Call this regularly, every 10 ms or so, for accurate timing.
+    /// See [`ButtonEvents`] for the events that are detected.
+    pub async fn poll(&mut self, now_ms: u32) -> Result<ButtonEventList, E> {
+        let state = self.read().await?;
+        Ok(self.events.update(state, now_ms))
+    }
+
+    /// Get the timings used by [`poll`](Self::poll).
+    pub fn event_timings(&self) -> ButtonTimings {
+        self.events.timings()
+    }
+
+    /// Change the timings used by [`poll`](Self::poll).
+    pub fn set_event_timings(&mut self, timings: ButtonTimings) {
+        self.events.set_timings(timings);
+    }
+
+    /// Update the LED states on the device.
+    pub async fn update_leds(&mut self) -> Result<(), E> {
+        let data = led_payload(self.led_a, self.led_b, self.led_c);
+        self.device.write(&data).await?;
+        Ok(())
+    }
+
+    /// Set all LED states at once and update the hardware.
+    pub async fn set_leds(&mut self, a: bool, b: bool, c: bool) -> Result<(), E> {
+        self.led_a.set(a);
+        self.led_b.set(b);
+        self.led_c.set(c);
+        self.update_leds().await
+    }
+
+    /// Turn all LEDs off.
+    pub async fn all_leds_off(&mut self) -> Result<(), E> {
+        self.set_leds(false, false, false).await
+    }
+
+    /// Turn all LEDs on.
+    pub async fn all_leds_on(&mut self) -> Result<(), E> {
+        self.set_leds(true, true, true).await
+    }
+
+    /// Release the I2C bus.
+    pub fn release(self) -> I2C {
+        self.device.release()
+    }
+}
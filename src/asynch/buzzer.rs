@@ -0,0 +1,115 @@
+//! Async Modulino Buzzer driver.
+
+use super::{probe_addresses, I2cDevice};
+use crate::buzzer::tone_payload;
+use crate::{addresses, Note, Result, ToneStep};
+use embedded_hal_async::i2c::I2c;
+
+/// Async driver for the Modulino Buzzer module.
+///
+/// See [`crate::Buzzer`] for the blocking version.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use modulino::asynch::Buzzer;
+/// use modulino::Note;
+///
+/// let mut buzzer = Buzzer::new(i2c).await?;
+///
+/// buzzer.play_note(Note::C5, 1000).await?;
+/// buzzer.no_tone().await?;
+/// ```
+pub struct Buzzer<I2C> {
+    device: I2cDevice<I2C>,
+}
+
+impl<I2C, E> Buzzer<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Minimum supported frequency in Hz.
+    pub const MIN_FREQUENCY: u16 = crate::buzzer::MIN_FREQUENCY;
+
+    /// Create a new Buzzer instance with the default address.
+    pub async fn new(i2c: I2C) -> Result<Self, E> {
+        Self::new_with_address(i2c, addresses::BUZZER).await
+    }
+
+    /// Discover if a Buzzer module is connected.
+    ///
+    /// Probes the default/match addresses and returns the first one that ACKs.
+    ///
+    /// > [!WARNING]
+    /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
+    pub async fn discover(i2c: &mut I2C) -> Result<u8, E> {
+        probe_addresses(i2c, &[addresses::BUZZER]).await
+    }
+
+    /// Create a new Buzzer instance with a custom address.
+    pub async fn new_with_address(i2c: I2C, address: u8) -> Result<Self, E> {
+        let mut buzzer = Self {
+            device: I2cDevice::new(i2c, address),
+        };
+
+        // Initialize with no tone
+        buzzer.no_tone().await?;
+
+        Ok(buzzer)
+    }
+
+    /// Get the I2C address.
+    pub fn address(&self) -> u8 {
+        self.device.address
+    }
+
+    /// Play a tone at the specified frequency.
+    ///
+    /// # Arguments
+    ///
+    /// * `frequency` - The frequency in Hz (minimum 180 Hz, or 0 for silence)
+    /// * `duration_ms` - The duration in milliseconds (0xFFFF for indefinite)
+    pub async fn tone(&mut self, frequency: u16, duration_ms: u16) -> Result<(), E> {
+        let data = tone_payload(frequency, duration_ms);
+        self.device.write(&data).await?;
+        Ok(())
+    }
+
+    /// Play a tone indefinitely until stopped.
+    pub async fn tone_continuous(&mut self, frequency: u16) -> Result<(), E> {
+        self.tone(frequency, 0xFFFF).await
+    }
+
+    /// Play a musical note.
+    pub async fn play_note(&mut self, note: Note, duration_ms: u16) -> Result<(), E> {
+        self.tone(note.frequency(), duration_ms).await
+    }
+
+    /// Play one [`ToneStep`], e.g. from a [`Sequencer`](crate::Sequencer).
+    ///
+    /// Rests silence the buzzer.
+    pub async fn play_step(&mut self, step: ToneStep) -> Result<(), E> {
+        if step.is_rest() {
+            self.no_tone().await
+        } else {
+            self.tone(step.frequency, step.sound_ms).await
+        }
+    }
+
+    /// Stop playing any tone.
+    pub async fn no_tone(&mut self) -> Result<(), E> {
+        let data = [0u8; 8];
+        self.device.write(&data).await?;
+        Ok(())
+    }
+
+    /// Alias for `no_tone()`.
+    pub async fn stop(&mut self) -> Result<(), E> {
+        self.no_tone().await
+    }
+
+    /// Release the I2C bus.
+    pub fn release(self) -> I2C {
+        self.device.release()
+    }
+}
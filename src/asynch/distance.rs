@@ -0,0 +1,508 @@
+//! Async Modulino Distance driver.
+
+use super::{probe_addresses, I2cDevice};
+use crate::distance::*;
+use crate::{addresses, Error, Result};
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
+use embedded_hal_async::i2c::I2c;
+
+/// Async driver for the Modulino Distance module.
+///
+/// See [`crate::Distance`] for the blocking version.
+pub struct Distance<I2C> {
+    device: I2cDevice<I2C>,
+    timing_budget_ms: u16,
+    inter_measurement_ms: u32,
+}
+
+impl<I2C, E> Distance<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Create a new Distance instance.
+    pub fn new(i2c: I2C) -> Self {
+        Self::new_with_address(i2c, addresses::DISTANCE)
+    }
+
+    /// Discover if a Distance module is connected.
+    ///
+    /// Probes the default/match addresses and returns the first one that ACKs.
+    ///
+    /// > [!WARNING]
+    /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
+    pub async fn discover(i2c: &mut I2C) -> Result<u8, E> {
+        probe_addresses(i2c, &[addresses::DISTANCE]).await
+    }
+
+    /// Create a new Distance instance with a custom address.
+    pub fn new_with_address(i2c: I2C, address: u8) -> Self {
+        Self {
+            device: I2cDevice::new(i2c, address),
+            timing_budget_ms: DEFAULT_TIMING_BUDGET_MS,
+            inter_measurement_ms: DEFAULT_INTER_MEASUREMENT_MS,
+        }
+    }
+
+    /// Initialize the sensor.
+    /// This performs the firmware loading and tuning required by the VL53L4CD.
+    ///
+    /// Fails with [`Error::Timeout`] if the sensor does not boot or complete
+    /// its first measurement within a second.
+    pub async fn init<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), E> {
+        // 1. Wait for boot
+        let mut waited_ms = 0;
+        while self
+            .device
+            .read_reg16_u8(VL53L4CD_FIRMWARE_SYSTEM_STATUS)
+            .await?
+            != 0x03
+        {
+            if waited_ms >= INIT_TIMEOUT_MS {
+                return Err(Error::Timeout);
+            }
+            delay.delay_ms(1).await;
+            waited_ms += 1;
+        }
+
+        // 2. Load default configuration
+        for (i, &byte) in VL53L4CD_DEFAULT_CONFIGURATION.iter().enumerate() {
+            self.device
+                .write_reg16_u8(VL53L4CD_DEFAULT_CONFIGURATION_START + i as u16, byte)
+                .await?;
+        }
+
+        // 3. Start VHV
+        self.device
+            .write_reg16_u8(VL53L4CD_SYSTEM_START, 0x40)
+            .await?;
+
+        // 4. Wait for data ready
+        self.wait_for_data(delay, INIT_TIMEOUT_MS).await?;
+
+        self.clear_interrupt().await?;
+        self.stop_ranging().await?;
+
+        // 5. Apply specific settings
+        self.device
+            .write_reg16_u8(VL53L4CD_VHV_CONFIG_TIMEOUT_MACROP_LOOP_BOUND, 0x09)
+            .await?;
+        self.device.write_reg16_u8(0x000B, 0x00).await?;
+        self.device.write_reg16_u16(0x0024, 0x0500).await?;
+        self.device.write_reg16_u8(0x0081, 0x8A).await?; // 0b1000_1010
+        self.device.write_reg16_u8(0x004B, 0x03).await?;
+
+        // Set defaults
+        // Use 50ms inter-measurement instead of 0 to ensure stability
+        self.set_range_timing(DEFAULT_TIMING_BUDGET_MS, DEFAULT_INTER_MEASUREMENT_MS)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get the I2C address.
+    pub fn address(&self) -> u8 {
+        self.device.address
+    }
+
+    /// Set the timing budget and the inter-measurement period in milliseconds.
+    ///
+    /// See [`crate::Distance::set_range_timing`].
+    pub async fn set_range_timing(
+        &mut self,
+        budget_ms: u16,
+        inter_measurement_ms: u32,
+    ) -> Result<(), E> {
+        check_range_timing(budget_ms, inter_measurement_ms)?;
+        let osc_frequency = self.device.read_reg16_u16(VL53L4CD_OSC_FREQUENCY).await?;
+        let (range_config_a, range_config_b) =
+            range_config(budget_ms, inter_measurement_ms, osc_frequency).ok_or(Error::DataError)?;
+        let inter_measurement = if inter_measurement_ms == 0 {
+            0
+        } else {
+            let clock_pll = self
+                .device
+                .read_reg16_u16(VL53L4CD_RESULT_OSC_CALIBRATE_VAL)
+                .await?;
+            inter_measurement_value(inter_measurement_ms, clock_pll)?
+        };
+
+        self.device
+            .write_reg16_u32(VL53L4CD_INTERMEASUREMENT_MS, inter_measurement)
+            .await?;
+        self.device
+            .write_reg16_u16(VL53L4CD_RANGE_CONFIG_A, range_config_a)
+            .await?;
+        self.device
+            .write_reg16_u16(VL53L4CD_RANGE_CONFIG_B, range_config_b)
+            .await?;
+        self.timing_budget_ms = budget_ms;
+        self.inter_measurement_ms = inter_measurement_ms;
+        Ok(())
+    }
+
+    /// Set the timing budget in milliseconds (10 to 200), keeping the
+    /// inter-measurement period.
+    ///
+    /// See [`crate::Distance::set_timing_budget`].
+    pub async fn set_timing_budget(&mut self, budget_ms: u16) -> Result<(), E> {
+        self.set_range_timing(budget_ms, self.inter_measurement_ms)
+            .await
+    }
+
+    /// Set the inter-measurement period in milliseconds, keeping the timing
+    /// budget, or 0 to measure back to back.
+    pub async fn set_inter_measurement(&mut self, period_ms: u32) -> Result<(), E> {
+        self.set_range_timing(self.timing_budget_ms, period_ms)
+            .await
+    }
+
+    /// Read the timing budget in milliseconds back from the sensor.
+    pub async fn timing_budget(&mut self) -> Result<u16, E> {
+        let inter_measurement = self
+            .device
+            .read_reg16_u32(VL53L4CD_INTERMEASUREMENT_MS)
+            .await?;
+        let osc_frequency = self.device.read_reg16_u16(VL53L4CD_OSC_FREQUENCY).await?;
+        let range_config_a = self.device.read_reg16_u16(VL53L4CD_RANGE_CONFIG_A).await?;
+        timing_budget_from_config(range_config_a, inter_measurement, osc_frequency)
+            .ok_or(Error::DataError)
+    }
+
+    /// Read the inter-measurement period in milliseconds back from the
+    /// sensor, 0 if it measures back to back.
+    pub async fn inter_measurement(&mut self) -> Result<u32, E> {
+        let inter_measurement = self
+            .device
+            .read_reg16_u32(VL53L4CD_INTERMEASUREMENT_MS)
+            .await?;
+        if inter_measurement == 0 {
+            return Ok(0);
+        }
+        let clock_pll = self
+            .device
+            .read_reg16_u16(VL53L4CD_RESULT_OSC_CALIBRATE_VAL)
+            .await?;
+        inter_measurement_from_value(inter_measurement, clock_pll).ok_or(Error::DataError)
+    }
+
+    /// Start continuous ranging, back to back or with the inter-measurement
+    /// period set by [`set_range_timing`](Self::set_range_timing).
+    pub async fn start_ranging(&mut self) -> Result<(), E> {
+        self.device
+            .write_reg16_u8(
+                VL53L4CD_SYSTEM_START,
+                system_start_continuous(self.inter_measurement_ms),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Stop ranging.
+    pub async fn stop_ranging(&mut self) -> Result<(), E> {
+        self.device
+            .write_reg16_u8(VL53L4CD_SYSTEM_START, 0x00)
+            .await?;
+        Ok(())
+    }
+
+    /// Start a single measurement. The sensor stops by itself afterwards.
+    pub async fn start_single_shot(&mut self) -> Result<(), E> {
+        self.device
+            .write_reg16_u8(VL53L4CD_SYSTEM_START, SYSTEM_START_SINGLE_SHOT)
+            .await?;
+        Ok(())
+    }
+
+    /// Wait up to `timeout_ms` milliseconds for new data.
+    ///
+    /// See [`crate::Distance::wait_for_data`].
+    pub async fn wait_for_data<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        timeout_ms: u32,
+    ) -> Result<(), E> {
+        let mut waited_ms = 0;
+        while !self.data_ready().await? {
+            if waited_ms >= timeout_ms {
+                return Err(Error::Timeout);
+            }
+            delay.delay_ms(1).await;
+            waited_ms += 1;
+        }
+        Ok(())
+    }
+
+    /// Take one measurement and return the distance in millimeters.
+    ///
+    /// See [`crate::Distance::measure_blocking`].
+    pub async fn measure_blocking<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        timeout_ms: u32,
+    ) -> Result<Option<u16>, E> {
+        self.stop_ranging().await?;
+        self.clear_interrupt().await?;
+        self.start_single_shot().await?;
+        if let Err(error) = self.wait_for_data(delay, timeout_ms).await {
+            // Report the original error even if stopping fails too
+            let _ = self.stop_ranging().await;
+            return Err(error);
+        }
+        let distance = self.read_distance().await?;
+        self.stop_ranging().await?;
+        Ok(distance)
+    }
+
+    /// Check if new data is ready.
+    pub async fn data_ready(&mut self) -> Result<bool, E> {
+        let mux_ctrl = self.device.read_reg16_u8(VL53L4CD_GPIO_HV_MUX_CTRL).await?;
+        let status = self
+            .device
+            .read_reg16_u8(VL53L4CD_GPIO_TIO_HV_STATUS)
+            .await?;
+        Ok(is_data_ready(mux_ctrl, status))
+    }
+
+    /// Raise the interrupt only for measurements that meet `mode` for the
+    /// thresholds `low_mm` and `high_mm`.
+    ///
+    /// See [`crate::Distance::set_detection_thresholds`].
+    pub async fn set_detection_thresholds(
+        &mut self,
+        low_mm: u16,
+        high_mm: u16,
+        mode: DetectionMode,
+    ) -> Result<(), E> {
+        if matches!(mode, DetectionMode::Inside | DetectionMode::Outside) && low_mm > high_mm {
+            return Err(Error::InvalidParameter);
+        }
+        self.device
+            .write_reg16_u8(VL53L4CD_SYSTEM_INTERRUPT, mode.config())
+            .await?;
+        self.device
+            .write_reg16_u16(VL53L4CD_THRESH_HIGH, high_mm)
+            .await?;
+        self.device
+            .write_reg16_u16(VL53L4CD_THRESH_LOW, low_mm)
+            .await?;
+        Ok(())
+    }
+
+    /// Raise the interrupt for every measurement again, as after
+    /// [`init`](Self::init).
+    pub async fn clear_detection_thresholds(&mut self) -> Result<(), E> {
+        self.device
+            .write_reg16_u8(VL53L4CD_SYSTEM_INTERRUPT, INTERRUPT_NEW_SAMPLE_READY)
+            .await?;
+        Ok(())
+    }
+
+    /// Read the detection thresholds back from the sensor, or `None` if the
+    /// interrupt is raised for every measurement.
+    pub async fn detection_thresholds(&mut self) -> Result<Option<DetectionThresholds>, E> {
+        let config = self.device.read_reg16_u8(VL53L4CD_SYSTEM_INTERRUPT).await?;
+        let Some(mode) = DetectionMode::from_config(config) else {
+            return Ok(None);
+        };
+        Ok(Some(DetectionThresholds {
+            low_mm: self.device.read_reg16_u16(VL53L4CD_THRESH_LOW).await?,
+            high_mm: self.device.read_reg16_u16(VL53L4CD_THRESH_HIGH).await?,
+            mode,
+        }))
+    }
+
+    /// Read why the interrupt is raised, or `None` if it is not.
+    pub async fn read_interrupt_source(&mut self) -> Result<Option<InterruptSource>, E> {
+        if !self.data_ready().await? {
+            return Ok(None);
+        }
+        let config = self.device.read_reg16_u8(VL53L4CD_SYSTEM_INTERRUPT).await?;
+        Ok(Some(match DetectionMode::from_config(config) {
+            Some(mode) => InterruptSource::Threshold(mode),
+            None => InterruptSource::DataReady,
+        }))
+    }
+
+    /// Wait until the GPIO1 interrupt line is asserted, without using the bus.
+    ///
+    /// GPIO1 is an open-drain output, active low as configured by
+    /// [`init`](Self::init), so it needs a pull-up. With a pin that wakes the
+    /// executor, the device can sleep until the interrupt.
+    pub async fn wait_for_interrupt<P: Wait>(
+        &self,
+        gpio1: &mut P,
+    ) -> core::result::Result<(), P::Error> {
+        gpio1.wait_for_low().await
+    }
+
+    /// Clear the interrupt flag.
+    pub async fn clear_interrupt(&mut self) -> Result<(), E> {
+        self.device
+            .write_reg16_u8(VL53L4CD_SYSTEM_INTERRUPT_CLEAR, 0x01)
+            .await?;
+        Ok(())
+    }
+
+    /// Read the distance measurement in millimeters and clear the interrupt.
+    ///
+    /// Returns `None` if the range status is not [`RangeStatus::Valid`]; use
+    /// [`read_measurement`](Self::read_measurement) to see why.
+    pub async fn read_distance(&mut self) -> Result<Option<u16>, E> {
+        let status = self
+            .device
+            .read_reg16_u8(VL53L4CD_RESULT_RANGE_STATUS)
+            .await?;
+        let distance = self
+            .device
+            .read_reg16_u16(VL53L4CD_RESULT_FINAL_CROSSTALK_CORRECTED_RANGE_MM_SD0)
+            .await?;
+        self.clear_interrupt().await?;
+
+        Ok(RangeStatus::from_raw(status).is_valid().then_some(distance))
+    }
+
+    /// Read the full measurement, valid or not, and clear the interrupt.
+    pub async fn read_measurement(&mut self) -> Result<DistanceMeasurement, E> {
+        let mut data = [0u8; RESULT_BLOCK_LEN];
+        self.device
+            .read_regs16(VL53L4CD_RESULT_RANGE_STATUS, &mut data)
+            .await?;
+        self.clear_interrupt().await?;
+        Ok(DistanceMeasurement::from_bytes(&data))
+    }
+
+    /// Set the range offset in millimeters (-1024 to 1023), added to every
+    /// distance.
+    pub async fn set_offset(&mut self, offset_mm: i16) -> Result<(), E> {
+        let raw = offset_register(offset_mm).ok_or(Error::InvalidParameter)?;
+        self.device
+            .write_reg16_u16(VL53L4CD_RANGE_OFFSET_MM, raw)
+            .await?;
+        self.device
+            .write_reg16_u16(VL53L4CD_INNER_OFFSET_MM, 0)
+            .await?;
+        self.device
+            .write_reg16_u16(VL53L4CD_OUTER_OFFSET_MM, 0)
+            .await?;
+        Ok(())
+    }
+
+    /// Read the range offset in millimeters back from the sensor.
+    pub async fn offset(&mut self) -> Result<i16, E> {
+        let raw = self.device.read_reg16_u16(VL53L4CD_RANGE_OFFSET_MM).await?;
+        Ok(offset_from_register(raw))
+    }
+
+    /// Set the crosstalk compensation in kcps per SPAD (0 to 128).
+    pub async fn set_crosstalk(&mut self, kcps: f32) -> Result<(), E> {
+        let raw = crosstalk_register(kcps).ok_or(Error::InvalidParameter)?;
+        self.device
+            .write_reg16_u16(VL53L4CD_XTALK_X_PLANE_GRADIENT_KCPS, 0)
+            .await?;
+        self.device
+            .write_reg16_u16(VL53L4CD_XTALK_Y_PLANE_GRADIENT_KCPS, 0)
+            .await?;
+        self.device
+            .write_reg16_u16(VL53L4CD_XTALK_PLANE_OFFSET_KCPS, raw)
+            .await?;
+        Ok(())
+    }
+
+    /// Read the crosstalk compensation in kcps per SPAD back from the sensor.
+    pub async fn crosstalk(&mut self) -> Result<f32, E> {
+        let raw = self
+            .device
+            .read_reg16_u16(VL53L4CD_XTALK_PLANE_OFFSET_KCPS)
+            .await?;
+        Ok(crosstalk_from_register(raw))
+    }
+
+    /// Measure and apply the range offset against a target at `target_mm`.
+    ///
+    /// See [`crate::Distance::calibrate_offset`].
+    pub async fn calibrate_offset<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        target_mm: u16,
+        samples: u8,
+    ) -> Result<i16, E> {
+        if !OFFSET_CALIBRATION_RANGE_MM.contains(&target_mm) || samples < MIN_CALIBRATION_SAMPLES {
+            return Err(Error::InvalidParameter);
+        }
+        self.set_offset(0).await?;
+        self.warm_up(delay).await?;
+
+        self.start_ranging().await?;
+        let mut total_mm = 0u32;
+        for _ in 0..samples {
+            total_mm += self.next_measurement(delay).await?.distance_mm as u32;
+        }
+        self.stop_ranging().await?;
+
+        let offset = calibrated_offset(target_mm, total_mm / samples as u32)?;
+        self.set_offset(offset).await?;
+        Ok(offset)
+    }
+
+    /// Measure and apply the crosstalk compensation against a scene with no
+    /// target.
+    ///
+    /// See [`crate::Distance::calibrate_crosstalk`].
+    pub async fn calibrate_crosstalk<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        samples: u8,
+    ) -> Result<f32, E> {
+        if samples < MIN_CALIBRATION_SAMPLES {
+            return Err(Error::InvalidParameter);
+        }
+        self.set_crosstalk(0.0).await?;
+        self.warm_up(delay).await?;
+
+        self.start_ranging().await?;
+        // The first measurement after starting is not reliable
+        self.next_measurement(delay).await?;
+        let mut total_kcps = 0.0;
+        for _ in 1..samples {
+            total_kcps += signal_per_spad(&self.next_measurement(delay).await?);
+        }
+        self.stop_ranging().await?;
+
+        let kcps = calibrated_crosstalk(total_kcps / (samples - 1) as f32)?;
+        self.set_crosstalk(kcps).await?;
+        Ok(kcps)
+    }
+
+    /// Range for a few measurements so the sensor settles before calibrating.
+    async fn warm_up<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), E> {
+        self.start_ranging().await?;
+        for _ in 0..CALIBRATION_WARM_UP_SAMPLES {
+            self.next_measurement(delay).await?;
+        }
+        self.stop_ranging().await
+    }
+
+    /// Wait for the next measurement while ranging and read it.
+    async fn next_measurement<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<DistanceMeasurement, E> {
+        self.wait_for_data(delay, CALIBRATION_TIMEOUT_MS).await?;
+        self.read_measurement().await
+    }
+
+    /// Read the range status of the last measurement.
+    pub async fn read_range_status(&mut self) -> Result<RangeStatus, E> {
+        let status = self
+            .device
+            .read_reg16_u8(VL53L4CD_RESULT_RANGE_STATUS)
+            .await?;
+        Ok(RangeStatus::from_raw(status))
+    }
+
+    /// Release the I2C bus.
+    pub fn release(self) -> I2C {
+        self.device.release()
+    }
+}
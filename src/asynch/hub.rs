@@ -0,0 +1,248 @@
+//! Async Modulino Hub driver.
+//!
+//! > [!WARNING]
+//! > **EXPERIMENTAL**: This driver is a work-in-progress and is based on the TCA9548A I2C
+//! > multiplexer (such as the SparkFun Qwiic Mux Breakout). It has NOT yet been tested on physical
+//! > Modulino Hub hardware, as the official Modulino Hub was never officially released.
+
+use crate::hub::{address_from_pins, subtract, DEFAULT_ADDRESS};
+use crate::{Result, ScanResult};
+use embedded_hal_async::i2c::{ErrorType, I2c, Operation};
+
+/// Async driver for the Modulino Hub (TCA9548A I2C multiplexer).
+///
+/// See [`crate::Hub`] for the blocking version.
+pub struct Hub<I2C> {
+    i2c: I2C,
+    address: u8,
+    control: Option<u8>,
+}
+
+impl<I2C, E> Hub<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Create a new Hub instance with the default address (0x70).
+    pub fn new(i2c: I2C) -> Self {
+        Self::new_with_address(i2c, DEFAULT_ADDRESS)
+    }
+
+    /// Create a new Hub instance with a custom address.
+    pub fn new_with_address(i2c: I2C, address: u8) -> Self {
+        Self {
+            i2c,
+            address,
+            control: None,
+        }
+    }
+
+    /// Create a new Hub instance from the levels of its A0-A2 address pins.
+    pub fn new_with_pins(i2c: I2C, a0: bool, a1: bool, a2: bool) -> Self {
+        Self::new_with_address(i2c, address_from_pins(a0, a1, a2))
+    }
+
+    /// Get the I2C address.
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// Select an active port channel (0 to 7).
+    ///
+    /// This enables the specified channel and disables all others.
+    pub async fn select(&mut self, port: u8) -> Result<(), E> {
+        if port >= 8 {
+            return Err(crate::Error::InvalidParameter);
+        }
+        self.write_control(1 << port)
+            .await
+            .map_err(crate::Error::I2c)
+    }
+
+    /// Enable several port channels at once.
+    ///
+    /// Bit `n` of `mask` enables port `n`; all other channels are disabled.
+    pub async fn select_mask(&mut self, mask: u8) -> Result<(), E> {
+        self.write_control(mask).await.map_err(crate::Error::I2c)
+    }
+
+    /// Clear/deselect all port channels.
+    pub async fn clear(&mut self) -> Result<(), E> {
+        self.write_control(0x00).await.map_err(crate::Error::I2c)
+    }
+
+    /// Read back the control register.
+    pub async fn read_control(&mut self) -> Result<u8, E> {
+        let mut buf = [0u8; 1];
+        match self.i2c.read(self.address, &mut buf).await {
+            Ok(()) => {
+                self.control = Some(buf[0]);
+                Ok(buf[0])
+            }
+            Err(e) => {
+                self.control = None;
+                Err(crate::Error::I2c(e))
+            }
+        }
+    }
+
+    /// List the devices behind a single port channel (0 to 7).
+    ///
+    /// See [`crate::Hub::scan_port`].
+    pub async fn scan_port(&mut self, port: u8) -> Result<ScanResult, E> {
+        if port >= 8 {
+            return Err(crate::Error::InvalidParameter);
+        }
+        let baseline = self.scan_baseline().await?;
+        let found = self.scan_behind(1 << port, &baseline).await?;
+        self.clear().await?;
+        Ok(found)
+    }
+
+    /// List the devices behind every port channel, indexed by port.
+    ///
+    /// See [`crate::Hub::scan_ports`].
+    pub async fn scan_ports(&mut self) -> Result<[ScanResult; 8], E> {
+        let baseline = self.scan_baseline().await?;
+        let mut results: [ScanResult; 8] = Default::default();
+        for (port, result) in results.iter_mut().enumerate() {
+            *result = self.scan_behind(1 << port, &baseline).await?;
+        }
+        self.clear().await?;
+        Ok(results)
+    }
+
+    async fn scan_baseline(&mut self) -> Result<ScanResult, E> {
+        self.clear().await?;
+        Ok(super::scan(&mut self.i2c).await)
+    }
+
+    async fn scan_behind(&mut self, mask: u8, baseline: &ScanResult) -> Result<ScanResult, E> {
+        self.select_mask(mask).await?;
+        let mut found = super::scan(&mut self.i2c).await;
+        subtract(&mut found, baseline);
+        Ok(found)
+    }
+
+    /// Select a port channel (0 to 7) unless it is already the only one selected.
+    pub async fn ensure_selected(&mut self, port: u8) -> Result<(), E> {
+        if port >= 8 {
+            return Err(crate::Error::InvalidParameter);
+        }
+        self.select_cached(port).await.map_err(crate::Error::I2c)
+    }
+
+    /// Forget the cached channel selection.
+    pub fn invalidate(&mut self) {
+        self.control = None;
+    }
+
+    /// Get a [`HubPort`] for one of the port channels (0 to 7).
+    pub fn port(&mut self, port: u8) -> Result<HubPort<'_, I2C>, E> {
+        HubPort::new(self, port)
+    }
+
+    async fn write_control(&mut self, control: u8) -> core::result::Result<(), E> {
+        match self.i2c.write(self.address, &[control]).await {
+            Ok(()) => {
+                self.control = Some(control);
+                Ok(())
+            }
+            Err(e) => {
+                self.control = None;
+                Err(e)
+            }
+        }
+    }
+
+    async fn select_cached(&mut self, port: u8) -> core::result::Result<(), E> {
+        let control = 1 << port;
+        if self.control == Some(control) {
+            return Ok(());
+        }
+        self.write_control(control).await
+    }
+
+    /// Release the I2C bus.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+}
+
+/// A helper representing a specific port of the Hub.
+///
+/// Implements the async `I2c` trait, selecting this port before each transaction.
+/// See [`crate::HubPort`] for the blocking version.
+pub struct HubPort<'a, I2C> {
+    hub: &'a mut Hub<I2C>,
+    port: u8,
+}
+
+impl<'a, I2C, E> HubPort<'a, I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Create a new HubPort.
+    ///
+    /// Returns [`Error::InvalidParameter`](crate::Error::InvalidParameter)
+    /// if `port` is not in the range 0 to 7.
+    pub fn new(hub: &'a mut Hub<I2C>, port: u8) -> Result<Self, E> {
+        if port >= 8 {
+            return Err(crate::Error::InvalidParameter);
+        }
+        Ok(Self { hub, port })
+    }
+
+    /// Get the port number.
+    pub fn port(&self) -> u8 {
+        self.port
+    }
+
+    /// Enable this port channel on the multiplexer.
+    pub async fn select(&mut self) -> Result<(), E> {
+        self.hub.select(self.port).await
+    }
+
+    /// Disable all port channels on the multiplexer.
+    pub async fn clear(&mut self) -> Result<(), E> {
+        self.hub.clear().await
+    }
+}
+
+impl<I2C: I2c> ErrorType for HubPort<'_, I2C> {
+    type Error = I2C::Error;
+}
+
+impl<I2C: I2c> I2c for HubPort<'_, I2C> {
+    async fn read(
+        &mut self,
+        address: u8,
+        read: &mut [u8],
+    ) -> core::result::Result<(), Self::Error> {
+        self.hub.select_cached(self.port).await?;
+        self.hub.i2c.read(address, read).await
+    }
+
+    async fn write(&mut self, address: u8, write: &[u8]) -> core::result::Result<(), Self::Error> {
+        self.hub.select_cached(self.port).await?;
+        self.hub.i2c.write(address, write).await
+    }
+
+    async fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> core::result::Result<(), Self::Error> {
+        self.hub.select_cached(self.port).await?;
+        self.hub.i2c.write_read(address, write, read).await
+    }
+
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> core::result::Result<(), Self::Error> {
+        self.hub.select_cached(self.port).await?;
+        self.hub.i2c.transaction(address, operations).await
+    }
+}
@@ -0,0 +1,141 @@
+//! Async Modulino Joystick driver.
+
+use super::{probe_addresses, I2cDevice};
+use crate::joystick::{angle, decode, magnitude};
+use crate::{addresses, Result};
+use embedded_hal_async::i2c::I2c;
+
+/// Async driver for the Modulino Joystick module.
+///
+/// See [`crate::Joystick`] for the blocking version.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use modulino::asynch::Joystick;
+///
+/// let mut joystick = Joystick::new(i2c).await?;
+///
+/// loop {
+///     if joystick.update().await? {
+///         let (x, y) = joystick.position();
+///     }
+/// }
+/// ```
+pub struct Joystick<I2C> {
+    device: I2cDevice<I2C>,
+    x: i8,
+    y: i8,
+    button_pressed: bool,
+    deadzone: u8,
+}
+
+impl<I2C, E> Joystick<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Default deadzone threshold.
+    pub const DEFAULT_DEADZONE: u8 = 26;
+
+    /// Create a new Joystick instance with the default address.
+    pub async fn new(i2c: I2C) -> Result<Self, E> {
+        Self::new_with_address(i2c, addresses::JOYSTICK).await
+    }
+
+    /// Discover if a Joystick module is connected.
+    ///
+    /// Probes the default/match addresses and returns the first one that ACKs.
+    ///
+    /// > [!WARNING]
+    /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
+    pub async fn discover(i2c: &mut I2C) -> Result<u8, E> {
+        probe_addresses(i2c, &[addresses::JOYSTICK]).await
+    }
+
+    /// Create a new Joystick instance with a custom address.
+    pub async fn new_with_address(i2c: I2C, address: u8) -> Result<Self, E> {
+        let mut joystick = Self {
+            device: I2cDevice::new(i2c, address),
+            x: 0,
+            y: 0,
+            button_pressed: false,
+            deadzone: Self::DEFAULT_DEADZONE,
+        };
+
+        // Read initial state
+        joystick.update().await?;
+
+        Ok(joystick)
+    }
+
+    /// Get the I2C address.
+    pub fn address(&self) -> u8 {
+        self.device.address
+    }
+
+    /// Update the joystick state.
+    ///
+    /// Returns `true` if the state has changed.
+    pub async fn update(&mut self) -> Result<bool, E> {
+        let previous_x = self.x;
+        let previous_y = self.y;
+        let previous_button = self.button_pressed;
+
+        let mut buf = [0u8; 4]; // 1 pinstrap + 2 axes + 1 button
+        self.device.read(&mut buf).await?;
+
+        (self.x, self.y, self.button_pressed) = decode(&buf, self.deadzone);
+
+        Ok(self.x != previous_x || self.y != previous_y || self.button_pressed != previous_button)
+    }
+
+    /// Get the X-axis value (-128 to 127).
+    pub fn x(&self) -> i8 {
+        self.x
+    }
+
+    /// Get the Y-axis value (-128 to 127).
+    pub fn y(&self) -> i8 {
+        self.y
+    }
+
+    /// Get both axis values as a tuple.
+    pub fn position(&self) -> (i8, i8) {
+        (self.x, self.y)
+    }
+
+    /// Check if the button is pressed.
+    pub fn button_pressed(&self) -> bool {
+        self.button_pressed
+    }
+
+    /// Get the deadzone threshold.
+    pub fn deadzone(&self) -> u8 {
+        self.deadzone
+    }
+
+    /// Set the deadzone threshold.
+    pub fn set_deadzone(&mut self, deadzone: u8) {
+        self.deadzone = deadzone;
+    }
+
+    /// Check if the joystick is in the center position (within deadzone).
+    pub fn is_centered(&self) -> bool {
+        self.x == 0 && self.y == 0
+    }
+
+    /// Get the magnitude of joystick displacement from center.
+    pub fn magnitude(&self) -> f32 {
+        magnitude(self.x, self.y)
+    }
+
+    /// Get the angle of joystick displacement in radians.
+    pub fn angle(&self) -> f32 {
+        angle(self.x, self.y)
+    }
+
+    /// Release the I2C bus.
+    pub fn release(self) -> I2C {
+        self.device.release()
+    }
+}
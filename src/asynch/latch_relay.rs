@@ -0,0 +1,104 @@
+//! Async Modulino Latch Relay driver.
+
+use super::{probe_addresses, I2cDevice};
+use crate::latch_relay::decode_state;
+use crate::{addresses, Result};
+use embedded_hal_async::i2c::I2c;
+
+/// Async driver for the Modulino Latch Relay module.
+///
+/// See [`crate::LatchRelay`] for the blocking version.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use modulino::asynch::LatchRelay;
+///
+/// let mut relay = LatchRelay::new(i2c)?;
+///
+/// relay.on().await?;
+/// if relay.is_on().await? == Some(true) {
+///     println!("Relay is ON");
+/// }
+/// ```
+pub struct LatchRelay<I2C> {
+    device: I2cDevice<I2C>,
+}
+
+impl<I2C, E> LatchRelay<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Create a new LatchRelay instance with the default address.
+    pub fn new(i2c: I2C) -> Result<Self, E> {
+        Self::new_with_address(i2c, addresses::LATCH_RELAY)
+    }
+
+    /// Discover if a LatchRelay module is connected.
+    ///
+    /// Probes the default/match addresses and returns the first one that ACKs.
+    ///
+    /// > [!WARNING]
+    /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
+    pub async fn discover(i2c: &mut I2C) -> Result<u8, E> {
+        probe_addresses(i2c, &[addresses::LATCH_RELAY]).await
+    }
+
+    /// Create a new LatchRelay instance with a custom address.
+    pub fn new_with_address(i2c: I2C, address: u8) -> Result<Self, E> {
+        let relay = Self {
+            device: I2cDevice::new(i2c, address),
+        };
+        Ok(relay)
+    }
+
+    /// Get the I2C address.
+    pub fn address(&self) -> u8 {
+        self.device.address
+    }
+
+    /// Turn the relay on.
+    pub async fn on(&mut self) -> Result<(), E> {
+        let data = [1u8, 0, 0];
+        self.device.write(&data).await?;
+        Ok(())
+    }
+
+    /// Turn the relay off.
+    pub async fn off(&mut self) -> Result<(), E> {
+        let data = [0u8, 0, 0];
+        self.device.write(&data).await?;
+        Ok(())
+    }
+
+    /// Set the relay state.
+    pub async fn set(&mut self, on: bool) -> Result<(), E> {
+        if on {
+            self.on().await
+        } else {
+            self.off().await
+        }
+    }
+
+    /// Toggle the relay state.
+    pub async fn toggle(&mut self) -> Result<(), E> {
+        match self.is_on().await? {
+            Some(true) => self.off().await,
+            _ => self.on().await,
+        }
+    }
+
+    /// Check if the relay is currently on.
+    ///
+    /// Returns `None` if the state is unknown (e.g., after power cycle before first command).
+    pub async fn is_on(&mut self) -> Result<Option<bool>, E> {
+        let mut buf = [0u8; 4]; // 1 pinstrap + 3 status
+        self.device.read(&mut buf).await?;
+        Ok(decode_state(&buf))
+    }
+
+    /// Release the I2C bus.
+    pub fn release(self) -> I2C {
+        self.device.release()
+    }
+}
@@ -0,0 +1,122 @@
+//! Async Modulino LED Matrix driver.
+
+use super::{probe_addresses, I2cDevice};
+use crate::led_matrix::{Frame, ModeSwitch};
+use crate::{addresses, DisplayMode, Error, Result};
+use embedded_hal_async::i2c::I2c;
+
+/// Async driver for the Modulino LED Matrix module.
+///
+/// See [`crate::LedMatrix`] for the blocking version.
+pub struct LedMatrix<I2C> {
+    device: I2cDevice<I2C>,
+    frame: Frame,
+}
+
+impl<I2C, E> LedMatrix<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Create a new LedMatrix instance with the default address.
+    pub fn new(i2c: I2C) -> Self {
+        Self::new_with_address(i2c, addresses::LED_MATRIX)
+    }
+
+    /// Discover if an LedMatrix module is connected.
+    ///
+    /// Probes the default/match addresses and returns the first one that ACKs.
+    ///
+    /// > [!WARNING]
+    /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
+    pub async fn discover(i2c: &mut I2C) -> Result<u8, E> {
+        probe_addresses(i2c, &[addresses::LED_MATRIX]).await
+    }
+
+    /// Create a new LedMatrix instance with a custom address.
+    pub fn new_with_address(i2c: I2C, address: u8) -> Self {
+        Self {
+            device: I2cDevice::new(i2c, address),
+            frame: Frame::new(),
+        }
+    }
+
+    /// Initialize the LED matrix and set it to default Monochromatic Vertical mode.
+    pub async fn init(&mut self) -> Result<(), E> {
+        self.set_mode(DisplayMode::MonochromaticVertical).await?;
+        Ok(())
+    }
+
+    /// Set the display mode for the LED matrix.
+    ///
+    /// This queries the current mode on the device and performs the appropriate
+    /// mode-switch payload write.
+    pub async fn set_mode(&mut self, mode: DisplayMode) -> Result<(), E> {
+        // Query the current mode from the device by reading 4 bytes (1 pinstrap + 3 identifier)
+        let mut query_buf = [0u8; 4];
+        self.device.read(&mut query_buf).await?;
+
+        let payload = ModeSwitch::new(&query_buf, mode);
+        self.device.write(payload.as_bytes()).await?;
+
+        self.frame.set_mode(mode);
+        Ok(())
+    }
+
+    /// Get the current display mode.
+    pub fn mode(&self) -> DisplayMode {
+        self.frame.mode
+    }
+
+    /// Turn off all LEDs and update the display.
+    pub async fn clear(&mut self) -> Result<(), E> {
+        self.frame.clear();
+        self.show().await
+    }
+
+    /// Set the brightness of a specific pixel (0-255).
+    ///
+    /// In Grayscale mode the value is mapped to 0-15; in monochromatic modes
+    /// any non-zero brightness turns the LED on.
+    pub fn set_pixel(&mut self, x: u8, y: u8, brightness: u8) -> Result<(), E> {
+        if !self.frame.set_pixel(x, y, brightness) {
+            return Err(Error::InvalidParameter);
+        }
+        Ok(())
+    }
+
+    /// Set the 4-bit grayscale value (0-15) of a specific pixel (Grayscale mode only).
+    pub fn set_grayscale_pixel(&mut self, x: u8, y: u8, value: u8) -> Result<(), E> {
+        if !self.frame.set_grayscale_pixel(x, y, value) {
+            return Err(Error::InvalidParameter);
+        }
+        Ok(())
+    }
+
+    /// Set the entire active frame from a slice and show it.
+    ///
+    /// Expects 12 bytes for monochromatic modes or 48 bytes for grayscale mode.
+    pub async fn set_frame(&mut self, frame: &[u8]) -> Result<(), E> {
+        if !self.frame.load(frame) {
+            return Err(Error::InvalidParameter);
+        }
+        self.show().await
+    }
+
+    /// Render the current local buffer to the display.
+    pub async fn show(&mut self) -> Result<(), E> {
+        let mut out = [0u8; 48];
+        let len = self.frame.render(&mut out);
+        self.device.write(&out[..len]).await?;
+        Ok(())
+    }
+
+    /// Get the current local buffer.
+    pub fn buffer(&self) -> &[u8] {
+        self.frame.buffer()
+    }
+
+    /// Release the I2C bus.
+    pub fn release(self) -> I2C {
+        self.device.release()
+    }
+}
@@ -0,0 +1,158 @@
+//! Async Modulino Light sensor driver.
+
+use super::probe_addresses;
+use crate::light::*;
+use crate::{addresses, Error, Gain, LightMeasurement, MeasurementRate, Resolution, Result};
+use embedded_hal_async::i2c::I2c;
+
+/// Async driver for the Modulino Light module (LTR-381RGB sensor).
+///
+/// See [`crate::Light`] for the blocking version.
+pub struct Light<I2C> {
+    i2c: I2C,
+    address: u8,
+    current_gain: Gain,
+    current_res: Resolution,
+}
+
+impl<I2C, E> Light<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Create a new Light instance.
+    pub fn new(i2c: I2C) -> Self {
+        Self::new_with_address(i2c, addresses::LIGHT)
+    }
+
+    /// Discover if a Light module is connected.
+    ///
+    /// Probes the default/match addresses and returns the first one that ACKs.
+    ///
+    /// > [!WARNING]
+    /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
+    pub async fn discover(i2c: &mut I2C) -> Result<u8, E> {
+        probe_addresses(i2c, &[addresses::LIGHT]).await
+    }
+
+    /// Create a new Light instance with a custom address.
+    pub fn new_with_address(i2c: I2C, address: u8) -> Self {
+        Self {
+            i2c,
+            address,
+            current_gain: Gain::Gain18x,
+            current_res: Resolution::Res16Bit,
+        }
+    }
+
+    /// Initialize the sensor.
+    pub async fn init(&mut self) -> Result<(), E> {
+        // Verify Part ID
+        let mut part_id = [0u8; 1];
+        self.i2c
+            .write_read(self.address, &[REG_PART_ID], &mut part_id)
+            .await
+            .map_err(Error::I2c)?;
+        if !part_id_matches(part_id[0]) {
+            return Err(Error::DeviceNotFound);
+        }
+
+        // Clear status
+        let mut status = [0u8; 1];
+        self.i2c
+            .write_read(self.address, &[REG_MAIN_STATUS], &mut status)
+            .await
+            .map_err(Error::I2c)?;
+
+        // Default config: 18x gain, 16-bit res, 25ms rate (Standard Arduino Modulino)
+        self.set_gain(Gain::Gain18x).await?;
+        self.set_config(Resolution::Res16Bit, MeasurementRate::Rate25ms)
+            .await?;
+
+        // Enable RGB mode
+        self.enable(true).await?;
+
+        Ok(())
+    }
+
+    /// Enable or disable the sensor measurements.
+    pub async fn enable(&mut self, enabled: bool) -> Result<(), E> {
+        self.i2c
+            .write(self.address, &[REG_MAIN_CTRL, main_ctrl_value(enabled)])
+            .await
+            .map_err(Error::I2c)
+    }
+
+    /// Set sensor gain.
+    pub async fn set_gain(&mut self, gain: Gain) -> Result<(), E> {
+        self.i2c
+            .write(self.address, &[REG_GAIN, gain as u8])
+            .await
+            .map_err(Error::I2c)?;
+        self.current_gain = gain;
+        Ok(())
+    }
+
+    /// Set ADC resolution and measurement rate.
+    pub async fn set_config(&mut self, res: Resolution, rate: MeasurementRate) -> Result<(), E> {
+        self.i2c
+            .write(self.address, &[REG_MEAS_RATE, meas_rate_value(res, rate)])
+            .await
+            .map_err(Error::I2c)?;
+        self.current_res = res;
+        Ok(())
+    }
+
+    /// Read the infrared channel value.
+    pub async fn ir(&mut self) -> Result<u32, E> {
+        self.read_channel(REG_DATA_IR).await
+    }
+
+    /// Read the raw ambient light level (Green channel).
+    pub async fn raw_lux(&mut self) -> Result<u32, E> {
+        self.read_channel(REG_DATA_GREEN).await
+    }
+
+    /// Read the calculated ambient light level in Lux.
+    pub async fn lux(&mut self) -> Result<f32, E> {
+        let ir = self.ir().await?;
+        let raw_lux = self.raw_lux().await?;
+        Ok(calculate_lux(
+            ir,
+            raw_lux,
+            self.current_gain,
+            self.current_res,
+        ))
+    }
+
+    /// Read all sensor values including calculated Lux.
+    pub async fn read(&mut self) -> Result<LightMeasurement, E> {
+        let ir = self.ir().await?;
+        let green = self.read_channel(REG_DATA_GREEN).await?;
+        let red = self.read_channel(REG_DATA_RED).await?;
+        let blue = self.read_channel(REG_DATA_BLUE).await?;
+
+        Ok(LightMeasurement {
+            ir,
+            green,
+            red,
+            blue,
+            raw_lux: green,
+            lux: calculate_lux(ir, green, self.current_gain, self.current_res),
+        })
+    }
+
+    async fn read_channel(&mut self, reg: u8) -> Result<u32, E> {
+        let mut buf = [0u8; 3];
+        self.i2c
+            .write_read(self.address, &[reg], &mut buf)
+            .await
+            .map_err(Error::I2c)?;
+
+        Ok(decode_channel(&buf))
+    }
+
+    /// Release the I2C bus.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+}
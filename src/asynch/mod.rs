@@ -0,0 +1,78 @@
+//! Async drivers built on [`embedded-hal-async`](https://crates.io/crates/embedded-hal-async).
+//!
+//! Every driver in this module mirrors its blocking counterpart in the crate root
+//! (same constructors, same method names), but all bus operations are `async fn`s.
+//! Register maps, payload encoding and measurement decoding are shared with the
+//! blocking drivers, so both variants talk to the hardware identically.
+//!
+//! Plain data types such as [`Color`](crate::Color), [`ButtonState`](crate::ButtonState)
+//! or [`MovementValues`](crate::MovementValues) are re-used from the crate root.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use modulino::asynch::Pixels;
+//! use modulino::Color;
+//!
+//! let mut pixels = Pixels::new(i2c)?;
+//! pixels.set_color(0, Color::RED, 50)?;
+//! pixels.show().await?;
+//! ```
+
+mod buttons;
+mod buzzer;
+mod distance;
+mod hub;
+mod i2c_device;
+mod joystick;
+mod knob;
+mod latch_relay;
+mod led_matrix;
+mod light;
+mod movement;
+mod opto_relay;
+mod pixels;
+mod pressure;
+mod scan;
+mod thermo;
+mod vibro;
+
+pub use buttons::Buttons;
+pub use buzzer::Buzzer;
+pub use distance::Distance;
+pub use hub::{Hub, HubPort};
+pub use i2c_device::I2cDevice;
+pub use joystick::Joystick;
+pub use knob::Knob;
+pub use latch_relay::LatchRelay;
+pub use led_matrix::LedMatrix;
+pub use light::Light;
+pub use movement::Movement;
+pub use opto_relay::OptoRelay;
+pub use pixels::Pixels;
+pub use pressure::Pressure;
+pub use scan::{identify, scan};
+pub use thermo::Thermo;
+pub use vibro::Vibro;
+
+use crate::{Error, Result};
+use embedded_hal_async::i2c::I2c;
+
+/// Async counterpart of [`Modulino::probe`](crate::Modulino::probe).
+///
+/// Returns the first address that ACKs a zero-length write, or the bus error
+/// for the first address if none do.
+pub(crate) async fn probe_addresses<I2C: I2c>(
+    i2c: &mut I2C,
+    addresses: &[u8],
+) -> Result<u8, I2C::Error> {
+    for &addr in addresses {
+        if i2c.write(addr, &[]).await.is_ok() {
+            return Ok(addr);
+        }
+    }
+    i2c.write(addresses[0], &[])
+        .await
+        .map(|_| addresses[0])
+        .map_err(Error::I2c)
+}
@@ -0,0 +1,123 @@
+//! Async Modulino Movement driver.
+
+use super::{probe_addresses, I2cDevice};
+use crate::movement::*;
+use crate::{addresses, Error, MovementValues, Result};
+use embedded_hal_async::i2c::I2c;
+
+/// Async driver for the Modulino Movement module (LSM6DSOX IMU).
+///
+/// See [`crate::Movement`] for the blocking version.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use modulino::asynch::Movement;
+///
+/// let mut movement = Movement::new(i2c).await?;
+///
+/// let accel = movement.acceleration().await?;
+/// let gyro = movement.angular_velocity().await?;
+/// ```
+pub struct Movement<I2C> {
+    device: I2cDevice<I2C>,
+    accel_sensitivity: f32,
+    gyro_sensitivity: f32,
+}
+
+impl<I2C, E> Movement<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Create a new Movement instance with the default address (0x6A).
+    pub async fn new(i2c: I2C) -> Result<Self, E> {
+        Self::new_with_address(i2c, addresses::MOVEMENT[0]).await
+    }
+
+    /// Discover if a Movement module is connected.
+    ///
+    /// Probes the default/match addresses and returns the first one that ACKs.
+    ///
+    /// > [!WARNING]
+    /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
+    pub async fn discover(i2c: &mut I2C) -> Result<u8, E> {
+        probe_addresses(i2c, &addresses::MOVEMENT).await
+    }
+
+    /// Create a new Movement instance with a custom address.
+    ///
+    /// Valid addresses are 0x6A or 0x6B depending on the SA0 pin configuration.
+    pub async fn new_with_address(i2c: I2C, address: u8) -> Result<Self, E> {
+        let mut movement = Self {
+            device: I2cDevice::new(i2c, address),
+            accel_sensitivity: ACCEL_SENSITIVITY,
+            gyro_sensitivity: GYRO_SENSITIVITY,
+        };
+
+        // Verify device identity
+        let who_am_i = movement.device.read_reg(LSM6DSOX_WHO_AM_I).await?;
+        if who_am_i != LSM6DSOX_WHO_AM_I_VALUE {
+            return Err(Error::DeviceNotFound);
+        }
+
+        // Initialize with default settings
+        movement.init().await?;
+
+        Ok(movement)
+    }
+
+    /// Get the I2C address.
+    pub fn address(&self) -> u8 {
+        self.device.address
+    }
+
+    /// Initialize the sensor with default settings.
+    async fn init(&mut self) -> Result<(), E> {
+        for (reg, value) in INIT_SEQUENCE {
+            self.device.write_reg(reg, value).await?;
+        }
+        self.accel_sensitivity = ACCEL_SENSITIVITY;
+        self.gyro_sensitivity = GYRO_SENSITIVITY;
+
+        Ok(())
+    }
+
+    /// Read acceleration values.
+    ///
+    /// Returns acceleration in g (gravitational units).
+    pub async fn acceleration(&mut self) -> Result<MovementValues, E> {
+        let mut buf = [0u8; 6];
+        self.device.read_regs(LSM6DSOX_OUTX_L_A, &mut buf).await?;
+        Ok(decode_axes(&buf, self.accel_sensitivity))
+    }
+
+    /// Get the magnitude of acceleration.
+    pub async fn acceleration_magnitude(&mut self) -> Result<f32, E> {
+        Ok(self.acceleration().await?.magnitude())
+    }
+
+    /// Read angular velocity (gyroscope) values.
+    ///
+    /// Returns angular velocity in degrees per second (dps).
+    pub async fn angular_velocity(&mut self) -> Result<MovementValues, E> {
+        let mut buf = [0u8; 6];
+        self.device.read_regs(LSM6DSOX_OUTX_L_G, &mut buf).await?;
+        Ok(decode_axes(&buf, self.gyro_sensitivity))
+    }
+
+    /// Alias for `angular_velocity()`.
+    pub async fn gyro(&mut self) -> Result<MovementValues, E> {
+        self.angular_velocity().await
+    }
+
+    /// Check if new data is available.
+    pub async fn data_ready(&mut self) -> Result<bool, E> {
+        let status = self.device.read_reg(LSM6DSOX_STATUS_REG).await?;
+        Ok(status_data_ready(status))
+    }
+
+    /// Release the I2C bus.
+    pub fn release(self) -> I2C {
+        self.device.release()
+    }
+}
@@ -0,0 +1,254 @@
+//! Async Modulino Pixels driver.
+
+use super::{probe_addresses, I2cDevice};
+use crate::pixels::{Frame, NUM_LEDS};
+use crate::power::{ua_to_ma, PowerScale};
+use crate::{addresses, Color, ColorCorrection, Error, PowerModel, Result};
+use embedded_hal_async::i2c::I2c;
+
+/// Async driver for the Modulino Pixels module.
+///
+/// See [`crate::Pixels`] for the blocking version. Only [`show`](Self::show)
+/// touches the bus; all setters update the local frame buffer.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use modulino::asynch::Pixels;
+/// use modulino::Color;
+///
+/// let mut pixels = Pixels::new(i2c)?;
+///
+/// pixels.set_all_color(Color::BLUE, 25);
+/// pixels.show().await?;
+/// ```
+pub struct Pixels<I2C> {
+    device: I2cDevice<I2C>,
+    frame: Frame,
+    correction: Option<ColorCorrection>,
+    power_model: PowerModel,
+    power_budget_ma: Option<u16>,
+}
+
+impl<I2C, E> Pixels<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Create a new Pixels instance with the default address.
+    pub fn new(i2c: I2C) -> Result<Self, E> {
+        Self::new_with_address(i2c, addresses::PIXELS)
+    }
+
+    /// Discover if a Pixels module is connected.
+    ///
+    /// Probes the default/match addresses and returns the first one that ACKs.
+    ///
+    /// > [!WARNING]
+    /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
+    pub async fn discover(i2c: &mut I2C) -> Result<u8, E> {
+        probe_addresses(i2c, &[addresses::PIXELS]).await
+    }
+
+    /// Create a new Pixels instance with a custom address.
+    pub fn new_with_address(i2c: I2C, address: u8) -> Result<Self, E> {
+        // The frame starts with all LEDs cleared
+        Ok(Self {
+            device: I2cDevice::new(i2c, address),
+            frame: Frame::new(),
+            correction: None,
+            power_model: PowerModel::APA102,
+            power_budget_ma: None,
+        })
+    }
+
+    /// Get the I2C address.
+    pub fn address(&self) -> u8 {
+        self.device.address
+    }
+
+    /// Set the color of a specific LED.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - LED index (0-7)
+    /// * `color` - The color to set
+    /// * `brightness` - Brightness level (0-100)
+    pub fn set_color(
+        &mut self,
+        index: usize,
+        color: Color,
+        brightness: u8,
+    ) -> Result<&mut Self, E> {
+        if !self.frame.set_color(index, color, brightness) {
+            return Err(Error::OutOfRange);
+        }
+        Ok(self)
+    }
+
+    /// Set the color of a specific LED using RGB values.
+    pub fn set_rgb(
+        &mut self,
+        index: usize,
+        r: u8,
+        g: u8,
+        b: u8,
+        brightness: u8,
+    ) -> Result<&mut Self, E> {
+        self.set_color(index, Color::new(r, g, b), brightness)
+    }
+
+    /// Set the brightness of a specific LED without changing its color.
+    pub fn set_brightness(&mut self, index: usize, brightness: u8) -> Result<&mut Self, E> {
+        if !self.frame.set_brightness(index, brightness) {
+            return Err(Error::OutOfRange);
+        }
+        Ok(self)
+    }
+
+    /// Set the color of all LEDs.
+    pub fn set_all_color(&mut self, color: Color, brightness: u8) -> &mut Self {
+        self.frame
+            .set_range_color(0, NUM_LEDS - 1, color, brightness);
+        self
+    }
+
+    /// Set all LEDs to the same RGB color.
+    pub fn set_all_rgb(&mut self, r: u8, g: u8, b: u8, brightness: u8) -> &mut Self {
+        self.set_all_color(Color::new(r, g, b), brightness)
+    }
+
+    /// Set the color of a range of LEDs (both ends inclusive).
+    pub fn set_range_color(
+        &mut self,
+        from: usize,
+        to: usize,
+        color: Color,
+        brightness: u8,
+    ) -> &mut Self {
+        self.frame.set_range_color(from, to, color, brightness);
+        self
+    }
+
+    /// Set the brightness of all LEDs without changing their colors.
+    pub fn set_all_brightness(&mut self, brightness: u8) -> &mut Self {
+        for i in 0..NUM_LEDS {
+            self.frame.set_brightness(i, brightness);
+        }
+        self
+    }
+
+    /// Clear (turn off) a specific LED.
+    pub fn clear(&mut self, index: usize) -> Result<&mut Self, E> {
+        self.set_color(index, Color::BLACK, 0)
+    }
+
+    /// Clear a range of LEDs.
+    pub fn clear_range(&mut self, from: usize, to: usize) -> &mut Self {
+        self.set_range_color(from, to, Color::BLACK, 0)
+    }
+
+    /// Clear all LEDs.
+    pub fn clear_all(&mut self) -> &mut Self {
+        self.set_all_color(Color::BLACK, 0)
+    }
+
+    /// Get the color of a specific LED, or `None` if `index` is out of range.
+    pub fn get_color(&self, index: usize) -> Option<Color> {
+        self.frame.color(index)
+    }
+
+    /// Get the brightness (0-100) of a specific LED, or `None` if `index` is
+    /// out of range.
+    pub fn get_brightness(&self, index: usize) -> Option<u8> {
+        self.frame.brightness(index)
+    }
+
+    /// Set the color correction applied by [`show`](Self::show).
+    pub fn set_correction(&mut self, correction: Option<ColorCorrection>) -> &mut Self {
+        self.correction = correction;
+        self
+    }
+
+    /// Get the current color correction.
+    pub fn correction(&self) -> Option<&ColorCorrection> {
+        self.correction.as_ref()
+    }
+
+    /// Set the model used to estimate the current drawn by the LEDs.
+    pub fn set_power_model(&mut self, model: PowerModel) -> &mut Self {
+        self.power_model = model;
+        self
+    }
+
+    /// Get the power model.
+    pub fn power_model(&self) -> &PowerModel {
+        &self.power_model
+    }
+
+    /// Limit the estimated current to `budget_ma` milliamps, or `None` for no limit.
+    pub fn set_power_budget(&mut self, budget_ma: Option<u16>) -> &mut Self {
+        self.power_budget_ma = budget_ma;
+        self
+    }
+
+    /// Get the power budget in milliamps.
+    pub fn power_budget(&self) -> Option<u16> {
+        self.power_budget_ma
+    }
+
+    /// Estimated current drawn by the current LED states, in milliamps,
+    /// before the power budget is applied.
+    pub fn estimated_current_ma(&self) -> u32 {
+        ua_to_ma(
+            self.frame
+                .current_ua(self.correction.as_ref(), &self.power_model),
+        )
+    }
+
+    /// Render the frame within the power budget and write it, unless
+    /// `only_if_changed` is set and the module already shows it.
+    async fn write_frame(&mut self, only_if_changed: bool) -> Result<bool, E> {
+        let scale = self.power_budget_ma.and_then(|budget| {
+            let draw = self
+                .frame
+                .current_ua(self.correction.as_ref(), &self.power_model);
+            let idle = self.power_model.idle_ua() * NUM_LEDS as u32;
+            PowerScale::for_budget(draw, idle, budget)
+        });
+        self.frame.render(self.correction.as_ref(), scale);
+        if only_if_changed && !self.frame.is_changed() {
+            return Ok(false);
+        }
+        self.device.write(self.frame.as_bytes()).await?;
+        self.frame.mark_sent();
+        Ok(true)
+    }
+
+    /// Apply the current LED states to the hardware.
+    pub async fn show(&mut self) -> Result<(), E> {
+        self.write_frame(false).await?;
+        Ok(())
+    }
+
+    /// Apply the current LED states only if they differ from the last ones
+    /// written. Returns `true` if the module was written.
+    pub async fn show_if_changed(&mut self) -> Result<bool, E> {
+        self.write_frame(true).await
+    }
+
+    /// Set a color and immediately show it.
+    pub async fn set_color_show(
+        &mut self,
+        index: usize,
+        color: Color,
+        brightness: u8,
+    ) -> Result<(), E> {
+        self.set_color(index, color, brightness)?;
+        self.show().await
+    }
+
+    /// Release the I2C bus.
+    pub fn release(self) -> I2C {
+        self.device.release()
+    }
+}
@@ -0,0 +1,96 @@
+//! Async Modulino Pressure driver.
+//!
+//! > [!WARNING]
+//! > **EXPERIMENTAL**: This driver is a work-in-progress and has only been verified via
+//! > unit tests using I2C mocks. It has NOT yet been tested on physical Modulino hardware.
+
+use super::probe_addresses;
+use crate::pressure::*;
+use crate::{addresses, Error, Result};
+use embedded_hal_async::i2c::I2c;
+
+/// Async driver for the Modulino Pressure module (LPS22HB sensor).
+///
+/// See [`crate::Pressure`] for the blocking version.
+pub struct Pressure<I2C> {
+    i2c: I2C,
+    address: u8,
+}
+
+impl<I2C, E> Pressure<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Create a new Pressure instance.
+    pub fn new(i2c: I2C) -> Self {
+        Self {
+            i2c,
+            address: addresses::PRESSURE,
+        }
+    }
+
+    /// Discover if a Pressure module is connected.
+    ///
+    /// Probes the default/match addresses and returns the first one that ACKs.
+    ///
+    /// > [!WARNING]
+    /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
+    pub async fn discover(i2c: &mut I2C) -> Result<u8, E> {
+        probe_addresses(i2c, &[addresses::PRESSURE]).await
+    }
+
+    /// Initialize the sensor.
+    pub async fn init(&mut self) -> Result<(), E> {
+        let mut id = [0u8; 1];
+        self.i2c
+            .write_read(self.address, &[REG_WHO_AM_I], &mut id)
+            .await
+            .map_err(Error::I2c)?;
+        if id[0] != WHO_AM_I_VALUE {
+            return Err(Error::DeviceNotFound);
+        }
+
+        // Set ODR to 10Hz, enable block data update
+        self.i2c
+            .write(self.address, &[REG_CTRL_REG1, 0x22])
+            .await
+            .map_err(Error::I2c)?;
+
+        Ok(())
+    }
+
+    /// Read atmospheric pressure in hPa.
+    pub async fn pressure(&mut self) -> Result<f32, E> {
+        let mut buf = [0u8; 3];
+        self.i2c
+            .write_read(self.address, &[REG_OUT_P_XL], &mut buf)
+            .await
+            .map_err(Error::I2c)?;
+
+        Ok(decode_pressure(&buf))
+    }
+
+    /// Read ambient temperature in degrees Celsius.
+    pub async fn temperature(&mut self) -> Result<f32, E> {
+        let mut buf = [0u8; 2];
+        self.i2c
+            .write_read(self.address, &[REG_OUT_T_L], &mut buf)
+            .await
+            .map_err(Error::I2c)?;
+
+        Ok(decode_temperature(&buf))
+    }
+
+    /// Perform a software reset.
+    pub async fn reset(&mut self) -> Result<(), E> {
+        self.i2c
+            .write(self.address, &[REG_CTRL_REG2, 0x04])
+            .await
+            .map_err(Error::I2c)
+    }
+
+    /// Release the I2C bus.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+}
@@ -0,0 +1,61 @@
+//! Async I2C bus enumeration.
+
+use crate::scan::{is_hub_address, Identity, SENSORS};
+use crate::{ModulinoKind, ScanResult, SCAN_RANGE};
+use embedded_hal_async::i2c::I2c;
+
+/// Scan the bus and identify every Modulino on it.
+///
+/// See [`crate::scan`] for the blocking version and the identification rules.
+///
+/// > [!WARNING]
+/// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
+pub async fn scan<I2C: I2c>(i2c: &mut I2C) -> ScanResult {
+    let mut result = ScanResult::new();
+    for address in SCAN_RANGE {
+        if i2c.write(address, &[]).await.is_ok() {
+            result.insert(address, identify(i2c, address).await);
+        }
+    }
+    result
+}
+
+/// Identify the device that ACKed at `address`.
+///
+/// See [`crate::scan`] for the order of checks.
+pub async fn identify<I2C: I2c>(i2c: &mut I2C, address: u8) -> ModulinoKind {
+    for (addr, kind, identity) in SENSORS {
+        if addr != address {
+            continue;
+        }
+        let matches = match identity {
+            Identity::Address => true,
+            Identity::Reg8 { reg, mask, value } => {
+                let mut buf = [0u8; 1];
+                i2c.write_read(address, &[reg], &mut buf).await.is_ok() && buf[0] & mask == value
+            }
+            Identity::Reg16 { reg, value } => {
+                let mut buf = [0u8; 2];
+                i2c.write_read(address, &reg.to_be_bytes(), &mut buf)
+                    .await
+                    .is_ok()
+                    && u16::from_be_bytes(buf) == value
+            }
+        };
+        if matches {
+            return kind;
+        }
+    }
+
+    if is_hub_address(address) {
+        return ModulinoKind::Hub;
+    }
+
+    let mut buf = [0u8; 1];
+    if i2c.read(address, &mut buf).await.is_ok() {
+        if let Some(kind) = ModulinoKind::from_pinstrap(buf[0]) {
+            return kind;
+        }
+    }
+    ModulinoKind::Unknown
+}
@@ -0,0 +1,91 @@
+//! Async Modulino Thermo driver.
+//!
+//! The `hs3003` crate is blocking-only, so this driver talks to the HS3003
+//! directly: a measurement request, a wait, then a 4-byte read.
+
+use super::probe_addresses;
+use crate::thermo::MEASUREMENT_TIME_MS;
+use crate::{addresses, Error, Result, ThermoMeasurement};
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::i2c::I2c;
+
+/// Async driver for the Modulino Thermo module (HS3003 sensor).
+///
+/// See [`crate::Thermo`] for the blocking version.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use modulino::asynch::Thermo;
+///
+/// let mut thermo = Thermo::new(i2c);
+///
+/// let measurement = thermo.read(&mut delay).await?;
+/// println!("Temperature: {:.1}°C", measurement.temperature);
+/// ```
+pub struct Thermo<I2C> {
+    i2c: I2C,
+}
+
+impl<I2C, E> Thermo<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Create a new Thermo instance.
+    ///
+    /// The HS3003 sensor has a fixed I2C address of 0x44.
+    pub fn new(i2c: I2C) -> Self {
+        Self { i2c }
+    }
+
+    /// Discover if a Thermo module is connected.
+    ///
+    /// Probes the default/match addresses and returns the first one that ACKs.
+    ///
+    /// > [!WARNING]
+    /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
+    pub async fn discover(i2c: &mut I2C) -> Result<u8, E> {
+        probe_addresses(i2c, &[addresses::THERMO]).await
+    }
+
+    /// Get the I2C address.
+    ///
+    /// The HS3003 has a fixed address of 0x44.
+    pub fn address(&self) -> u8 {
+        addresses::THERMO
+    }
+
+    /// Read temperature and humidity.
+    ///
+    /// Triggers a measurement, waits for it to complete and reads the result.
+    pub async fn read<D: DelayNs>(&mut self, delay: &mut D) -> Result<ThermoMeasurement, E> {
+        self.i2c
+            .write(addresses::THERMO, &[0x00])
+            .await
+            .map_err(Error::I2c)?;
+        delay.delay_ms(MEASUREMENT_TIME_MS).await;
+
+        let mut buf = [0u8; 4];
+        self.i2c
+            .read(addresses::THERMO, &mut buf)
+            .await
+            .map_err(Error::I2c)?;
+
+        Ok(ThermoMeasurement::from_bytes(&buf))
+    }
+
+    /// Read temperature only.
+    pub async fn temperature<D: DelayNs>(&mut self, delay: &mut D) -> Result<f32, E> {
+        Ok(self.read(delay).await?.temperature)
+    }
+
+    /// Read humidity only.
+    pub async fn humidity<D: DelayNs>(&mut self, delay: &mut D) -> Result<f32, E> {
+        Ok(self.read(delay).await?.humidity)
+    }
+
+    /// Release the I2C bus.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+}
@@ -0,0 +1,120 @@
+//! Async Modulino Vibro driver.
+
+use super::{probe_addresses, I2cDevice};
+use crate::vibro::on_payload;
+use crate::{addresses, PowerLevel, Result};
+use embedded_hal_async::i2c::I2c;
+
+/// Async driver for the Modulino Vibro module.
+///
+/// See [`crate::Vibro`] for the blocking version.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use modulino::asynch::Vibro;
+/// use modulino::PowerLevel;
+///
+/// let mut vibro = Vibro::new(i2c).await?;
+///
+/// vibro.on(500, PowerLevel::Medium).await?;
+/// ```
+pub struct Vibro<I2C> {
+    device: I2cDevice<I2C>,
+    frequency: u32,
+}
+
+impl<I2C, E> Vibro<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Default vibration frequency in Hz.
+    pub const DEFAULT_FREQUENCY: u32 = 1000;
+
+    /// Create a new Vibro instance with the default address.
+    pub async fn new(i2c: I2C) -> Result<Self, E> {
+        Self::new_with_address(i2c, addresses::VIBRO).await
+    }
+
+    /// Discover if a Vibro module is connected.
+    ///
+    /// Probes the default/match addresses and returns the first one that ACKs.
+    ///
+    /// > [!WARNING]
+    /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
+    pub async fn discover(i2c: &mut I2C) -> Result<u8, E> {
+        probe_addresses(i2c, &[addresses::VIBRO]).await
+    }
+
+    /// Create a new Vibro instance with a custom address.
+    pub async fn new_with_address(i2c: I2C, address: u8) -> Result<Self, E> {
+        let mut vibro = Self {
+            device: I2cDevice::new(i2c, address),
+            frequency: Self::DEFAULT_FREQUENCY,
+        };
+
+        // Ensure motor is off on init
+        vibro.off().await?;
+
+        Ok(vibro)
+    }
+
+    /// Get the I2C address.
+    pub fn address(&self) -> u8 {
+        self.device.address
+    }
+
+    /// Get the current frequency setting.
+    pub fn frequency(&self) -> u32 {
+        self.frequency
+    }
+
+    /// Set the vibration frequency.
+    pub fn set_frequency(&mut self, frequency: u32) {
+        self.frequency = frequency;
+    }
+
+    /// Turn on the vibration motor.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration_ms` - Duration in milliseconds (0xFFFF for indefinite)
+    /// * `power` - Power level
+    pub async fn on(&mut self, duration_ms: u16, power: PowerLevel) -> Result<(), E> {
+        self.on_with_power(duration_ms, power.value()).await
+    }
+
+    /// Turn on the vibration motor with a custom power level (0-100).
+    pub async fn on_with_power(&mut self, duration_ms: u16, power: u8) -> Result<(), E> {
+        let data = on_payload(self.frequency, duration_ms, power);
+        self.device.write(&data).await?;
+        Ok(())
+    }
+
+    /// Turn on the vibration motor indefinitely.
+    pub async fn on_continuous(&mut self, power: PowerLevel) -> Result<(), E> {
+        self.on(0xFFFF, power).await
+    }
+
+    /// Turn off the vibration motor.
+    pub async fn off(&mut self) -> Result<(), E> {
+        let data = [0u8; 8];
+        self.device.write(&data).await?;
+        Ok(())
+    }
+
+    /// Alias for `off()`.
+    pub async fn stop(&mut self) -> Result<(), E> {
+        self.off().await
+    }
+
+    /// Vibrate in a pattern (pulse).
+    pub async fn pulse(&mut self, on_ms: u16, power: PowerLevel) -> Result<(), E> {
+        self.on(on_ms, power).await
+    }
+
+    /// Release the I2C bus.
+    pub fn release(self) -> I2C {
+        self.device.release()
+    }
+}
@@ -0,0 +1,312 @@
+//! Level widgets for the Modulino Pixels: bar graphs, gauges and meters.
+
+use crate::pixels::NUM_LEDS;
+use crate::{Color, Palette, PixelStrip, Pixels, Result};
+use embedded_hal::i2c::I2c;
+
+const DEFAULT_BRIGHTNESS: u8 = 50;
+
+/// How a [`BarGraph`] shows its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BarStyle {
+    /// LEDs fill from the first one up to the value.
+    #[default]
+    Bar,
+    /// A single dot marks the value, like a gauge needle.
+    Dot,
+    /// LEDs fill from the middle: up for positive values, down for negative.
+    CenterZero,
+}
+
+/// A widget that shows a value in a range on a row of LEDs.
+///
+/// Each LED takes its color from the palette at its position along the bar,
+/// so a palette like [`Palette::LEVEL`] gives a green to red gradient. With
+/// anti-aliasing, the LED at the edge of the bar is blended with the
+/// background in proportion to how much of it is covered, so the bar moves
+/// smoothly instead of jumping one LED at a time. An optional peak marker
+/// stays on the highest level for a while.
+///
+/// Widgets are sized for one module by default. For a longer
+/// [`PixelStrip`], create one with [`for_strip`](Self::for_strip).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use modulino::{BarGraph, Joystick, Pixels};
+///
+/// let mut pixels = Pixels::new(pixels_i2c)?;
+///
+/// // Distance from 0 to 1 m
+/// let mut bar = BarGraph::new(0.0, 1000.0);
+/// bar.draw(&mut pixels, distance_mm as f32, now_ms())?;
+///
+/// // Joystick X, filling left or right of the middle
+/// let mut x = BarGraph::center_zero(-128.0, 127.0);
+/// x.draw(&mut pixels, joystick.x() as f32, now_ms())?;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarGraph<'a, const N: usize = NUM_LEDS> {
+    min: f32,
+    max: f32,
+    style: BarStyle,
+    palette: Palette<'a>,
+    background: Color,
+    anti_aliasing: bool,
+    brightness: u8,
+    peak_hold_ms: Option<u32>,
+    /// The far edge of the highest bar, in LEDs, and when it was reached.
+    peak: Option<(f32, u32)>,
+}
+
+impl BarGraph<'_> {
+    /// A bar for one Pixels module filling from the first LED, with a green to
+    /// red gradient and anti-aliasing.
+    pub fn new(min: f32, max: f32) -> Self {
+        Self::for_strip(min, max)
+    }
+
+    /// A gauge for one Pixels module: a single anti-aliased dot.
+    pub fn gauge(min: f32, max: f32) -> Self {
+        Self::new(min, max)
+            .with_style(BarStyle::Dot)
+            .with_palette(Color::CYAN)
+    }
+
+    /// A VU meter for one Pixels module: a green to red bar with a peak marker
+    /// held for a second.
+    pub fn vu_meter(min: f32, max: f32) -> Self {
+        Self::new(min, max).with_peak_hold(1000)
+    }
+
+    /// A center-zero bar for one Pixels module, for signed values.
+    ///
+    /// `min` is shown at the first LED and `max` at the last, with zero in the
+    /// middle.
+    pub fn center_zero(min: f32, max: f32) -> Self {
+        Self::new(min, max).with_style(BarStyle::CenterZero)
+    }
+
+    /// Show `value` on `pixels`, writing the module only if the LEDs change.
+    ///
+    /// Returns `true` if the module was written.
+    pub fn draw<I2C, E>(
+        &mut self,
+        pixels: &mut Pixels<I2C>,
+        value: f32,
+        now_ms: u32,
+    ) -> Result<bool, E>
+    where
+        I2C: I2c<Error = E>,
+    {
+        for (index, color) in self.render(value, now_ms).iter().enumerate() {
+            pixels.set_color(index, *color, self.brightness)?;
+        }
+        pixels.show_if_changed()
+    }
+}
+
+impl<'a, const N: usize> BarGraph<'a, N> {
+    /// A bar of `N` LEDs filling from the first LED, with a green to red
+    /// gradient and anti-aliasing.
+    pub fn for_strip(min: f32, max: f32) -> Self {
+        Self {
+            min,
+            max,
+            style: BarStyle::Bar,
+            palette: Palette::LEVEL,
+            background: Color::BLACK,
+            anti_aliasing: true,
+            brightness: DEFAULT_BRIGHTNESS,
+            peak_hold_ms: None,
+            peak: None,
+        }
+    }
+
+    /// Set the style.
+    pub fn with_style(mut self, style: BarStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the colors along the bar: a [`Palette`], a single [`Color`] or a
+    /// slice of colors.
+    ///
+    /// For [`BarStyle::CenterZero`], the palette runs from the middle outwards.
+    pub fn with_palette(mut self, palette: impl Into<Palette<'a>>) -> Self {
+        self.palette = palette.into();
+        self
+    }
+
+    /// Set the color of unlit LEDs.
+    pub fn with_background(mut self, background: Color) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Enable or disable blending of partly covered LEDs.
+    pub fn with_anti_aliasing(mut self, enabled: bool) -> Self {
+        self.anti_aliasing = enabled;
+        self
+    }
+
+    /// Set the brightness passed to the LEDs (0-100).
+    pub fn with_brightness(mut self, brightness: u8) -> Self {
+        self.brightness = brightness.min(100);
+        self
+    }
+
+    /// Mark the highest level for `hold_ms` milliseconds, or disable the
+    /// marker with `None`.
+    pub fn with_peak_hold(mut self, hold_ms: impl Into<Option<u32>>) -> Self {
+        self.peak_hold_ms = hold_ms.into();
+        self.peak = None;
+        self
+    }
+
+    /// The style.
+    pub fn style(&self) -> BarStyle {
+        self.style
+    }
+
+    /// The range of values shown.
+    pub fn range(&self) -> (f32, f32) {
+        (self.min, self.max)
+    }
+
+    /// The brightness (0-100).
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Forget the peak.
+    pub fn reset_peak(&mut self) {
+        self.peak = None;
+    }
+
+    /// The colors that show `value` at `now_ms`.
+    ///
+    /// `now_ms` is only used for the peak marker and may wrap around.
+    pub fn render(&mut self, value: f32, now_ms: u32) -> [Color; N] {
+        let mut frame = [self.background; N];
+        if N == 0 {
+            return frame;
+        }
+        let n = N as f32;
+        let middle = n / 2.0;
+
+        // The lit span, in LEDs
+        let edge = self.edge(value);
+        let (from, to) = match self.style {
+            BarStyle::Bar => (0.0, edge),
+            BarStyle::Dot => (edge, edge + 1.0),
+            BarStyle::CenterZero if edge < middle => (edge, middle),
+            BarStyle::CenterZero => (middle, edge),
+        };
+
+        for (i, led) in frame.iter_mut().enumerate() {
+            let start = i as f32;
+            let mut coverage = (to.min(start + 1.0) - from.max(start)).clamp(0.0, 1.0);
+            if !self.anti_aliasing {
+                coverage = if coverage >= 0.5 { 1.0 } else { 0.0 };
+            }
+            if coverage > 0.0 {
+                let alpha = libm::roundf(coverage * 255.0) as u8;
+                *led = self.background.blend(self.color_at(i), alpha);
+            }
+        }
+
+        if let Some(peak) = self.update_peak(edge, now_ms) {
+            let index = match self.style {
+                BarStyle::Dot => Some(libm::roundf(peak) as usize),
+                BarStyle::CenterZero if peak < middle => Some(libm::floorf(peak) as usize),
+                BarStyle::CenterZero if peak > middle => Some(libm::ceilf(peak) as usize - 1),
+                BarStyle::Bar if peak > 0.0 => Some(libm::ceilf(peak) as usize - 1),
+                // Nothing to mark at zero
+                _ => None,
+            };
+            if let Some(index) = index.filter(|&index| index < N) {
+                frame[index] = self.color_at(index);
+            }
+        }
+        frame
+    }
+
+    /// Show `value` on `strip`, writing only the modules whose LEDs change.
+    ///
+    /// Returns `true` if any module was written.
+    pub fn draw_strip<I2C, E, const M: usize>(
+        &mut self,
+        strip: &mut PixelStrip<I2C, M>,
+        value: f32,
+        now_ms: u32,
+    ) -> Result<bool, E>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let frame = self.render(value, now_ms);
+        strip.set_frame(&frame, self.brightness);
+        strip.show_if_changed()
+    }
+
+    /// The moving edge for `value`, in LEDs from the start of the bar: the
+    /// end of a bar, the start of a dot, or the outer end of a center-zero bar.
+    fn edge(&self, value: f32) -> f32 {
+        let n = N as f32;
+        let fraction = |value: f32, min: f32, max: f32| {
+            if max == min || value.is_nan() {
+                0.0
+            } else {
+                ((value - min) / (max - min)).clamp(0.0, 1.0)
+            }
+        };
+        match self.style {
+            BarStyle::Bar => fraction(value, self.min, self.max) * n,
+            BarStyle::Dot => fraction(value, self.min, self.max) * (n - 1.0),
+            BarStyle::CenterZero => {
+                let middle = n / 2.0;
+                if value >= 0.0 {
+                    middle + fraction(value, 0.0, self.max) * middle
+                } else {
+                    middle - fraction(-value, 0.0, -self.min) * middle
+                }
+            }
+        }
+    }
+
+    /// The palette color for LED `index`.
+    fn color_at(&self, index: usize) -> Color {
+        let position = match self.style {
+            BarStyle::CenterZero => {
+                // Distance of the LED's center from the middle, 0 to 1
+                let middle = N as f32 / 2.0;
+                let distance = libm::fabsf(index as f32 + 0.5 - middle) / (middle - 0.5).max(0.5);
+                distance.min(1.0)
+            }
+            _ if N < 2 => 0.0,
+            _ => index as f32 / (N - 1) as f32,
+        };
+        self.palette.sample(libm::roundf(position * 255.0) as u8)
+    }
+
+    /// Track the peak edge and return it while it is held.
+    fn update_peak(&mut self, edge: f32, now_ms: u32) -> Option<f32> {
+        let hold_ms = self.peak_hold_ms?;
+        let middle = N as f32 / 2.0;
+        let height = |edge: f32| match self.style {
+            BarStyle::CenterZero => libm::fabsf(edge - middle),
+            _ => edge,
+        };
+        let peak = match self.peak {
+            Some((peak, at))
+                if height(peak) > height(edge) && now_ms.wrapping_sub(at) < hold_ms =>
+            {
+                (peak, at)
+            }
+            _ => (edge, now_ms),
+        };
+        self.peak = Some(peak);
+        Some(peak.0)
+    }
+}
@@ -0,0 +1,484 @@
+//! Command-line tool for inspecting and driving Modulinos from a Linux host.
+//!
+//! Talks to an I2C bus through `/dev/i2c-*` (e.g. on a Raspberry Pi), or to a
+//! simulated bus with one of every Modulino when run with `--sim`.
+//!
+//! Run `modulino --help` for the list of commands.
+
+use std::cell::RefCell;
+use std::env;
+use std::process::ExitCode;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::I2c;
+use linux_embedded_hal::{Delay, I2cdev};
+use modulino::sim::{self, SimBus, SimDevice};
+use modulino::{
+    Color, Distance, LatchRelay, LedMatrix, Light, Movement, OptoRelay, Pitch, Pixels, Pressure,
+    Thermo,
+};
+
+const USAGE: &str = "\
+Usage: modulino [--bus <path> | --sim] <command> [args]
+
+Options:
+  --bus <path>   I2C bus device (default: /dev/i2c-1)
+  --sim          Use a simulated bus with one of every Modulino
+  -h, --help     Show this help
+
+Commands:
+  scan                                   List the modules found on the bus
+  read <sensor>                          Print a measurement from thermo, distance,
+                                         movement, light or pressure
+  pixels <index|all> <color> [percent]   Set Pixels LEDs; color is a name or RRGGBB
+  pixels off                             Turn all Pixels LEDs off
+  buzzer <note|hz> [ms]                  Play a note (e.g. A4, C#5) or frequency
+  buzzer off                             Silence the Buzzer
+  matrix clear                           Turn all LED Matrix pixels off
+  matrix fill                            Turn all LED Matrix pixels on
+  matrix pixel <x> <y>                   Show a single pixel (x 0-11, y 0-7)
+  matrix frame <hex>                     Show 12 column bytes, e.g. 00ff00...
+  relay <latch|opto> <on|off|toggle>     Switch a relay
+";
+
+/// Default I2C bus on a Raspberry Pi.
+const DEFAULT_BUS: &str = "/dev/i2c-1";
+
+/// How long to wait for a Distance measurement before giving up.
+const DISTANCE_TIMEOUT_MS: u32 = 1000;
+
+/// A parsed command line.
+struct Options {
+    bus: String,
+    sim: bool,
+    command: Command,
+}
+
+enum Command {
+    Help,
+    Scan,
+    Read(Sensor),
+    Pixels(Option<usize>, Color, u8),
+    PixelsOff,
+    Buzzer(u16, u16),
+    BuzzerOff,
+    Matrix(MatrixCommand),
+    Relay(RelayKind, RelayAction),
+}
+
+#[derive(Clone, Copy)]
+enum Sensor {
+    Thermo,
+    Distance,
+    Movement,
+    Light,
+    Pressure,
+}
+
+enum MatrixCommand {
+    Clear,
+    Fill,
+    Pixel(u8, u8),
+    Frame([u8; 12]),
+}
+
+#[derive(Clone, Copy)]
+enum RelayKind {
+    Latch,
+    Opto,
+}
+
+#[derive(Clone, Copy)]
+enum RelayAction {
+    On,
+    Off,
+    Toggle,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let result = if let Command::Help = options.command {
+        print!("{}", USAGE);
+        Ok(())
+    } else if options.sim {
+        run_sim(&options.command)
+    } else {
+        run_hardware(&options.bus, &options.command)
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_hardware(bus: &str, command: &Command) -> Result<(), String> {
+    let i2c = I2cdev::new(bus).map_err(|e| format!("cannot open {}: {}", bus, e))?;
+    execute(i2c, &mut Delay, command)
+}
+
+fn run_sim(command: &Command) -> Result<(), String> {
+    let buttons = RefCell::new(sim::Buttons::new());
+    let buzzer = RefCell::new(sim::Buzzer::new());
+    let pixels = RefCell::new(sim::Pixels::new());
+    let knob = RefCell::new(sim::Knob::new());
+    let joystick = RefCell::new(sim::Joystick::new());
+    let latch = RefCell::new(sim::LatchRelay::new());
+    let opto = RefCell::new(sim::OptoRelay::new());
+    let vibro = RefCell::new(sim::Vibro::new());
+    let matrix = RefCell::new(sim::LedMatrix::new());
+    let thermo = RefCell::new(sim::Thermo::new());
+    let distance = RefCell::new(sim::Distance::new());
+    let movement = RefCell::new(sim::Movement::new());
+    let light = RefCell::new(sim::Light::new());
+    let pressure = RefCell::new(sim::Pressure::new());
+    light.borrow_mut().set_channels(120, 4000, 3500, 2500);
+
+    let devices: [&RefCell<dyn SimDevice>; 14] = [
+        &buttons, &buzzer, &pixels, &knob, &joystick, &latch, &opto, &vibro, &matrix, &thermo,
+        &distance, &movement, &light, &pressure,
+    ];
+    execute(SimBus::new(&devices), &mut Delay, command)
+}
+
+fn execute<I2C, D>(mut i2c: I2C, delay: &mut D, command: &Command) -> Result<(), String>
+where
+    I2C: I2c,
+    D: DelayNs,
+{
+    match *command {
+        Command::Help => Ok(()),
+        Command::Scan => {
+            let found = modulino::scan(&mut i2c);
+            if found.is_empty() {
+                println!("no devices found");
+            }
+            for (address, kind) in found.iter() {
+                println!("0x{:02X}  {}", address, kind);
+            }
+            Ok(())
+        }
+        Command::Read(sensor) => read(i2c, delay, sensor),
+        Command::Pixels(index, color, brightness) => {
+            let mut pixels = Pixels::new(i2c).map_err(describe)?;
+            match index {
+                Some(index) => {
+                    pixels
+                        .set_color(index, color, brightness)
+                        .map_err(describe)?;
+                }
+                None => {
+                    pixels.set_all_color(color, brightness);
+                }
+            }
+            pixels.show().map_err(describe)?;
+            println!(
+                "pixels: {} set to {:02X}{:02X}{:02X} at {}%",
+                index.map_or("all LEDs".to_string(), |i| format!("LED {}", i)),
+                color.r,
+                color.g,
+                color.b,
+                brightness
+            );
+            Ok(())
+        }
+        Command::PixelsOff => {
+            let mut pixels = Pixels::new(i2c).map_err(describe)?;
+            pixels.clear_all().show().map_err(describe)?;
+            println!("pixels: off");
+            Ok(())
+        }
+        Command::Buzzer(frequency, duration_ms) => {
+            let mut buzzer = modulino::Buzzer::new(i2c).map_err(describe)?;
+            buzzer.tone(frequency, duration_ms).map_err(describe)?;
+            println!("buzzer: {} Hz for {} ms", frequency, duration_ms);
+            Ok(())
+        }
+        Command::BuzzerOff => {
+            let mut buzzer = modulino::Buzzer::new(i2c).map_err(describe)?;
+            buzzer.no_tone().map_err(describe)?;
+            println!("buzzer: off");
+            Ok(())
+        }
+        Command::Matrix(ref matrix_command) => {
+            let mut matrix = LedMatrix::new(i2c);
+            matrix.init().map_err(describe)?;
+            match *matrix_command {
+                MatrixCommand::Clear => matrix.clear().map_err(describe)?,
+                MatrixCommand::Fill => matrix.set_frame(&[0xFF; 12]).map_err(describe)?,
+                MatrixCommand::Pixel(x, y) => {
+                    matrix.set_pixel(x, y, 255).map_err(describe)?;
+                    matrix.show().map_err(describe)?;
+                }
+                MatrixCommand::Frame(ref frame) => matrix.set_frame(frame).map_err(describe)?,
+            }
+            println!("matrix: updated");
+            Ok(())
+        }
+        Command::Relay(RelayKind::Latch, action) => {
+            let mut relay = LatchRelay::new(i2c).map_err(describe)?;
+            match action {
+                RelayAction::On => relay.on(),
+                RelayAction::Off => relay.off(),
+                RelayAction::Toggle => relay.toggle(),
+            }
+            .map_err(describe)?;
+            let state = match relay.is_on().map_err(describe)? {
+                Some(true) => "on",
+                Some(false) => "off",
+                None => "unknown",
+            };
+            println!("latch relay: {}", state);
+            Ok(())
+        }
+        Command::Relay(RelayKind::Opto, action) => {
+            let mut relay = OptoRelay::new(i2c).map_err(describe)?;
+            match action {
+                RelayAction::On => relay.on(),
+                RelayAction::Off => relay.off(),
+                RelayAction::Toggle => relay.toggle(),
+            }
+            .map_err(describe)?;
+            println!("opto relay: {}", if relay.is_on() { "on" } else { "off" });
+            Ok(())
+        }
+    }
+}
+
+fn read<I2C, D>(i2c: I2C, delay: &mut D, sensor: Sensor) -> Result<(), String>
+where
+    I2C: I2c,
+    D: DelayNs,
+{
+    match sensor {
+        Sensor::Thermo => {
+            let mut thermo = Thermo::new(i2c);
+            let measurement = thermo.read(delay).map_err(describe)?;
+            println!("temperature: {:.1} °C", measurement.temperature);
+            println!("humidity: {:.1} %", measurement.humidity);
+        }
+        Sensor::Distance => {
+            let mut distance = Distance::new(i2c);
+            distance.init(delay).map_err(describe)?;
+            distance.start_ranging().map_err(describe)?;
+            distance
+                .wait_for_data(delay, DISTANCE_TIMEOUT_MS)
+                .map_err(|error| match error {
+                    modulino::Error::Timeout => {
+                        "timed out waiting for a distance measurement".to_string()
+                    }
+                    error => describe(error),
+                })?;
+            let measurement = distance.read_measurement().map_err(describe)?;
+            distance.stop_ranging().map_err(describe)?;
+            match measurement.distance() {
+                Some(mm) => println!("distance: {} mm", mm),
+                None => println!("distance: no valid target ({:?})", measurement.status),
+            }
+        }
+        Sensor::Movement => {
+            let mut movement = Movement::new(i2c).map_err(describe)?;
+            let accel = movement.acceleration().map_err(describe)?;
+            let gyro = movement.angular_velocity().map_err(describe)?;
+            println!(
+                "acceleration: x={:.3} y={:.3} z={:.3} g",
+                accel.x, accel.y, accel.z
+            );
+            println!(
+                "angular velocity: x={:.2} y={:.2} z={:.2} dps",
+                gyro.x, gyro.y, gyro.z
+            );
+        }
+        Sensor::Light => {
+            let mut light = Light::new(i2c);
+            light.init().map_err(describe)?;
+            // Let the first 25 ms measurement complete
+            delay.delay_ms(50);
+            let measurement = light.read().map_err(describe)?;
+            println!("lux: {:.1}", measurement.lux);
+            println!(
+                "red: {} green: {} blue: {} ir: {}",
+                measurement.red, measurement.green, measurement.blue, measurement.ir
+            );
+        }
+        Sensor::Pressure => {
+            let mut pressure = Pressure::new(i2c);
+            pressure.init().map_err(describe)?;
+            // Let the first 10 Hz measurement complete
+            delay.delay_ms(150);
+            let hpa = pressure.pressure().map_err(describe)?;
+            let temperature = pressure.temperature().map_err(describe)?;
+            println!("pressure: {:.2} hPa", hpa);
+            println!("temperature: {:.1} °C", temperature);
+        }
+    }
+    Ok(())
+}
+
+fn describe<E: std::fmt::Debug>(error: modulino::Error<E>) -> String {
+    error.to_string()
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut bus = DEFAULT_BUS.to_string();
+    let mut sim = false;
+    let mut rest = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--bus" => {
+                bus = iter.next().ok_or("--bus needs a device path")?.to_string();
+            }
+            "--sim" => sim = true,
+            "-h" | "--help" => {
+                return Ok(Options {
+                    bus,
+                    sim,
+                    command: Command::Help,
+                })
+            }
+            _ => rest.push(arg.as_str()),
+        }
+    }
+
+    let command = parse_command(&rest)?;
+    Ok(Options { bus, sim, command })
+}
+
+fn parse_command(args: &[&str]) -> Result<Command, String> {
+    match args {
+        [] => Err("missing command".to_string()),
+        ["scan"] => Ok(Command::Scan),
+        ["read", sensor] => {
+            let sensor = match *sensor {
+                "thermo" => Sensor::Thermo,
+                "distance" => Sensor::Distance,
+                "movement" => Sensor::Movement,
+                "light" => Sensor::Light,
+                "pressure" => Sensor::Pressure,
+                other => return Err(format!("unknown sensor '{}'", other)),
+            };
+            Ok(Command::Read(sensor))
+        }
+        ["pixels", "off"] => Ok(Command::PixelsOff),
+        ["pixels", index, color, brightness @ ..] if brightness.len() <= 1 => {
+            let index = match *index {
+                "all" => None,
+                i => {
+                    let i = parse_number::<usize>(i, "LED index")?;
+                    if i >= 8 {
+                        return Err("LED index must be 0-7".to_string());
+                    }
+                    Some(i)
+                }
+            };
+            let brightness = match brightness.first() {
+                Some(b) => parse_number::<u8>(b, "brightness")?.min(100),
+                None => 50,
+            };
+            Ok(Command::Pixels(index, parse_color(color)?, brightness))
+        }
+        ["buzzer", "off"] => Ok(Command::BuzzerOff),
+        ["buzzer", note, duration @ ..] if duration.len() <= 1 => {
+            let duration = match duration.first() {
+                Some(ms) => parse_number::<u16>(ms, "duration")?,
+                None => 500,
+            };
+            Ok(Command::Buzzer(parse_frequency(note)?, duration))
+        }
+        ["matrix", "clear"] => Ok(Command::Matrix(MatrixCommand::Clear)),
+        ["matrix", "fill"] => Ok(Command::Matrix(MatrixCommand::Fill)),
+        ["matrix", "pixel", x, y] => {
+            let x = parse_number::<u8>(x, "x")?;
+            let y = parse_number::<u8>(y, "y")?;
+            if x >= 12 || y >= 8 {
+                return Err("pixel must be within x 0-11, y 0-7".to_string());
+            }
+            Ok(Command::Matrix(MatrixCommand::Pixel(x, y)))
+        }
+        ["matrix", "frame", hex] => Ok(Command::Matrix(MatrixCommand::Frame(parse_frame(hex)?))),
+        ["relay", kind, action] => {
+            let kind = match *kind {
+                "latch" => RelayKind::Latch,
+                "opto" => RelayKind::Opto,
+                other => return Err(format!("unknown relay '{}'", other)),
+            };
+            let action = match *action {
+                "on" => RelayAction::On,
+                "off" => RelayAction::Off,
+                "toggle" => RelayAction::Toggle,
+                other => return Err(format!("unknown relay action '{}'", other)),
+            };
+            Ok(Command::Relay(kind, action))
+        }
+        [command, ..] => Err(format!("invalid arguments for '{}'", command)),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(text: &str, what: &str) -> Result<T, String> {
+    text.parse()
+        .map_err(|_| format!("invalid {} '{}'", what, text))
+}
+
+/// Parse a color name or a `RRGGBB` hex value (with optional `#`).
+fn parse_color(text: &str) -> Result<Color, String> {
+    let color = match text.to_ascii_lowercase().as_str() {
+        "black" | "off" => Color::BLACK,
+        "red" => Color::RED,
+        "green" => Color::GREEN,
+        "blue" => Color::BLUE,
+        "yellow" => Color::YELLOW,
+        "cyan" => Color::CYAN,
+        "magenta" => Color::MAGENTA,
+        "white" => Color::WHITE,
+        "orange" => Color::ORANGE,
+        "purple" => Color::PURPLE,
+        hex => {
+            let hex = hex.strip_prefix('#').unwrap_or(hex);
+            if hex.len() != 6 {
+                return Err(format!("invalid color '{}'", text));
+            }
+            let rgb =
+                u32::from_str_radix(hex, 16).map_err(|_| format!("invalid color '{}'", text))?;
+            Color::from_rgb24(rgb)
+        }
+    };
+    Ok(color)
+}
+
+/// Parse a frequency in Hz or a note name such as `A4`, `C#5` or `Bb3`.
+fn parse_frequency(text: &str) -> Result<u16, String> {
+    if let Ok(hz) = text.parse::<u16>() {
+        return Ok(hz);
+    }
+
+    let pitch: Pitch = text
+        .parse()
+        .map_err(|_| format!("invalid note '{}'", text))?;
+    Ok(pitch.frequency())
+}
+
+/// Parse 12 column bytes written as 24 hex digits.
+fn parse_frame(text: &str) -> Result<[u8; 12], String> {
+    let invalid = || format!("invalid frame '{}' (expected 24 hex digits)", text);
+    if text.len() != 24 || !text.is_ascii() {
+        return Err(invalid());
+    }
+    let mut frame = [0u8; 12];
+    for (i, byte) in frame.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&text[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+    }
+    Ok(frame)
+}
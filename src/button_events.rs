@@ -0,0 +1,321 @@
+//! Button event detection for the Modulino Buttons module.
+//!
+//! [`ButtonEvents`] turns a stream of [`ButtonState`] samples into debounced
+//! press, release, click, double-click, long-press, hold-repeat and chord
+//! events. It is driven by a caller-supplied millisecond timestamp, so it works
+//! with any time source and with both the blocking and async drivers.
+
+use crate::ButtonState;
+
+/// Identifies one of the three buttons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Button {
+    /// Button A
+    A,
+    /// Button B
+    B,
+    /// Button C
+    C,
+}
+
+impl Button {
+    /// All buttons, in order.
+    pub const ALL: [Button; 3] = [Button::A, Button::B, Button::C];
+
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl ButtonState {
+    /// Check if `button` is pressed.
+    pub fn is_pressed(&self, button: Button) -> bool {
+        match button {
+            Button::A => self.a,
+            Button::B => self.b,
+            Button::C => self.c,
+        }
+    }
+
+    /// Number of buttons pressed.
+    pub fn count(&self) -> usize {
+        self.a as usize + self.b as usize + self.c as usize
+    }
+}
+
+/// An event reported by [`ButtonEvents`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ButtonEvent {
+    /// The button went down (after debouncing).
+    Pressed(Button),
+    /// The button went up (after debouncing).
+    Released(Button),
+    /// A short press that was not followed by a second one within the
+    /// double-click window.
+    Click(Button),
+    /// Two short presses within the double-click window.
+    DoubleClick(Button),
+    /// The button has been held for the long-press time.
+    LongPress(Button),
+    /// Repeated while the button stays held after a long press.
+    Held(Button),
+    /// Two or more buttons are held together; carries the set of pressed buttons.
+    ///
+    /// Buttons that take part in a chord do not report clicks or long presses
+    /// for that press.
+    Chord(ButtonState),
+}
+
+/// Timings used by [`ButtonEvents`], in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ButtonTimings {
+    /// How long a new level must be stable before it is accepted (default 20).
+    pub debounce_ms: u32,
+    /// Hold time that turns a press into a long press (default 800).
+    pub long_press_ms: u32,
+    /// Maximum gap between two clicks of a double-click (default 300).
+    ///
+    /// A single click is only reported once this window has passed. Set it to 0
+    /// to report clicks immediately and disable double-click detection.
+    pub double_click_ms: u32,
+    /// Interval of [`ButtonEvent::Held`] repeats after a long press (default 200).
+    ///
+    /// Set it to 0 to disable repeats.
+    pub hold_repeat_ms: u32,
+}
+
+impl Default for ButtonTimings {
+    fn default() -> Self {
+        Self {
+            debounce_ms: 20,
+            long_press_ms: 800,
+            double_click_ms: 300,
+            hold_repeat_ms: 200,
+        }
+    }
+}
+
+/// Maximum number of events a single update can report.
+const MAX_EVENTS: usize = 10;
+
+/// Events reported by one [`ButtonEvents::update`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ButtonEventList {
+    events: [Option<ButtonEvent>; MAX_EVENTS],
+    len: usize,
+}
+
+impl ButtonEventList {
+    fn push(&mut self, event: ButtonEvent) {
+        if let Some(slot) = self.events.get_mut(self.len) {
+            *slot = Some(event);
+            self.len += 1;
+        }
+    }
+
+    /// Iterate over the events in the order they happened.
+    pub fn iter(&self) -> impl Iterator<Item = ButtonEvent> + '_ {
+        self.events[..self.len].iter().flatten().copied()
+    }
+
+    /// Check if `event` is in the list.
+    pub fn contains(&self, event: ButtonEvent) -> bool {
+        self.iter().any(|e| e == event)
+    }
+
+    /// Number of events.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if there are no events.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Tracker {
+    raw: bool,
+    raw_since: u32,
+    pressed: bool,
+    pressed_at: u32,
+    /// Time of the last Held (or LongPress) event, once the long press fired.
+    long_press: Option<u32>,
+    chorded: bool,
+    /// This press started within the double-click window of a click.
+    second_press: bool,
+    /// Release time of a click that may still become a double-click.
+    pending_click: Option<u32>,
+}
+
+/// Debounced event detection for three buttons.
+///
+/// Call [`update`](Self::update) regularly (every 10 ms or so) with the latest
+/// [`ButtonState`] and the current time. [`Buttons::poll`](crate::Buttons::poll)
+/// does both in one call.
+///
+/// # Example
+///
+/// ```rust
+/// use modulino::{Button, ButtonEvent, ButtonEvents, ButtonState};
+///
+/// let mut events = ButtonEvents::new();
+/// let down = ButtonState { a: true, b: false, c: false };
+///
+/// events.update(down, 0);
+/// assert!(events.update(down, 20).contains(ButtonEvent::Pressed(Button::A)));
+/// assert!(events
+///     .update(down, 820)
+///     .contains(ButtonEvent::LongPress(Button::A)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ButtonEvents {
+    timings: ButtonTimings,
+    trackers: [Tracker; 3],
+    chord: Option<ButtonState>,
+}
+
+impl ButtonEvents {
+    /// Create an event detector with the default timings.
+    pub fn new() -> Self {
+        Self::with_timings(ButtonTimings::default())
+    }
+
+    /// Create an event detector with custom timings.
+    pub fn with_timings(timings: ButtonTimings) -> Self {
+        Self {
+            timings,
+            trackers: [Tracker::default(); 3],
+            chord: None,
+        }
+    }
+
+    /// Get the timings.
+    pub fn timings(&self) -> ButtonTimings {
+        self.timings
+    }
+
+    /// Change the timings.
+    pub fn set_timings(&mut self, timings: ButtonTimings) {
+        self.timings = timings;
+    }
+
+    /// The debounced state of the buttons.
+    pub fn state(&self) -> ButtonState {
+        ButtonState {
+            a: self.trackers[0].pressed,
+            b: self.trackers[1].pressed,
+            c: self.trackers[2].pressed,
+        }
+    }
+
+    /// Forget all button history, e.g. after the module was reconnected.
+    pub fn reset(&mut self) {
+        self.trackers = [Tracker::default(); 3];
+        self.chord = None;
+    }
+
+    /// Feed a new sample taken at `now_ms` and return the resulting events.
+    ///
+    /// `now_ms` may wrap around.
+    pub fn update(&mut self, state: ButtonState, now_ms: u32) -> ButtonEventList {
+        let mut events = ButtonEventList::default();
+        let timings = self.timings;
+
+        for button in Button::ALL {
+            let tracker = &mut self.trackers[button.index()];
+            let raw = state.is_pressed(button);
+
+            if let Some(released_at) = tracker.pending_click {
+                if now_ms.wrapping_sub(released_at) > timings.double_click_ms {
+                    tracker.pending_click = None;
+                    events.push(ButtonEvent::Click(button));
+                }
+            }
+
+            if raw != tracker.raw {
+                tracker.raw = raw;
+                tracker.raw_since = now_ms;
+            }
+
+            let stable = now_ms.wrapping_sub(tracker.raw_since) >= timings.debounce_ms;
+            if stable && tracker.raw != tracker.pressed {
+                tracker.pressed = tracker.raw;
+                if tracker.pressed {
+                    tracker.pressed_at = now_ms;
+                    tracker.long_press = None;
+                    tracker.chorded = false;
+                    tracker.second_press = tracker.pending_click.take().is_some();
+                    events.push(ButtonEvent::Pressed(button));
+                } else {
+                    events.push(ButtonEvent::Released(button));
+                    if !tracker.chorded && tracker.long_press.is_none() {
+                        if tracker.second_press {
+                            events.push(ButtonEvent::DoubleClick(button));
+                        } else if timings.double_click_ms == 0 {
+                            events.push(ButtonEvent::Click(button));
+                        } else {
+                            tracker.pending_click = Some(now_ms);
+                        }
+                    }
+                }
+            }
+
+            if tracker.pressed && !tracker.chorded {
+                match tracker.long_press {
+                    None => {
+                        if now_ms.wrapping_sub(tracker.pressed_at) >= timings.long_press_ms {
+                            tracker.long_press = Some(now_ms);
+                            if tracker.second_press {
+                                // The earlier click is no longer a double-click
+                                tracker.second_press = false;
+                                events.push(ButtonEvent::Click(button));
+                            }
+                            events.push(ButtonEvent::LongPress(button));
+                        }
+                    }
+                    Some(last) => {
+                        if timings.hold_repeat_ms > 0
+                            && now_ms.wrapping_sub(last) >= timings.hold_repeat_ms
+                        {
+                            tracker.long_press = Some(now_ms);
+                            events.push(ButtonEvent::Held(button));
+                        }
+                    }
+                }
+            }
+        }
+
+        let pressed = self.state();
+        if pressed.count() >= 2 {
+            if self.chord != Some(pressed) {
+                self.chord = Some(pressed);
+                for button in Button::ALL {
+                    if pressed.is_pressed(button) {
+                        let tracker = &mut self.trackers[button.index()];
+                        tracker.chorded = true;
+                        if tracker.second_press {
+                            tracker.second_press = false;
+                            events.push(ButtonEvent::Click(button));
+                        }
+                    }
+                }
+                events.push(ButtonEvent::Chord(pressed));
+            }
+        } else {
+            self.chord = None;
+        }
+
+        events
+    }
+}
+
+impl Default for ButtonEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -2,7 +2,10 @@
 //!
 //! The Modulino Buttons module has three buttons (A, B, C), each with an associated LED.
 
-use crate::{addresses, Error, I2cDevice, Result};
+use crate::{
+    addresses, pinstrap, ButtonEventList, ButtonEvents, ButtonTimings, I2cDevice, Modulino,
+    ModulinoKind, Result,
+};
 use embedded_hal::i2c::I2c;
 
 /// Button state representation.
@@ -27,6 +30,16 @@ impl ButtonState {
     pub fn all_pressed(&self) -> bool {
         self.a && self.b && self.c
     }
+
+    /// Decode a raw read (1 pinstrap + 3 button states).
+    pub(crate) fn from_bytes(buf: &[u8; 4]) -> Self {
+        // Skip first byte (pinstrap address)
+        Self {
+            a: buf[1] != 0,
+            b: buf[2] != 0,
+            c: buf[3] != 0,
+        }
+    }
 }
 
 /// LED state for a single button LED.
@@ -98,6 +111,7 @@ pub struct Buttons<I2C> {
     /// LED C state
     pub led_c: ButtonLed,
     current_state: ButtonState,
+    events: ButtonEvents,
 }
 
 impl<I2C, E> Buttons<I2C>
@@ -116,15 +130,7 @@ where
     /// > [!WARNING]
     /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
     pub fn discover(i2c: &mut I2C) -> Result<u8, E> {
-        let addresses = [addresses::BUTTONS];
-        for &addr in &addresses {
-            if i2c.write(addr, &[]).is_ok() {
-                return Ok(addr);
-            }
-        }
-        i2c.write(addresses[0], &[])
-            .map(|_| addresses[0])
-            .map_err(Error::I2c)
+        <Self as Modulino<I2C>>::probe(i2c)
     }
 
     /// Create a new Buttons instance with a custom address.
@@ -135,6 +141,7 @@ where
             led_b: ButtonLed::new(),
             led_c: ButtonLed::new(),
             current_state: ButtonState::default(),
+            events: ButtonEvents::new(),
         };
 
         // Verify device is present
@@ -155,12 +162,7 @@ where
         let mut buf = [0u8; 4]; // 1 pinstrap + 3 button states
         self.device.read(&mut buf)?;
 
-        // Skip first byte (pinstrap address)
-        self.current_state = ButtonState {
-            a: buf[1] != 0,
-            b: buf[2] != 0,
-            c: buf[3] != 0,
-        };
+        self.current_state = ButtonState::from_bytes(&buf);
 
         Ok(self.current_state)
     }
@@ -185,15 +187,31 @@ where
         self.current_state.c
     }
 
+    /// Read the buttons and return the events since the last poll.
+    ///
+    /// `now_ms` is a millisecond timestamp from the caller's clock (it may wrap
+    /// around). Call this regularly, every 10 ms or so, for accurate timing.
+    /// See [`ButtonEvents`] for the events that are detected.
+    pub fn poll(&mut self, now_ms: u32) -> Result<ButtonEventList, E> {
+        let state = self.read()?;
+        Ok(self.events.update(state, now_ms))
+    }
+
+    /// Get the timings used by [`poll`](Self::poll).
+    pub fn event_timings(&self) -> ButtonTimings {
+        self.events.timings()
+    }
+
+    /// Change the timings used by [`poll`](Self::poll).
+    pub fn set_event_timings(&mut self, timings: ButtonTimings) {
+        self.events.set_timings(timings);
+    }
+
     /// Update the LED states on the device.
     ///
     /// This writes the current LED states to the hardware.
     pub fn update_leds(&mut self) -> Result<(), E> {
-        let data = [
-            self.led_a.is_on() as u8,
-            self.led_b.is_on() as u8,
-            self.led_c.is_on() as u8,
-        ];
+        let data = led_payload(self.led_a, self.led_b, self.led_c);
         self.device.write(&data)?;
         Ok(())
     }
@@ -221,3 +239,26 @@ where
         self.device.release()
     }
 }
+
+impl<I2C: I2c> Modulino<I2C> for Buttons<I2C> {
+    const KIND: ModulinoKind = ModulinoKind::Buttons;
+    const DEFAULT_ADDRESSES: &'static [u8] = &[addresses::BUTTONS];
+    const PINSTRAP: &'static [u8] = &[pinstrap::BUTTONS];
+
+    fn new_with_address(i2c: I2C, address: u8) -> Result<Self, I2C::Error> {
+        Self::new_with_address(i2c, address)
+    }
+
+    fn address(&self) -> u8 {
+        self.device.address
+    }
+
+    fn release(self) -> I2C {
+        self.device.release()
+    }
+}
+
+/// Build the LED write payload for the three button LEDs.
+pub(crate) fn led_payload(a: ButtonLed, b: ButtonLed, c: ButtonLed) -> [u8; 3] {
+    [a.is_on() as u8, b.is_on() as u8, c.is_on() as u8]
+}
@@ -3,13 +3,19 @@
 //! The Modulino Buzzer module contains a piezo speaker that can play tones
 //! at specified frequencies.
 
-use crate::{addresses, Error, I2cDevice, Result};
+use crate::{addresses, pinstrap, I2cDevice, Modulino, ModulinoKind, Pitch, Result, ToneStep};
 use embedded_hal::i2c::I2c;
 
+/// Lowest frequency the buzzer firmware can play, in Hz.
+pub(crate) const MIN_FREQUENCY: u16 = 180;
+
 /// Musical note frequencies in Hz.
 ///
 /// Notes are named with the note letter, optional sharp (S), and octave number.
 /// For example, `A4` is 440 Hz (standard tuning), `CS5` is C# in octave 5.
+///
+/// The enum covers the range the buzzer can play; see [`Pitch`] for the full
+/// MIDI range, transposition and detuning.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u16)]
@@ -90,10 +96,103 @@ pub enum Note {
 }
 
 impl Note {
+    /// Every pitched note, from lowest to highest.
+    const PITCHED: [Note; 58] = [
+        Note::FS3,
+        Note::G3,
+        Note::GS3,
+        Note::A3,
+        Note::AS3,
+        Note::B3,
+        Note::C4,
+        Note::CS4,
+        Note::D4,
+        Note::DS4,
+        Note::E4,
+        Note::F4,
+        Note::FS4,
+        Note::G4,
+        Note::GS4,
+        Note::A4,
+        Note::AS4,
+        Note::B4,
+        Note::C5,
+        Note::CS5,
+        Note::D5,
+        Note::DS5,
+        Note::E5,
+        Note::F5,
+        Note::FS5,
+        Note::G5,
+        Note::GS5,
+        Note::A5,
+        Note::AS5,
+        Note::B5,
+        Note::C6,
+        Note::CS6,
+        Note::D6,
+        Note::DS6,
+        Note::E6,
+        Note::F6,
+        Note::FS6,
+        Note::G6,
+        Note::GS6,
+        Note::A6,
+        Note::AS6,
+        Note::B6,
+        Note::C7,
+        Note::CS7,
+        Note::D7,
+        Note::DS7,
+        Note::E7,
+        Note::F7,
+        Note::FS7,
+        Note::G7,
+        Note::GS7,
+        Note::A7,
+        Note::AS7,
+        Note::B7,
+        Note::C8,
+        Note::CS8,
+        Note::D8,
+        Note::DS8,
+    ];
+
+    /// MIDI note number of [`Note::FS3`], the lowest pitched note.
+    const FIRST_MIDI: u8 = 54;
+
     /// Get the frequency in Hz.
     pub const fn frequency(&self) -> u16 {
         *self as u16
     }
+
+    /// The note with the given MIDI note number, if the enum has one.
+    ///
+    /// ```rust
+    /// use modulino::Note;
+    ///
+    /// assert_eq!(Note::from_midi(69), Some(Note::A4));
+    /// assert_eq!(Note::from_midi(40), None);
+    /// ```
+    pub fn from_midi(midi: u8) -> Option<Note> {
+        let index = midi.checked_sub(Self::FIRST_MIDI)?;
+        Self::PITCHED.get(index as usize).copied()
+    }
+
+    /// The MIDI note number, or `None` for [`Note::Rest`].
+    pub fn midi(&self) -> Option<u8> {
+        Self::PITCHED
+            .iter()
+            .position(|note| note == self)
+            .map(|index| Self::FIRST_MIDI + index as u8)
+    }
+
+    /// The note as a [`Pitch`], or `None` for [`Note::Rest`].
+    ///
+    /// Use this to transpose or detune a note.
+    pub fn pitch(&self) -> Option<Pitch> {
+        self.midi().and_then(Pitch::from_midi)
+    }
 }
 
 impl From<Note> for u16 {
@@ -129,7 +228,7 @@ where
     I2C: I2c<Error = E>,
 {
     /// Minimum supported frequency in Hz.
-    pub const MIN_FREQUENCY: u16 = 180;
+    pub const MIN_FREQUENCY: u16 = MIN_FREQUENCY;
 
     /// Create a new Buzzer instance with the default address.
     pub fn new(i2c: I2C) -> Result<Self, E> {
@@ -143,15 +242,7 @@ where
     /// > [!WARNING]
     /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
     pub fn discover(i2c: &mut I2C) -> Result<u8, E> {
-        let addresses = [addresses::BUZZER];
-        for &addr in &addresses {
-            if i2c.write(addr, &[]).is_ok() {
-                return Ok(addr);
-            }
-        }
-        i2c.write(addresses[0], &[])
-            .map(|_| addresses[0])
-            .map_err(Error::I2c)
+        <Self as Modulino<I2C>>::probe(i2c)
     }
 
     /// Create a new Buzzer instance with a custom address.
@@ -182,20 +273,7 @@ where
     ///
     /// Frequencies below 180 Hz (except 0) are not supported by the hardware.
     pub fn tone(&mut self, frequency: u16, duration_ms: u16) -> Result<(), E> {
-        let freq_bytes = (frequency as u32).to_le_bytes();
-        let duration_bytes = (duration_ms as u32).to_le_bytes();
-
-        let data = [
-            freq_bytes[0],
-            freq_bytes[1],
-            freq_bytes[2],
-            freq_bytes[3],
-            duration_bytes[0],
-            duration_bytes[1],
-            duration_bytes[2],
-            duration_bytes[3],
-        ];
-
+        let data = tone_payload(frequency, duration_ms);
         self.device.write(&data)?;
         Ok(())
     }
@@ -215,6 +293,17 @@ where
         self.tone(note.frequency(), duration_ms)
     }
 
+    /// Play one [`ToneStep`], e.g. from a [`Sequencer`](crate::Sequencer).
+    ///
+    /// Rests silence the buzzer.
+    pub fn play_step(&mut self, step: ToneStep) -> Result<(), E> {
+        if step.is_rest() {
+            self.no_tone()
+        } else {
+            self.tone(step.frequency, step.sound_ms)
+        }
+    }
+
     /// Stop playing any tone.
     pub fn no_tone(&mut self) -> Result<(), E> {
         let data = [0u8; 8];
@@ -232,3 +321,38 @@ where
         self.device.release()
     }
 }
+
+impl<I2C: I2c> Modulino<I2C> for Buzzer<I2C> {
+    const KIND: ModulinoKind = ModulinoKind::Buzzer;
+    const DEFAULT_ADDRESSES: &'static [u8] = &[addresses::BUZZER];
+    const PINSTRAP: &'static [u8] = &[pinstrap::BUZZER];
+
+    fn new_with_address(i2c: I2C, address: u8) -> Result<Self, I2C::Error> {
+        Self::new_with_address(i2c, address)
+    }
+
+    fn address(&self) -> u8 {
+        self.device.address
+    }
+
+    fn release(self) -> I2C {
+        self.device.release()
+    }
+}
+
+/// Build the 8-byte tone payload (frequency and duration, both little-endian u32).
+pub(crate) fn tone_payload(frequency: u16, duration_ms: u16) -> [u8; 8] {
+    let freq_bytes = (frequency as u32).to_le_bytes();
+    let duration_bytes = (duration_ms as u32).to_le_bytes();
+
+    [
+        freq_bytes[0],
+        freq_bytes[1],
+        freq_bytes[2],
+        freq_bytes[3],
+        duration_bytes[0],
+        duration_bytes[1],
+        duration_bytes[2],
+        duration_bytes[3],
+    ]
+}
@@ -1,4 +1,7 @@
 //! Color type for RGB LEDs.
+//!
+//! Hue is given in degrees (0-360), saturation, value and lightness in
+//! percent (0-100), the same scales used by the Light sensor's color names.
 
 /// Represents an RGB color.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -27,6 +30,141 @@ impl Color {
         }
     }
 
+    /// Create a color from hue (degrees), saturation and value (percent).
+    ///
+    /// Hues outside 0-360 wrap around; saturation and value are clamped.
+    pub fn from_hsv(h: f32, s: f32, v: f32) -> Self {
+        let s = unit(s);
+        let v = unit(v);
+        let c = v * s;
+        Self::from_chroma(h, c, v - c)
+    }
+
+    /// Convert to hue (degrees), saturation and value (percent).
+    pub fn to_hsv(&self) -> (f32, f32, f32) {
+        let (r, g, b) = self.normalized();
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let s = if max > 0.0 { (max - min) / max } else { 0.0 };
+        (hue(r, g, b), s * 100.0, max * 100.0)
+    }
+
+    /// Create a color from hue (degrees), saturation and lightness (percent).
+    ///
+    /// Hues outside 0-360 wrap around; saturation and lightness are clamped.
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> Self {
+        let s = unit(s);
+        let l = unit(l);
+        let c = (1.0 - libm::fabsf(2.0 * l - 1.0)) * s;
+        Self::from_chroma(h, c, l - c / 2.0)
+    }
+
+    /// Convert to hue (degrees), saturation and lightness (percent).
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let (r, g, b) = self.normalized();
+        hsl(r, g, b)
+    }
+
+    /// The color of a black body at `kelvin` (clamped to 1000-40000 K).
+    ///
+    /// 6600 K is close to white; lower temperatures are warmer (redder) and
+    /// higher ones cooler (bluer).
+    pub fn from_kelvin(kelvin: u16) -> Self {
+        // Fit of the black-body curve by Tanner Helland
+        let t = kelvin.clamp(1000, 40000) as f32 / 100.0;
+        let r = if t <= 66.0 {
+            255.0
+        } else {
+            329.698_73 * libm::powf(t - 60.0, -0.133_204_76)
+        };
+        let g = if t <= 66.0 {
+            99.470_8 * libm::logf(t) - 161.119_57
+        } else {
+            288.122_17 * libm::powf(t - 60.0, -0.075_514_85)
+        };
+        let b = if t >= 66.0 {
+            255.0
+        } else if t <= 19.0 {
+            0.0
+        } else {
+            138.517_73 * libm::logf(t - 10.0) - 305.044_8
+        };
+        let channel = |v: f32| libm::roundf(v).clamp(0.0, 255.0) as u8;
+        Self::new(channel(r), channel(g), channel(b))
+    }
+
+    /// Linear interpolation towards `other`: 0.0 gives `self`, 1.0 gives `other`.
+    ///
+    /// `t` is clamped to 0.0-1.0.
+    pub fn lerp(&self, other: Color, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| libm::roundf(a as f32 + (b as f32 - a as f32) * t) as u8;
+        Self::new(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+        )
+    }
+
+    /// Draw `over` on top of this color with opacity `alpha` (0-255).
+    pub const fn blend(&self, over: Color, alpha: u8) -> Self {
+        const fn mix(under: u8, over: u8, alpha: u8) -> u8 {
+            let a = alpha as u16;
+            ((over as u16 * a + under as u16 * (255 - a) + 127) / 255) as u8
+        }
+        Self::new(
+            mix(self.r, over.r, alpha),
+            mix(self.g, over.g, alpha),
+            mix(self.b, over.b, alpha),
+        )
+    }
+
+    /// Add two colors channel by channel, stopping at 255.
+    pub const fn saturating_add(&self, other: Color) -> Self {
+        Self::new(
+            self.r.saturating_add(other.r),
+            self.g.saturating_add(other.g),
+            self.b.saturating_add(other.b),
+        )
+    }
+
+    /// Scale every channel by `factor / 255`, so 255 leaves the color unchanged.
+    pub const fn scale(&self, factor: u8) -> Self {
+        const fn mul(c: u8, factor: u8) -> u8 {
+            ((c as u16 * factor as u16 + 127) / 255) as u8
+        }
+        Self::new(
+            mul(self.r, factor),
+            mul(self.g, factor),
+            mul(self.b, factor),
+        )
+    }
+
+    /// Build a color from a hue, chroma and offset, all but the hue in 0.0-1.0.
+    fn from_chroma(h: f32, c: f32, m: f32) -> Self {
+        let h = libm::fmodf(h, 360.0);
+        let h = if h < 0.0 { h + 360.0 } else { h } / 60.0;
+        let x = c * (1.0 - libm::fabsf(libm::fmodf(h, 2.0) - 1.0));
+        let (r, g, b) = match h as u8 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let channel = |v: f32| libm::roundf((v + m) * 255.0).clamp(0.0, 255.0) as u8;
+        Self::new(channel(r), channel(g), channel(b))
+    }
+
+    fn normalized(&self) -> (f32, f32, f32) {
+        (
+            self.r as f32 / 255.0,
+            self.g as f32 / 255.0,
+            self.b as f32 / 255.0,
+        )
+    }
+
     /// Convert to a 32-bit value for APA102 LEDs.
     /// Format: 0xRRGGBB00 (red in high bits, blue shifted left by 8)
     pub const fn to_apa102_data(&self) -> u32 {
@@ -81,3 +219,41 @@ impl From<u32> for Color {
         Self::from_rgb24(rgb)
     }
 }
+
+/// Clamp a percentage and scale it to 0.0-1.0.
+fn unit(percent: f32) -> f32 {
+    percent.clamp(0.0, 100.0) / 100.0
+}
+
+/// Hue in degrees of normalized RGB components.
+fn hue(r: f32, g: f32, b: f32) -> f32 {
+    let max = r.max(g).max(b);
+    let delta = max - r.min(g).min(b);
+    if delta == 0.0 {
+        return 0.0;
+    }
+    let h = if max == r {
+        (g - b) / delta + if g < b { 6.0 } else { 0.0 }
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+    h * 60.0
+}
+
+/// Hue (degrees), saturation and lightness (percent) of normalized RGB components.
+pub(crate) fn hsl(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let l = (max + min) / 2.0;
+    let s = if delta == 0.0 {
+        0.0
+    } else if l > 0.5 {
+        delta / (2.0 - max - min)
+    } else {
+        delta / (max + min)
+    };
+    (hue(r, g, b), s * 100.0, l * 100.0)
+}
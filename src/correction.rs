@@ -0,0 +1,150 @@
+//! Gamma correction and white balance for the Modulino Pixels.
+
+use crate::Color;
+
+/// Full-scale value of a corrected channel.
+const FULL_SCALE: u32 = 65535;
+
+/// Highest APA102 global brightness.
+const MAX_GLOBAL: u32 = 31;
+
+/// Correction applied by [`Pixels`](crate::Pixels) when a frame is shown.
+///
+/// Colors go through a gamma lookup table into 16-bit linear intensity, then
+/// each channel is scaled by the white balance. With high resolution enabled,
+/// the brightness is folded into the intensity and split into the lowest
+/// APA102 global brightness that can reach it plus the 8-bit PWM value, so dim
+/// levels keep their color resolution instead of bottoming out at 1/31.
+///
+/// The default is a pass-through: no gamma, neutral white balance and the
+/// plain 0-100 to 0-31 brightness mapping.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use modulino::{Color, ColorCorrection, Pixels};
+///
+/// let mut pixels = Pixels::new(i2c)?;
+/// pixels.set_correction(Some(
+///     ColorCorrection::new()
+///         .with_gamma(2.2)
+///         .with_white_balance(Color::new(255, 200, 160))
+///         .with_high_resolution(true),
+/// ));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorCorrection {
+    gamma: [u16; 256],
+    white_balance: Color,
+    high_resolution: bool,
+}
+
+impl ColorCorrection {
+    /// Gamma that suits the APA102 LEDs on the Modulino Pixels.
+    pub const DEFAULT_GAMMA: f32 = 2.2;
+
+    /// Create a pass-through correction.
+    pub const fn new() -> Self {
+        let mut gamma = [0u16; 256];
+        let mut i = 0;
+        while i < 256 {
+            gamma[i] = (i * 257) as u16;
+            i += 1;
+        }
+        Self {
+            gamma,
+            white_balance: Color::WHITE,
+            high_resolution: false,
+        }
+    }
+
+    /// Rebuild the gamma table for `gamma` (1.0 is linear).
+    ///
+    /// Values that are not positive leave the table linear.
+    pub fn with_gamma(mut self, gamma: f32) -> Self {
+        let gamma = if gamma > 0.0 { gamma } else { 1.0 };
+        for (i, entry) in self.gamma.iter_mut().enumerate() {
+            let level = libm::powf(i as f32 / 255.0, gamma);
+            *entry = libm::roundf(level * FULL_SCALE as f32) as u16;
+        }
+        self
+    }
+
+    /// Scale each channel by the matching component of `white` (255 is unchanged).
+    ///
+    /// Use the color that looks neutral on the LEDs, for example from
+    /// [`Color::from_kelvin`], to correct a blue tint.
+    pub const fn with_white_balance(mut self, white: Color) -> Self {
+        self.white_balance = white;
+        self
+    }
+
+    /// Combine the 5-bit global brightness with the 8-bit PWM.
+    pub const fn with_high_resolution(mut self, enabled: bool) -> Self {
+        self.high_resolution = enabled;
+        self
+    }
+
+    /// The white balance.
+    pub fn white_balance(&self) -> Color {
+        self.white_balance
+    }
+
+    /// Whether high resolution dimming is enabled.
+    pub fn is_high_resolution(&self) -> bool {
+        self.high_resolution
+    }
+
+    /// Corrected 16-bit intensity of one channel.
+    fn channel(&self, value: u8, balance: u8) -> u32 {
+        self.gamma[value as usize] as u32 * balance as u32 / 255
+    }
+
+    /// Encode one LED as an APA102 frame: `[0xE0 | global, blue, green, red]`.
+    ///
+    /// `brightness` is 0-100.
+    pub(crate) fn encode(&self, color: Color, brightness: u8) -> [u8; 4] {
+        let wb = self.white_balance;
+        let levels = [
+            self.channel(color.b, wb.b),
+            self.channel(color.g, wb.g),
+            self.channel(color.r, wb.r),
+        ];
+
+        let (global, pwm) = if self.high_resolution {
+            // Intensities scaled by brightness, full scale is FULL_SCALE * 100
+            let targets = levels.map(|level| level as u64 * brightness.min(100) as u64);
+            let max = targets.iter().copied().max().unwrap_or(0);
+            let full = FULL_SCALE as u64 * 100;
+            // Lowest global brightness that reaches the brightest channel
+            let global = (max * MAX_GLOBAL as u64).div_ceil(full);
+            if global == 0 {
+                (0, [0; 3])
+            } else {
+                let pwm = targets.map(|target| {
+                    let scaled = target * MAX_GLOBAL as u64 * 255;
+                    let divisor = global * full;
+                    ((scaled + divisor / 2) / divisor).min(255) as u8
+                });
+                (global as u8, pwm)
+            }
+        } else {
+            let pwm = levels.map(|level| ((level + 128) / 257) as u8);
+            (map_brightness(brightness), pwm)
+        };
+
+        [0xE0 | global, pwm[0], pwm[1], pwm[2]]
+    }
+}
+
+impl Default for ColorCorrection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Map brightness from 0-100 to 0-31 (5-bit brightness for APA102).
+pub(crate) const fn map_brightness(brightness: u8) -> u8 {
+    let clamped = if brightness > 100 { 100 } else { brightness };
+    ((clamped as u16 * 31) / 100) as u8
+}
@@ -2,25 +2,45 @@
 //!
 //! The Modulino Distance module uses a VL53L4CD Time-of-Flight sensor.
 
-use crate::{addresses, Error, I2cDevice, Result};
+use crate::{addresses, Error, I2cDevice, Modulino, ModulinoKind, Result};
 use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::InputPin;
 use embedded_hal::i2c::I2c;
 
 // VL53L4CD Register Addresses
-const VL53L4CD_SYSTEM_START: u16 = 0x0087;
-const VL53L4CD_RESULT_RANGE_STATUS: u16 = 0x0089;
-const VL53L4CD_RESULT_FINAL_CROSSTALK_CORRECTED_RANGE_MM_SD0: u16 = 0x0096;
-const VL53L4CD_SYSTEM_INTERRUPT_CLEAR: u16 = 0x0086;
-const VL53L4CD_GPIO_HV_MUX_CTRL: u16 = 0x0030;
-const VL53L4CD_GPIO_TIO_HV_STATUS: u16 = 0x0031;
-const VL53L4CD_RANGE_CONFIG_A: u16 = 0x005E;
-const VL53L4CD_RANGE_CONFIG_B: u16 = 0x0061;
-const VL53L4CD_INTERMEASUREMENT_MS: u16 = 0x006C;
-const VL53L4CD_FIRMWARE_SYSTEM_STATUS: u16 = 0x00E5;
-const VL53L4CD_VHV_CONFIG_TIMEOUT_MACROP_LOOP_BOUND: u16 = 0x0008;
+pub(crate) const VL53L4CD_OSC_FREQUENCY: u16 = 0x0006;
+pub(crate) const VL53L4CD_XTALK_PLANE_OFFSET_KCPS: u16 = 0x0016;
+pub(crate) const VL53L4CD_XTALK_X_PLANE_GRADIENT_KCPS: u16 = 0x0018;
+pub(crate) const VL53L4CD_XTALK_Y_PLANE_GRADIENT_KCPS: u16 = 0x001A;
+pub(crate) const VL53L4CD_RANGE_OFFSET_MM: u16 = 0x001E;
+pub(crate) const VL53L4CD_INNER_OFFSET_MM: u16 = 0x0020;
+pub(crate) const VL53L4CD_OUTER_OFFSET_MM: u16 = 0x0022;
+pub(crate) const VL53L4CD_SYSTEM_START: u16 = 0x0087;
+pub(crate) const VL53L4CD_RESULT_RANGE_STATUS: u16 = 0x0089;
+pub(crate) const VL53L4CD_RESULT_SPAD_NB: u16 = 0x008C;
+pub(crate) const VL53L4CD_RESULT_SIGNAL_RATE: u16 = 0x008E;
+pub(crate) const VL53L4CD_RESULT_AMBIENT_RATE: u16 = 0x0090;
+pub(crate) const VL53L4CD_RESULT_SIGMA: u16 = 0x0092;
+pub(crate) const VL53L4CD_RESULT_FINAL_CROSSTALK_CORRECTED_RANGE_MM_SD0: u16 = 0x0096;
+pub(crate) const VL53L4CD_SYSTEM_INTERRUPT_CLEAR: u16 = 0x0086;
+pub(crate) const VL53L4CD_GPIO_HV_MUX_CTRL: u16 = 0x0030;
+pub(crate) const VL53L4CD_GPIO_TIO_HV_STATUS: u16 = 0x0031;
+pub(crate) const VL53L4CD_SYSTEM_INTERRUPT: u16 = 0x0046;
+pub(crate) const VL53L4CD_RANGE_CONFIG_A: u16 = 0x005E;
+pub(crate) const VL53L4CD_RANGE_CONFIG_B: u16 = 0x0061;
+pub(crate) const VL53L4CD_INTERMEASUREMENT_MS: u16 = 0x006C;
+pub(crate) const VL53L4CD_THRESH_HIGH: u16 = 0x0072;
+pub(crate) const VL53L4CD_THRESH_LOW: u16 = 0x0074;
+pub(crate) const VL53L4CD_RESULT_OSC_CALIBRATE_VAL: u16 = 0x00DE;
+pub(crate) const VL53L4CD_FIRMWARE_SYSTEM_STATUS: u16 = 0x00E5;
+pub(crate) const VL53L4CD_VHV_CONFIG_TIMEOUT_MACROP_LOOP_BOUND: u16 = 0x0008;
+pub(crate) const VL53L4CD_IDENTIFICATION_MODEL_ID: u16 = 0x010F;
+
+// First register written by the tuning blob
+pub(crate) const VL53L4CD_DEFAULT_CONFIGURATION_START: u16 = 0x002D;
 
 // Tuning blob from ST Ultra Low Power Driver
-const VL53L4CD_DEFAULT_CONFIGURATION: [u8; 91] = [
+pub(crate) const VL53L4CD_DEFAULT_CONFIGURATION: [u8; 91] = [
     0x00, 0x00, 0x00, 0x11, 0x02, 0x00, 0x02, 0x08, 0x00, 0x08, 0x10, 0x01, 0x01, 0x00, 0x00, 0x00,
     0x00, 0xff, 0x00, 0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x20, 0x0b, 0x00, 0x00, 0x02, 0x14, 0x21,
     0x00, 0x00, 0x05, 0x00, 0x00, 0x00, 0x00, 0xc8, 0x00, 0x00, 0x38, 0xff, 0x01, 0x00, 0x08, 0x00,
@@ -29,9 +49,283 @@ const VL53L4CD_DEFAULT_CONFIGURATION: [u8; 91] = [
     0x00, 0x02, 0xc7, 0xff, 0x9B, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
 ];
 
+/// Length of the result block, from RESULT__RANGE_STATUS to the end of the distance.
+pub(crate) const RESULT_BLOCK_LEN: usize = (VL53L4CD_RESULT_FINAL_CROSSTALK_CORRECTED_RANGE_MM_SD0
+    + 2
+    - VL53L4CD_RESULT_RANGE_STATUS) as usize;
+
+/// How long `init()` waits for the sensor to boot and to complete its first
+/// measurement.
+pub(crate) const INIT_TIMEOUT_MS: u32 = 1000;
+/// SYSTEM_START value for a single measurement.
+pub(crate) const SYSTEM_START_SINGLE_SHOT: u8 = 0x10;
+/// SYSTEM_START value for back-to-back ranging (inter-measurement period 0).
+pub(crate) const SYSTEM_START_BACK_TO_BACK: u8 = 0x21;
+/// SYSTEM_START value for autonomous ranging with an inter-measurement period.
+pub(crate) const SYSTEM_START_AUTONOMOUS: u8 = 0x40;
+
+/// Timing budget set by `init()`.
+pub(crate) const DEFAULT_TIMING_BUDGET_MS: u16 = 20;
+/// Inter-measurement period set by `init()`.
+pub(crate) const DEFAULT_INTER_MEASUREMENT_MS: u32 = 50;
+/// Timing budgets supported by the sensor.
+pub(crate) const TIMING_BUDGET_RANGE_MS: core::ops::RangeInclusive<u16> = 10..=200;
+
+/// SYSTEM__INTERRUPT value that raises the interrupt for every measurement.
+pub(crate) const INTERRUPT_NEW_SAMPLE_READY: u8 = 0x20;
+
+/// Measurements taken to warm the sensor up before calibrating.
+pub(crate) const CALIBRATION_WARM_UP_SAMPLES: u8 = 10;
+/// Fewest measurements averaged by a calibration.
+pub(crate) const MIN_CALIBRATION_SAMPLES: u8 = 5;
+/// How long a calibration waits for each measurement.
+pub(crate) const CALIBRATION_TIMEOUT_MS: u32 = 5000;
+/// Target distances accepted by the offset calibration.
+pub(crate) const OFFSET_CALIBRATION_RANGE_MM: core::ops::RangeInclusive<u16> = 10..=1000;
+/// Largest crosstalk the sensor can correct, in kcps per SPAD.
+pub(crate) const MAX_CROSSTALK_KCPS: f32 = 128.0;
+
+/// Range status of a VL53L4CD measurement, following ST's status table.
+///
+/// Only [`Valid`](Self::Valid) measurements have a trustworthy distance.
+/// [`SigmaAboveThreshold`](Self::SigmaAboveThreshold),
+/// [`SignalBelowThreshold`](Self::SignalBelowThreshold) and
+/// [`NoWrapAroundCheck`](Self::NoWrapAroundCheck) are warnings: the distance
+/// may be usable, but with less confidence.
+///
+/// ST's table maps no raw value to status 8, so there is no variant for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RangeStatus {
+    /// 0: The distance is valid.
+    Valid,
+    /// 1: The standard deviation of the distance is above the sigma threshold.
+    SigmaAboveThreshold,
+    /// 2: The return signal is below the signal threshold.
+    SignalBelowThreshold,
+    /// 3: The target is below the minimum detection distance.
+    BelowMinimumDistance,
+    /// 4: The phase is out of the valid limits.
+    PhaseOutOfLimit,
+    /// 5: Hardware or VCSEL failure.
+    HardwareFail,
+    /// 6: The phase is valid but no wrap-around check was performed.
+    NoWrapAroundCheck,
+    /// 7: Wrapped target: the phases of two measurements do not match.
+    WrappedTarget,
+    /// 9: The crosstalk signal is too high.
+    CrosstalkSignalFail,
+    /// 10: The first interrupt after starting may report a wrong distance.
+    InterruptError,
+    /// 11: Several targets were merged into one.
+    MergedTarget,
+    /// 12: The signal is too weak to detect a target.
+    SignalTooWeak,
+    /// Any other raw RESULT__RANGE_STATUS value.
+    Other(u8),
+}
+
+impl RangeStatus {
+    /// Decode a raw RESULT__RANGE_STATUS register value.
+    pub const fn from_raw(raw: u8) -> Self {
+        match raw & 0x1F {
+            3 => Self::HardwareFail,
+            4 => Self::SignalBelowThreshold,
+            5 => Self::PhaseOutOfLimit,
+            6 => Self::SigmaAboveThreshold,
+            7 => Self::WrappedTarget,
+            8 => Self::BelowMinimumDistance,
+            9 => Self::Valid,
+            12 => Self::CrosstalkSignalFail,
+            18 => Self::InterruptError,
+            19 => Self::NoWrapAroundCheck,
+            22 => Self::MergedTarget,
+            23 => Self::SignalTooWeak,
+            other => Self::Other(other),
+        }
+    }
+
+    /// The status number from ST's table, or 255 for [`Other`](Self::Other).
+    pub const fn code(&self) -> u8 {
+        match self {
+            Self::Valid => 0,
+            Self::SigmaAboveThreshold => 1,
+            Self::SignalBelowThreshold => 2,
+            Self::BelowMinimumDistance => 3,
+            Self::PhaseOutOfLimit => 4,
+            Self::HardwareFail => 5,
+            Self::NoWrapAroundCheck => 6,
+            Self::WrappedTarget => 7,
+            Self::CrosstalkSignalFail => 9,
+            Self::InterruptError => 10,
+            Self::MergedTarget => 11,
+            Self::SignalTooWeak => 12,
+            Self::Other(_) => 255,
+        }
+    }
+
+    /// Check if the distance is valid.
+    pub const fn is_valid(&self) -> bool {
+        matches!(self, Self::Valid)
+    }
+
+    /// Check if the status is a warning: the distance may still be usable.
+    pub const fn is_warning(&self) -> bool {
+        matches!(
+            self,
+            Self::SigmaAboveThreshold | Self::SignalBelowThreshold | Self::NoWrapAroundCheck
+        )
+    }
+}
+
+/// A VL53L4CD measurement with its quality indicators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DistanceMeasurement {
+    /// Distance in millimeters
+    pub distance_mm: u16,
+    /// Range status
+    pub status: RangeStatus,
+    /// Return signal rate in kcps (kilo counts per second)
+    pub signal_rate_kcps: u32,
+    /// Ambient light rate in kcps
+    pub ambient_rate_kcps: u32,
+    /// Estimated standard deviation of the distance in millimeters
+    pub sigma_mm: u16,
+    /// Number of SPADs (single photon avalanche diodes) used
+    pub spad_count: u16,
+}
+
+impl DistanceMeasurement {
+    /// Check if the distance is valid.
+    pub const fn is_valid(&self) -> bool {
+        self.status.is_valid()
+    }
+
+    /// The distance, or `None` if the status is not valid.
+    pub const fn distance(&self) -> Option<u16> {
+        if self.is_valid() {
+            Some(self.distance_mm)
+        } else {
+            None
+        }
+    }
+
+    /// Signal rate per SPAD in kcps, or 0 if no SPADs were used.
+    pub const fn signal_per_spad_kcps(&self) -> u32 {
+        if self.spad_count == 0 {
+            0
+        } else {
+            self.signal_rate_kcps / self.spad_count as u32
+        }
+    }
+
+    /// Ambient rate per SPAD in kcps, or 0 if no SPADs were used.
+    pub const fn ambient_per_spad_kcps(&self) -> u32 {
+        if self.spad_count == 0 {
+            0
+        } else {
+            self.ambient_rate_kcps / self.spad_count as u32
+        }
+    }
+
+    /// Decode the result block read from RESULT__RANGE_STATUS.
+    pub(crate) fn from_bytes(data: &[u8; RESULT_BLOCK_LEN]) -> Self {
+        let word = |reg: u16| {
+            let offset = (reg - VL53L4CD_RESULT_RANGE_STATUS) as usize;
+            u16::from_be_bytes([data[offset], data[offset + 1]])
+        };
+        Self {
+            distance_mm: word(VL53L4CD_RESULT_FINAL_CROSSTALK_CORRECTED_RANGE_MM_SD0),
+            status: RangeStatus::from_raw(data[0]),
+            signal_rate_kcps: word(VL53L4CD_RESULT_SIGNAL_RATE) as u32 * 8,
+            ambient_rate_kcps: word(VL53L4CD_RESULT_AMBIENT_RATE) as u32 * 8,
+            sigma_mm: word(VL53L4CD_RESULT_SIGMA) / 4,
+            spad_count: word(VL53L4CD_RESULT_SPAD_NB) / 256,
+        }
+    }
+}
+
+/// When a [`Distance`] sensor raises its interrupt, relative to the detection
+/// thresholds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DetectionMode {
+    /// The target is closer than the low threshold.
+    Below,
+    /// The target is further than the high threshold.
+    Above,
+    /// The target is closer than the low threshold or further than the high
+    /// threshold.
+    Outside,
+    /// The target is between the low and high thresholds.
+    Inside,
+}
+
+impl DetectionMode {
+    /// SYSTEM__INTERRUPT value for the mode.
+    pub(crate) const fn config(&self) -> u8 {
+        match self {
+            Self::Below => 0,
+            Self::Above => 1,
+            Self::Outside => 2,
+            Self::Inside => 3,
+        }
+    }
+
+    /// Decode a SYSTEM__INTERRUPT value, or `None` if it raises the interrupt
+    /// for every measurement.
+    pub(crate) const fn from_config(config: u8) -> Option<Self> {
+        if config & INTERRUPT_NEW_SAMPLE_READY != 0 {
+            return None;
+        }
+        match config & 0x07 {
+            0 => Some(Self::Below),
+            1 => Some(Self::Above),
+            2 => Some(Self::Outside),
+            3 => Some(Self::Inside),
+            _ => None,
+        }
+    }
+
+    /// Check if a distance meets the condition for `low_mm` and `high_mm`.
+    pub const fn matches(&self, distance_mm: u16, low_mm: u16, high_mm: u16) -> bool {
+        match self {
+            Self::Below => distance_mm < low_mm,
+            Self::Above => distance_mm > high_mm,
+            Self::Outside => distance_mm < low_mm || distance_mm > high_mm,
+            Self::Inside => distance_mm >= low_mm && distance_mm <= high_mm,
+        }
+    }
+}
+
+/// Detection thresholds configured on a [`Distance`] sensor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DetectionThresholds {
+    /// Low threshold in millimeters
+    pub low_mm: u16,
+    /// High threshold in millimeters
+    pub high_mm: u16,
+    /// When the interrupt is raised
+    pub mode: DetectionMode,
+}
+
+/// Why a [`Distance`] sensor raised its interrupt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum InterruptSource {
+    /// A new measurement is ready.
+    DataReady,
+    /// A measurement met the detection threshold condition.
+    Threshold(DetectionMode),
+}
+
 /// Driver for the Modulino Distance module.
 pub struct Distance<I2C> {
     device: I2cDevice<I2C>,
+    timing_budget_ms: u16,
+    inter_measurement_ms: u32,
 }
 
 impl<I2C, E> Distance<I2C>
@@ -50,61 +344,45 @@ where
     /// > [!WARNING]
     /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
     pub fn discover(i2c: &mut I2C) -> Result<u8, E> {
-        let addresses = [addresses::DISTANCE];
-        for &addr in &addresses {
-            if i2c.write(addr, &[]).is_ok() {
-                return Ok(addr);
-            }
-        }
-        i2c.write(addresses[0], &[])
-            .map(|_| addresses[0])
-            .map_err(Error::I2c)
+        <Self as Modulino<I2C>>::probe(i2c)
     }
 
     /// Create a new Distance instance with a custom address.
     pub fn new_with_address(i2c: I2C, address: u8) -> Self {
         Self {
             device: I2cDevice::new(i2c, address),
+            timing_budget_ms: DEFAULT_TIMING_BUDGET_MS,
+            inter_measurement_ms: DEFAULT_INTER_MEASUREMENT_MS,
         }
     }
 
     /// Initialize the sensor.
     /// This performs the firmware loading and tuning required by the VL53L4CD.
+    ///
+    /// Fails with [`Error::Timeout`] if the sensor does not boot or complete
+    /// its first measurement within a second.
     pub fn init<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), E> {
         // 1. Wait for boot
-        let mut attempts = 0;
-        loop {
-            let status = self.device.read_reg16_u8(VL53L4CD_FIRMWARE_SYSTEM_STATUS)?;
-            if status == 0x03 {
-                break;
-            }
-            attempts += 1;
-            if attempts > 1000 {
-                break;
+        let mut waited_ms = 0;
+        while self.device.read_reg16_u8(VL53L4CD_FIRMWARE_SYSTEM_STATUS)? != 0x03 {
+            if waited_ms >= INIT_TIMEOUT_MS {
+                return Err(Error::Timeout);
             }
             delay.delay_ms(1);
+            waited_ms += 1;
         }
 
         // 2. Load default configuration
         for (i, &byte) in VL53L4CD_DEFAULT_CONFIGURATION.iter().enumerate() {
-            self.device.write_reg16_u8(0x2D + i as u16, byte)?;
+            self.device
+                .write_reg16_u8(VL53L4CD_DEFAULT_CONFIGURATION_START + i as u16, byte)?;
         }
 
         // 3. Start VHV
         self.device.write_reg16_u8(VL53L4CD_SYSTEM_START, 0x40)?;
 
         // 4. Wait for data ready
-        attempts = 0;
-        loop {
-            if self.data_ready()? {
-                break;
-            }
-            attempts += 1;
-            if attempts > 1000 {
-                break;
-            }
-            delay.delay_ms(1);
-        }
+        self.wait_for_data(delay, INIT_TIMEOUT_MS)?;
 
         self.clear_interrupt()?;
         self.stop_ranging()?;
@@ -118,9 +396,8 @@ where
         self.device.write_reg16_u8(0x004B, 0x03)?;
 
         // Set defaults
-        self.set_timing_budget(20)?;
         // Use 50ms inter-measurement instead of 0 to ensure stability
-        self.set_inter_measurement(50)?;
+        self.set_range_timing(DEFAULT_TIMING_BUDGET_MS, DEFAULT_INTER_MEASUREMENT_MS)?;
 
         Ok(())
     }
@@ -130,44 +407,92 @@ where
         self.device.address
     }
 
-    /// Set the timing budget in milliseconds.
-    pub fn set_timing_budget(&mut self, budget_ms: u16) -> Result<(), E> {
-        let (range_config_a, range_config_b) = match budget_ms {
-            10 => (0x0001, 0x0001),
-            15 => (0x0002, 0x0002),
-            20 => (0x0005, 0x0005),
-            33 => (0x000B, 0x000B),
-            50 => (0x0013, 0x0013),
-            100 => (0x0029, 0x0029),
-            200 => (0x0055, 0x0055),
-            500 => (0x00D6, 0x00D6),
-            _ => (0x0005, 0x0005),
+    /// Set the timing budget and the inter-measurement period in milliseconds.
+    ///
+    /// The timing budget (10 to 200 ms) is how long each measurement takes:
+    /// longer budgets reach further with less noise. With an inter-measurement
+    /// period of 0 the sensor measures back to back. Otherwise it sleeps
+    /// between measurements to save power, and the period must be longer than
+    /// the budget.
+    ///
+    /// Fails with [`Error::InvalidParameter`] for other combinations and
+    /// [`Error::DataError`] if the sensor reports no oscillator calibration,
+    /// e.g. before [`init`](Self::init).
+    pub fn set_range_timing(&mut self, budget_ms: u16, inter_measurement_ms: u32) -> Result<(), E> {
+        check_range_timing(budget_ms, inter_measurement_ms)?;
+        let osc_frequency = self.device.read_reg16_u16(VL53L4CD_OSC_FREQUENCY)?;
+        let (range_config_a, range_config_b) =
+            range_config(budget_ms, inter_measurement_ms, osc_frequency).ok_or(Error::DataError)?;
+        let inter_measurement = if inter_measurement_ms == 0 {
+            0
+        } else {
+            let clock_pll = self
+                .device
+                .read_reg16_u16(VL53L4CD_RESULT_OSC_CALIBRATE_VAL)?;
+            inter_measurement_value(inter_measurement_ms, clock_pll)?
         };
+
+        self.device
+            .write_reg16_u32(VL53L4CD_INTERMEASUREMENT_MS, inter_measurement)?;
         self.device
             .write_reg16_u16(VL53L4CD_RANGE_CONFIG_A, range_config_a)?;
         self.device
             .write_reg16_u16(VL53L4CD_RANGE_CONFIG_B, range_config_b)?;
+        self.timing_budget_ms = budget_ms;
+        self.inter_measurement_ms = inter_measurement_ms;
         Ok(())
     }
 
-    /// Set the inter-measurement period in milliseconds.
+    /// Set the timing budget in milliseconds (10 to 200), keeping the
+    /// inter-measurement period.
+    ///
+    /// Fails with [`Error::InvalidParameter`] if the period is not longer
+    /// than the new budget; use [`set_range_timing`](Self::set_range_timing)
+    /// to change both, e.g. to 0 for back-to-back measurements.
+    pub fn set_timing_budget(&mut self, budget_ms: u16) -> Result<(), E> {
+        self.set_range_timing(budget_ms, self.inter_measurement_ms)
+    }
+
+    /// Set the inter-measurement period in milliseconds, keeping the timing
+    /// budget, or 0 to measure back to back.
+    ///
+    /// See [`set_range_timing`](Self::set_range_timing).
     pub fn set_inter_measurement(&mut self, period_ms: u32) -> Result<(), E> {
-        // Simplified calculation for demo
-        let osc_freq = 64000u32;
-        if period_ms == 0 {
-            self.device
-                .write_reg16_u32(VL53L4CD_INTERMEASUREMENT_MS, 0)?;
-        } else {
-            let clock_pll = (period_ms as f32 * osc_freq as f32 / 1000.0) as u32;
-            self.device
-                .write_reg16_u32(VL53L4CD_INTERMEASUREMENT_MS, clock_pll)?;
+        self.set_range_timing(self.timing_budget_ms, period_ms)
+    }
+
+    /// Read the timing budget in milliseconds back from the sensor.
+    ///
+    /// The sensor stores the budget with limited resolution, so long budgets
+    /// may read back a millisecond off.
+    pub fn timing_budget(&mut self) -> Result<u16, E> {
+        let inter_measurement = self.device.read_reg16_u32(VL53L4CD_INTERMEASUREMENT_MS)?;
+        let osc_frequency = self.device.read_reg16_u16(VL53L4CD_OSC_FREQUENCY)?;
+        let range_config_a = self.device.read_reg16_u16(VL53L4CD_RANGE_CONFIG_A)?;
+        timing_budget_from_config(range_config_a, inter_measurement, osc_frequency)
+            .ok_or(Error::DataError)
+    }
+
+    /// Read the inter-measurement period in milliseconds back from the
+    /// sensor, 0 if it measures back to back.
+    pub fn inter_measurement(&mut self) -> Result<u32, E> {
+        let inter_measurement = self.device.read_reg16_u32(VL53L4CD_INTERMEASUREMENT_MS)?;
+        if inter_measurement == 0 {
+            return Ok(0);
         }
-        Ok(())
+        let clock_pll = self
+            .device
+            .read_reg16_u16(VL53L4CD_RESULT_OSC_CALIBRATE_VAL)?;
+        inter_measurement_from_value(inter_measurement, clock_pll).ok_or(Error::DataError)
     }
 
-    /// Start continuous ranging.
+    /// Start continuous ranging, back to back or with the inter-measurement
+    /// period set by [`set_range_timing`](Self::set_range_timing).
     pub fn start_ranging(&mut self) -> Result<(), E> {
-        self.device.write_reg16_u8(VL53L4CD_SYSTEM_START, 0x40)?;
+        self.device.write_reg16_u8(
+            VL53L4CD_SYSTEM_START,
+            system_start_continuous(self.inter_measurement_ms),
+        )?;
         Ok(())
     }
 
@@ -177,11 +502,132 @@ where
         Ok(())
     }
 
+    /// Start a single measurement. The sensor stops by itself afterwards.
+    pub fn start_single_shot(&mut self) -> Result<(), E> {
+        self.device
+            .write_reg16_u8(VL53L4CD_SYSTEM_START, SYSTEM_START_SINGLE_SHOT)?;
+        Ok(())
+    }
+
+    /// Wait up to `timeout_ms` milliseconds for new data, polling every
+    /// millisecond.
+    ///
+    /// Fails with [`Error::Timeout`] if no data is ready in time.
+    pub fn wait_for_data<D: DelayNs>(&mut self, delay: &mut D, timeout_ms: u32) -> Result<(), E> {
+        let mut waited_ms = 0;
+        while !self.data_ready()? {
+            if waited_ms >= timeout_ms {
+                return Err(Error::Timeout);
+            }
+            delay.delay_ms(1);
+            waited_ms += 1;
+        }
+        Ok(())
+    }
+
+    /// Take one measurement and return the distance in millimeters.
+    ///
+    /// Stops any ranging in progress, starts a single measurement, waits up
+    /// to `timeout_ms` milliseconds for it, reads it and clears the
+    /// interrupt. Returns `None` if the range status is not valid, like
+    /// [`read_distance`](Self::read_distance), and fails with
+    /// [`Error::Timeout`] if the measurement does not complete in time.
+    /// The measurement takes about the timing budget.
+    pub fn measure_blocking<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        timeout_ms: u32,
+    ) -> Result<Option<u16>, E> {
+        self.stop_ranging()?;
+        self.clear_interrupt()?;
+        self.start_single_shot()?;
+        if let Err(error) = self.wait_for_data(delay, timeout_ms) {
+            // Report the original error even if stopping fails too
+            let _ = self.stop_ranging();
+            return Err(error);
+        }
+        let distance = self.read_distance()?;
+        self.stop_ranging()?;
+        Ok(distance)
+    }
+
     /// Check if new data is ready.
     pub fn data_ready(&mut self) -> Result<bool, E> {
-        let polarity = (self.device.read_reg16_u8(VL53L4CD_GPIO_HV_MUX_CTRL)? & 0x10) >> 4;
-        let status = self.device.read_reg16_u8(VL53L4CD_GPIO_TIO_HV_STATUS)? & 0x01;
-        Ok(status != polarity)
+        let mux_ctrl = self.device.read_reg16_u8(VL53L4CD_GPIO_HV_MUX_CTRL)?;
+        let status = self.device.read_reg16_u8(VL53L4CD_GPIO_TIO_HV_STATUS)?;
+        Ok(is_data_ready(mux_ctrl, status))
+    }
+
+    /// Raise the interrupt only for measurements that meet `mode` for the
+    /// thresholds `low_mm` and `high_mm`.
+    ///
+    /// [`data_ready`](Self::data_ready) and the GPIO1 line then only report
+    /// those measurements, so a device can sleep until something comes
+    /// close. [`DetectionMode::Below`] only uses `low_mm` and
+    /// [`DetectionMode::Above`] only uses `high_mm`. Fails with
+    /// [`Error::InvalidParameter`] if a window has `low_mm` above `high_mm`.
+    pub fn set_detection_thresholds(
+        &mut self,
+        low_mm: u16,
+        high_mm: u16,
+        mode: DetectionMode,
+    ) -> Result<(), E> {
+        if matches!(mode, DetectionMode::Inside | DetectionMode::Outside) && low_mm > high_mm {
+            return Err(Error::InvalidParameter);
+        }
+        self.device
+            .write_reg16_u8(VL53L4CD_SYSTEM_INTERRUPT, mode.config())?;
+        self.device.write_reg16_u16(VL53L4CD_THRESH_HIGH, high_mm)?;
+        self.device.write_reg16_u16(VL53L4CD_THRESH_LOW, low_mm)?;
+        Ok(())
+    }
+
+    /// Raise the interrupt for every measurement again, as after
+    /// [`init`](Self::init).
+    pub fn clear_detection_thresholds(&mut self) -> Result<(), E> {
+        self.device
+            .write_reg16_u8(VL53L4CD_SYSTEM_INTERRUPT, INTERRUPT_NEW_SAMPLE_READY)?;
+        Ok(())
+    }
+
+    /// Read the detection thresholds back from the sensor, or `None` if the
+    /// interrupt is raised for every measurement.
+    pub fn detection_thresholds(&mut self) -> Result<Option<DetectionThresholds>, E> {
+        let config = self.device.read_reg16_u8(VL53L4CD_SYSTEM_INTERRUPT)?;
+        let Some(mode) = DetectionMode::from_config(config) else {
+            return Ok(None);
+        };
+        Ok(Some(DetectionThresholds {
+            low_mm: self.device.read_reg16_u16(VL53L4CD_THRESH_LOW)?,
+            high_mm: self.device.read_reg16_u16(VL53L4CD_THRESH_HIGH)?,
+            mode,
+        }))
+    }
+
+    /// Read why the interrupt is raised, or `None` if it is not.
+    ///
+    /// The interrupt stays raised until [`clear_interrupt`](Self::clear_interrupt)
+    /// or a read of the measurement.
+    pub fn read_interrupt_source(&mut self) -> Result<Option<InterruptSource>, E> {
+        if !self.data_ready()? {
+            return Ok(None);
+        }
+        let config = self.device.read_reg16_u8(VL53L4CD_SYSTEM_INTERRUPT)?;
+        Ok(Some(match DetectionMode::from_config(config) {
+            Some(mode) => InterruptSource::Threshold(mode),
+            None => InterruptSource::DataReady,
+        }))
+    }
+
+    /// Check the GPIO1 interrupt line without using the bus.
+    ///
+    /// GPIO1 is an open-drain output, active low as configured by
+    /// [`init`](Self::init), so it needs a pull-up.
+    pub fn interrupt_asserted<P: InputPin>(
+        &self,
+        gpio1: &mut P,
+    ) -> core::result::Result<bool, P::Error> {
+        gpio1.is_low()
     }
 
     /// Clear the interrupt flag.
@@ -191,23 +637,166 @@ where
         Ok(())
     }
 
-    /// Read the distance measurement.
+    /// Read the distance measurement in millimeters and clear the interrupt.
+    ///
+    /// Returns `None` if the range status is not [`RangeStatus::Valid`]; use
+    /// [`read_measurement`](Self::read_measurement) to see why.
     pub fn read_distance(&mut self) -> Result<Option<u16>, E> {
         let status = self.device.read_reg16_u8(VL53L4CD_RESULT_RANGE_STATUS)?;
-        let _range_status = status & 0x1F;
         let distance = self
             .device
             .read_reg16_u16(VL53L4CD_RESULT_FINAL_CROSSTALK_CORRECTED_RANGE_MM_SD0)?;
         self.clear_interrupt()?;
 
-        // Return measurement regardless of status for debug visibility
-        Ok(Some(distance))
+        Ok(RangeStatus::from_raw(status).is_valid().then_some(distance))
+    }
+
+    /// Read the full measurement, valid or not, and clear the interrupt.
+    pub fn read_measurement(&mut self) -> Result<DistanceMeasurement, E> {
+        let mut data = [0u8; RESULT_BLOCK_LEN];
+        self.device
+            .read_regs16(VL53L4CD_RESULT_RANGE_STATUS, &mut data)?;
+        self.clear_interrupt()?;
+        Ok(DistanceMeasurement::from_bytes(&data))
+    }
+
+    /// Set the range offset in millimeters (-1024 to 1023), added to every
+    /// distance.
+    ///
+    /// Use this to re-apply a value from
+    /// [`calibrate_offset`](Self::calibrate_offset) after [`init`](Self::init).
+    pub fn set_offset(&mut self, offset_mm: i16) -> Result<(), E> {
+        let raw = offset_register(offset_mm).ok_or(Error::InvalidParameter)?;
+        self.device.write_reg16_u16(VL53L4CD_RANGE_OFFSET_MM, raw)?;
+        self.device.write_reg16_u16(VL53L4CD_INNER_OFFSET_MM, 0)?;
+        self.device.write_reg16_u16(VL53L4CD_OUTER_OFFSET_MM, 0)?;
+        Ok(())
+    }
+
+    /// Read the range offset in millimeters back from the sensor.
+    pub fn offset(&mut self) -> Result<i16, E> {
+        let raw = self.device.read_reg16_u16(VL53L4CD_RANGE_OFFSET_MM)?;
+        Ok(offset_from_register(raw))
+    }
+
+    /// Set the crosstalk compensation in kcps per SPAD (0 to 128).
+    ///
+    /// Use this to re-apply a value from
+    /// [`calibrate_crosstalk`](Self::calibrate_crosstalk) after
+    /// [`init`](Self::init).
+    pub fn set_crosstalk(&mut self, kcps: f32) -> Result<(), E> {
+        let raw = crosstalk_register(kcps).ok_or(Error::InvalidParameter)?;
+        self.device
+            .write_reg16_u16(VL53L4CD_XTALK_X_PLANE_GRADIENT_KCPS, 0)?;
+        self.device
+            .write_reg16_u16(VL53L4CD_XTALK_Y_PLANE_GRADIENT_KCPS, 0)?;
+        self.device
+            .write_reg16_u16(VL53L4CD_XTALK_PLANE_OFFSET_KCPS, raw)?;
+        Ok(())
+    }
+
+    /// Read the crosstalk compensation in kcps per SPAD back from the sensor.
+    pub fn crosstalk(&mut self) -> Result<f32, E> {
+        let raw = self
+            .device
+            .read_reg16_u16(VL53L4CD_XTALK_PLANE_OFFSET_KCPS)?;
+        Ok(crosstalk_from_register(raw))
     }
 
-    /// Publicly expose range status for debugging.
-    pub fn read_range_status(&mut self) -> Result<u8, E> {
+    /// Measure and apply the range offset against a target at `target_mm`.
+    ///
+    /// Place a target, ideally grey (17% reflectance), at 10 to 1000 mm in
+    /// front of the sensor and cover glass. After a warm-up, `samples`
+    /// measurements (at least 5) are averaged and the difference to
+    /// `target_mm` becomes the offset. Ranging is stopped afterwards.
+    ///
+    /// Returns the offset in millimeters, to be persisted and re-applied with
+    /// [`set_offset`](Self::set_offset). Fails with
+    /// [`Error::InvalidParameter`] for other distances or sample counts,
+    /// [`Error::Timeout`] if a measurement takes more than 5 s and
+    /// [`Error::OutOfRange`] if the offset is too large to apply.
+    pub fn calibrate_offset<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        target_mm: u16,
+        samples: u8,
+    ) -> Result<i16, E> {
+        if !OFFSET_CALIBRATION_RANGE_MM.contains(&target_mm) || samples < MIN_CALIBRATION_SAMPLES {
+            return Err(Error::InvalidParameter);
+        }
+        self.set_offset(0)?;
+        self.warm_up(delay)?;
+
+        self.start_ranging()?;
+        let mut total_mm = 0u32;
+        for _ in 0..samples {
+            total_mm += self.next_measurement(delay)?.distance_mm as u32;
+        }
+        self.stop_ranging()?;
+
+        let offset = calibrated_offset(target_mm, total_mm / samples as u32)?;
+        self.set_offset(offset)?;
+        Ok(offset)
+    }
+
+    /// Measure and apply the crosstalk compensation against a scene with no
+    /// target.
+    ///
+    /// Point the sensor, with its cover glass fitted, at an open space with
+    /// nothing within 80 cm, ideally in the dark. Any signal it receives then
+    /// comes from the cover glass. After a warm-up, the signal rate per SPAD
+    /// of `samples` measurements (at least 5, the first one discarded) is
+    /// averaged. Ranging is stopped afterwards.
+    ///
+    /// Returns the crosstalk in kcps per SPAD, to be persisted and
+    /// re-applied with [`set_crosstalk`](Self::set_crosstalk). Fails with
+    /// [`Error::InvalidParameter`] for fewer samples, [`Error::Timeout`] if a
+    /// measurement takes more than 5 s and [`Error::OutOfRange`] if the
+    /// crosstalk is too high to compensate.
+    pub fn calibrate_crosstalk<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        samples: u8,
+    ) -> Result<f32, E> {
+        if samples < MIN_CALIBRATION_SAMPLES {
+            return Err(Error::InvalidParameter);
+        }
+        self.set_crosstalk(0.0)?;
+        self.warm_up(delay)?;
+
+        self.start_ranging()?;
+        // The first measurement after starting is not reliable
+        self.next_measurement(delay)?;
+        let mut total_kcps = 0.0;
+        for _ in 1..samples {
+            total_kcps += signal_per_spad(&self.next_measurement(delay)?);
+        }
+        self.stop_ranging()?;
+
+        let kcps = calibrated_crosstalk(total_kcps / (samples - 1) as f32)?;
+        self.set_crosstalk(kcps)?;
+        Ok(kcps)
+    }
+
+    /// Range for a few measurements so the sensor settles before calibrating.
+    fn warm_up<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), E> {
+        self.start_ranging()?;
+        for _ in 0..CALIBRATION_WARM_UP_SAMPLES {
+            self.next_measurement(delay)?;
+        }
+        self.stop_ranging()
+    }
+
+    /// Wait for the next measurement while ranging and read it.
+    fn next_measurement<D: DelayNs>(&mut self, delay: &mut D) -> Result<DistanceMeasurement, E> {
+        self.wait_for_data(delay, CALIBRATION_TIMEOUT_MS)?;
+        self.read_measurement()
+    }
+
+    /// Read the range status of the last measurement.
+    pub fn read_range_status(&mut self) -> Result<RangeStatus, E> {
         let status = self.device.read_reg16_u8(VL53L4CD_RESULT_RANGE_STATUS)?;
-        Ok(status & 0x1F)
+        Ok(RangeStatus::from_raw(status))
     }
 
     /// Release the I2C bus.
@@ -215,3 +804,208 @@ where
         self.device.release()
     }
 }
+
+impl<I2C: I2c> Modulino<I2C> for Distance<I2C> {
+    const KIND: ModulinoKind = ModulinoKind::Distance;
+    const DEFAULT_ADDRESSES: &'static [u8] = &[addresses::DISTANCE];
+    const PINSTRAP: &'static [u8] = &[];
+
+    fn new_with_address(i2c: I2C, address: u8) -> Result<Self, I2C::Error> {
+        Ok(Self::new_with_address(i2c, address))
+    }
+
+    fn init<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), I2C::Error> {
+        Distance::init(self, delay)
+    }
+
+    fn address(&self) -> u8 {
+        self.device.address
+    }
+
+    fn release(self) -> I2C {
+        self.device.release()
+    }
+}
+
+/// Check a timing budget and inter-measurement period combination.
+pub(crate) fn check_range_timing<E>(budget_ms: u16, inter_measurement_ms: u32) -> Result<(), E> {
+    if !TIMING_BUDGET_RANGE_MS.contains(&budget_ms)
+        || (inter_measurement_ms != 0 && inter_measurement_ms <= budget_ms as u32)
+    {
+        return Err(Error::InvalidParameter);
+    }
+    Ok(())
+}
+
+/// SYSTEM_START value for continuous ranging with an inter-measurement
+/// period, as in ST's `VL53L4CD_StartRanging`.
+pub(crate) fn system_start_continuous(inter_measurement_ms: u32) -> u8 {
+    if inter_measurement_ms == 0 {
+        SYSTEM_START_BACK_TO_BACK
+    } else {
+        SYSTEM_START_AUTONOMOUS
+    }
+}
+
+/// Macro period for an OSC_FREQUENCY value, in microseconds scaled by 2^12,
+/// or `None` if the frequency is 0.
+fn macro_period(osc_frequency: u16) -> Option<u64> {
+    if osc_frequency == 0 {
+        return None;
+    }
+    Some((2304 * (0x4000_0000 / osc_frequency as u64)) >> 6)
+}
+
+/// Time the sensor spends ranging in each measurement, in microseconds.
+///
+/// Back-to-back measurements lose 2.5 ms of the budget to overhead. In
+/// low-power mode the overhead is 4.3 ms and the rest is split between two
+/// ranging phases.
+fn ranging_time_us(budget_ms: u16, inter_measurement_ms: u32) -> u32 {
+    let budget_us = budget_ms as u32 * 1000;
+    if inter_measurement_ms == 0 {
+        budget_us - 2500
+    } else {
+        (budget_us - 4300) / 2
+    }
+}
+
+/// Encode a ranging time as a macro-period count: an 8-bit mantissa in the
+/// low byte and a shift in the high byte.
+fn macro_period_count(ranging_us: u32, macro_period: u64, vcsel_factor: u64) -> u16 {
+    let unit = (macro_period * vcsel_factor) >> 6;
+    let mut count = ((((ranging_us as u64) << 12) + (unit >> 1)) / unit).saturating_sub(1);
+    let mut shift = 0u16;
+    while count > 0xFF {
+        count >>= 1;
+        shift += 1;
+    }
+    (shift << 8) | count as u16
+}
+
+/// RANGE_CONFIG_A/B values for a valid timing budget and inter-measurement
+/// period, or `None` if the oscillator frequency is 0.
+pub(crate) fn range_config(
+    budget_ms: u16,
+    inter_measurement_ms: u32,
+    osc_frequency: u16,
+) -> Option<(u16, u16)> {
+    let macro_period = macro_period(osc_frequency)?;
+    let ranging_us = ranging_time_us(budget_ms, inter_measurement_ms);
+    Some((
+        macro_period_count(ranging_us, macro_period, 16),
+        macro_period_count(ranging_us, macro_period, 12),
+    ))
+}
+
+/// Timing budget in milliseconds from RANGE_CONFIG_A and the raw
+/// INTERMEASUREMENT_MS, or `None` if the oscillator frequency is 0.
+pub(crate) fn timing_budget_from_config(
+    range_config_a: u16,
+    inter_measurement: u32,
+    osc_frequency: u16,
+) -> Option<u16> {
+    let unit = (macro_period(osc_frequency)? * 16) >> 6;
+    let shift = (range_config_a >> 8).min(24);
+    let mantissa = (range_config_a & 0xFF) as u64;
+    // Middle of the counts that encode to this value
+    let count = (mantissa << shift) + ((1u64 << shift) - 1) / 2;
+    let ranging_us = (((count + 1) * unit) >> 12) as u32;
+    let budget_us = if inter_measurement == 0 {
+        ranging_us + 2500
+    } else {
+        ranging_us * 2 + 4300
+    };
+    u16::try_from(budget_us.saturating_add(500) / 1000).ok()
+}
+
+/// Clock ticks per second from RESULT__OSC_CALIBRATE_VAL, which holds the
+/// ticks per millisecond before ST's 1.055 correction.
+fn clock_ticks_per_second(clock_pll: u16) -> u64 {
+    (clock_pll & 0x3FF) as u64 * 1055
+}
+
+/// INTERMEASUREMENT_MS value for a non-zero period in milliseconds.
+///
+/// Fails with [`Error::DataError`] if the clock is not calibrated and
+/// [`Error::InvalidParameter`] if the period is too long.
+pub(crate) fn inter_measurement_value<E>(period_ms: u32, clock_pll: u16) -> Result<u32, E> {
+    let ticks_per_second = clock_ticks_per_second(clock_pll);
+    if ticks_per_second == 0 {
+        return Err(Error::DataError);
+    }
+    u32::try_from(period_ms as u64 * ticks_per_second / 1000).map_err(|_| Error::InvalidParameter)
+}
+
+/// Period in milliseconds from a non-zero INTERMEASUREMENT_MS value, or
+/// `None` if the clock is not calibrated.
+pub(crate) fn inter_measurement_from_value(value: u32, clock_pll: u16) -> Option<u32> {
+    let ticks_per_second = clock_ticks_per_second(clock_pll);
+    if ticks_per_second == 0 {
+        return None;
+    }
+    Some(((value as u64 * 1000 + ticks_per_second / 2) / ticks_per_second) as u32)
+}
+
+/// RANGE_OFFSET_MM value for an offset in millimeters (quarter millimeters,
+/// 13-bit two's complement), or `None` if out of range.
+pub(crate) fn offset_register(offset_mm: i16) -> Option<u16> {
+    if !(-1024..=1023).contains(&offset_mm) {
+        return None;
+    }
+    Some((offset_mm * 4) as u16 & 0x1FFF)
+}
+
+/// Offset in millimeters from a RANGE_OFFSET_MM value.
+pub(crate) fn offset_from_register(raw: u16) -> i16 {
+    // Sign-extend the 13-bit value and drop the two fractional bits
+    ((raw << 3) as i16) >> 5
+}
+
+/// XTALK_PLANE_OFFSET_KCPS value (1/512 kcps) for a crosstalk in kcps, or
+/// `None` if out of range.
+pub(crate) fn crosstalk_register(kcps: f32) -> Option<u16> {
+    if !(0.0..MAX_CROSSTALK_KCPS).contains(&kcps) {
+        return None;
+    }
+    Some(libm::roundf(kcps * 512.0).min(u16::MAX as f32) as u16)
+}
+
+/// Crosstalk in kcps from an XTALK_PLANE_OFFSET_KCPS value.
+pub(crate) fn crosstalk_from_register(raw: u16) -> f32 {
+    raw as f32 / 512.0
+}
+
+/// The offset that corrects an average distance of `average_mm` to `target_mm`.
+pub(crate) fn calibrated_offset<E>(target_mm: u16, average_mm: u32) -> Result<i16, E> {
+    let offset = target_mm as i32 - average_mm as i32;
+    i16::try_from(offset)
+        .ok()
+        .filter(|&offset| offset_register(offset).is_some())
+        .ok_or(Error::OutOfRange)
+}
+
+/// Check that an average crosstalk can be compensated.
+pub(crate) fn calibrated_crosstalk<E>(kcps: f32) -> Result<f32, E> {
+    if crosstalk_register(kcps).is_some() {
+        Ok(kcps)
+    } else {
+        Err(Error::OutOfRange)
+    }
+}
+
+/// Signal rate per SPAD of a measurement in kcps, with fractions.
+pub(crate) fn signal_per_spad(measurement: &DistanceMeasurement) -> f32 {
+    if measurement.spad_count == 0 {
+        0.0
+    } else {
+        measurement.signal_rate_kcps as f32 / measurement.spad_count as f32
+    }
+}
+
+/// Compare the GPIO status against the configured interrupt polarity.
+pub(crate) fn is_data_ready(mux_ctrl: u8, tio_status: u8) -> bool {
+    let polarity = (mux_ctrl & 0x10) >> 4;
+    let status = tio_status & 0x01;
+    status != polarity
+}
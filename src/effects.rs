@@ -0,0 +1,253 @@
+//! Sound effects for the Modulino Buzzer.
+//!
+//! The buzzer only plays single tones, so effects are built by stepping the
+//! tone over time. A [`SoundEffect`] is a [`ToneSource`]: hand it to a
+//! [`Sequencer`](crate::Sequencer) and call `tick(now_ms)` to play it without
+//! blocking.
+
+use crate::buzzer::MIN_FREQUENCY;
+use crate::{ToneSource, ToneStep};
+
+/// How a [`SoundEffect`] moves between its start and end frequencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EffectKind {
+    /// Glide from the start to the end frequency.
+    Sweep,
+    /// Glide from the start to the end frequency and back.
+    Wail,
+    /// Alternate between the start and end frequencies.
+    Alternate,
+    /// Beep at the start frequency with equal silences in between.
+    Beep,
+}
+
+/// A parameterized sound effect.
+///
+/// Each cycle is made of `steps` tones (or beeps) of `step_ms` each, followed
+/// by an optional pause. Cycles repeat `repeats` times, or forever when
+/// `repeats` is 0. Frequencies below the buzzer's minimum are raised to it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use modulino::{Buzzer, EffectKind, Sequencer, SoundEffect};
+///
+/// let mut buzzer = Buzzer::new(i2c)?;
+///
+/// // A preset...
+/// let mut alarm = Sequencer::new(SoundEffect::alarm());
+///
+/// // ...or a custom effect: 400 Hz to 1200 Hz in 25 steps of 8 ms, three times
+/// let mut rise = Sequencer::new(
+///     SoundEffect::new(EffectKind::Sweep, 400, 1200, 8)
+///         .with_steps(25)
+///         .with_repeats(3),
+/// );
+///
+/// while alarm.tick(&mut buzzer, now_ms())? {
+///     // ... other work ...
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SoundEffect {
+    kind: EffectKind,
+    start_hz: u16,
+    end_hz: u16,
+    step_ms: u16,
+    steps: u16,
+    repeats: u16,
+    pause_ms: u16,
+    cycle: u16,
+    index: u16,
+}
+
+impl SoundEffect {
+    /// Default number of steps per cycle.
+    pub const DEFAULT_STEPS: u16 = 16;
+
+    /// Largest number of steps per cycle, so a wail or beep cycle fits in a
+    /// `u16` count.
+    pub const MAX_STEPS: u16 = u16::MAX / 2;
+
+    /// Create an effect that plays one cycle of [`DEFAULT_STEPS`](Self::DEFAULT_STEPS) steps.
+    pub const fn new(kind: EffectKind, start_hz: u16, end_hz: u16, step_ms: u16) -> Self {
+        Self {
+            kind,
+            start_hz: clamp_frequency(start_hz),
+            end_hz: clamp_frequency(end_hz),
+            step_ms,
+            steps: Self::DEFAULT_STEPS,
+            repeats: 1,
+            pause_ms: 0,
+            cycle: 0,
+            index: 0,
+        }
+    }
+
+    /// Set the number of steps per cycle, clamped to 1 to
+    /// [`MAX_STEPS`](Self::MAX_STEPS).
+    ///
+    /// For [`EffectKind::Beep`] this is the number of beeps.
+    pub const fn with_steps(mut self, steps: u16) -> Self {
+        self.steps = if steps < 1 {
+            1
+        } else if steps > Self::MAX_STEPS {
+            Self::MAX_STEPS
+        } else {
+            steps
+        };
+        self
+    }
+
+    /// Set the number of cycles, or 0 to repeat forever.
+    pub const fn with_repeats(mut self, repeats: u16) -> Self {
+        self.repeats = repeats;
+        self
+    }
+
+    /// Set the silence after each cycle, in milliseconds.
+    pub const fn with_pause(mut self, pause_ms: u16) -> Self {
+        self.pause_ms = pause_ms;
+        self
+    }
+
+    /// A rising sweep, e.g. for "power on".
+    pub const fn rising_sweep() -> Self {
+        Self::new(EffectKind::Sweep, 400, 2000, 15).with_steps(24)
+    }
+
+    /// A falling sweep, e.g. for "power off".
+    pub const fn falling_sweep() -> Self {
+        Self::new(EffectKind::Sweep, 2000, 400, 15).with_steps(24)
+    }
+
+    /// A short, fast upward chirp.
+    pub const fn chirp() -> Self {
+        Self::new(EffectKind::Sweep, 1500, 3500, 5).with_steps(12)
+    }
+
+    /// A two-tone (hi-lo) siren, repeating until stopped.
+    pub const fn siren() -> Self {
+        Self::new(EffectKind::Alternate, 960, 770, 500)
+            .with_steps(2)
+            .with_repeats(0)
+    }
+
+    /// A rising and falling wail siren, repeating until stopped.
+    pub const fn wail() -> Self {
+        Self::new(EffectKind::Wail, 600, 1400, 40)
+            .with_steps(25)
+            .with_repeats(0)
+    }
+
+    /// A rapid trill between two close notes.
+    pub const fn trill() -> Self {
+        Self::new(EffectKind::Alternate, 1319, 1397, 40).with_steps(20)
+    }
+
+    /// A "beep-beep" alarm, repeating until stopped.
+    pub const fn alarm() -> Self {
+        Self::new(EffectKind::Beep, 2500, 2500, 100)
+            .with_steps(2)
+            .with_repeats(0)
+            .with_pause(600)
+    }
+
+    /// A single short beep.
+    pub const fn beep() -> Self {
+        Self::new(EffectKind::Beep, 2000, 2000, 80).with_steps(1)
+    }
+
+    /// The kind of effect.
+    pub fn kind(&self) -> EffectKind {
+        self.kind
+    }
+
+    /// Number of cycles, 0 for forever.
+    pub fn repeats(&self) -> u16 {
+        self.repeats
+    }
+
+    /// Total length in milliseconds, or `None` if the effect repeats forever.
+    pub fn duration_ms(&self) -> Option<u32> {
+        if self.repeats == 0 {
+            return None;
+        }
+        let cycle = self.tones_per_cycle() as u32 * self.step_ms as u32 + self.pause_ms as u32;
+        Some(cycle * self.repeats as u32)
+    }
+
+    /// Number of tone steps in one cycle, not counting the pause.
+    fn tones_per_cycle(&self) -> u16 {
+        match self.kind {
+            EffectKind::Sweep | EffectKind::Alternate => self.steps,
+            EffectKind::Wail => (self.steps * 2).saturating_sub(2).max(1),
+            // A beep and a silence for each step, without a trailing silence
+            EffectKind::Beep => self.steps * 2 - 1,
+        }
+    }
+
+    /// Frequency `index` of `count` evenly spaced between start and end.
+    fn interpolate(&self, index: u16, count: u16) -> u16 {
+        if count < 2 {
+            return self.start_hz;
+        }
+        let start = self.start_hz as i32;
+        let end = self.end_hz as i32;
+        (start + (end - start) * index as i32 / (count as i32 - 1)) as u16
+    }
+
+    fn step_at(&self, index: u16) -> ToneStep {
+        let frequency = match self.kind {
+            EffectKind::Sweep => self.interpolate(index, self.steps),
+            EffectKind::Wail => {
+                let up = if index < self.steps {
+                    index
+                } else {
+                    2 * (self.steps - 1) - index
+                };
+                self.interpolate(up, self.steps)
+            }
+            EffectKind::Alternate if index % 2 == 0 => self.start_hz,
+            EffectKind::Alternate => self.end_hz,
+            EffectKind::Beep if index % 2 == 0 => self.start_hz,
+            EffectKind::Beep => 0,
+        };
+        ToneStep::new(frequency, self.step_ms)
+    }
+}
+
+impl ToneSource for SoundEffect {
+    fn next_step(&mut self) -> Option<ToneStep> {
+        loop {
+            if self.repeats != 0 && self.cycle >= self.repeats {
+                return None;
+            }
+            let tones = self.tones_per_cycle();
+            let index = self.index;
+            self.index += 1;
+            if index < tones {
+                return Some(self.step_at(index));
+            }
+            self.index = 0;
+            self.cycle = self.cycle.saturating_add(1);
+            if self.pause_ms > 0 {
+                return Some(ToneStep::rest(self.pause_ms));
+            }
+        }
+    }
+
+    fn rewind(&mut self) {
+        self.cycle = 0;
+        self.index = 0;
+    }
+}
+
+const fn clamp_frequency(hz: u16) -> u16 {
+    if hz < MIN_FREQUENCY {
+        MIN_FREQUENCY
+    } else {
+        hz
+    }
+}
@@ -4,17 +4,50 @@
 //! > **EXPERIMENTAL**: This driver is a work-in-progress and is based on the TCA9548A I2C
 //! > multiplexer (such as the SparkFun Qwiic Mux Breakout). It has NOT yet been tested on physical
 //! > Modulino Hub hardware, as the official Modulino Hub was never officially released.
+//!
+//! Devices behind the hub can be driven directly through a [`HubPort`] (exclusive
+//! borrow of the hub) or a [`HubChannel`] (shared through a `RefCell`). Both implement
+//! `embedded_hal::i2c::I2c` and select their channel before every transaction, so any
+//! driver can be built on top of them.
+//!
+//! Several channels can be enabled at once with [`Hub::select_mask`], and the
+//! control register can be read back with [`Hub::read_control`]. [`Hub::scan_port`]
+//! lists the devices found behind a single channel.
+//!
+//! Up to eight hubs can share a bus by strapping A0-A2 (addresses 0x70-0x77, see
+//! [`Hub::new_with_pins`]). Hubs can also be chained: a downstream hub is created on
+//! a [`HubChannel`] of the upstream one, and its own channels are then reached
+//! through that channel.
+
+use core::cell::RefCell;
 
-use crate::Result;
-use embedded_hal::i2c::I2c;
+use crate::{addresses, Modulino, ModulinoKind, Result, ScanResult};
+use embedded_hal::i2c::{ErrorType, I2c, Operation};
 
 /// Default address for the TCA9548A multiplexer (0x70).
-pub const DEFAULT_ADDRESS: u8 = 0x70;
+pub const DEFAULT_ADDRESS: u8 = addresses::HUB[0];
+
+/// All addresses selectable with the A0-A2 pins (0x70-0x77).
+pub(crate) const ADDRESSES: [u8; 8] = addresses::HUB;
+
+/// Compute the TCA9548A address for the given A0-A2 pin levels.
+pub(crate) const fn address_from_pins(a0: bool, a1: bool, a2: bool) -> u8 {
+    DEFAULT_ADDRESS | (a0 as u8) | ((a1 as u8) << 1) | ((a2 as u8) << 2)
+}
+
+/// Remove every device in `baseline` from `found`.
+pub(crate) fn subtract(found: &mut ScanResult, baseline: &ScanResult) {
+    for (address, _) in baseline.iter() {
+        found.remove(address);
+    }
+}
 
 /// Driver for the Modulino Hub (TCA9548A I2C multiplexer).
 pub struct Hub<I2C> {
     i2c: I2C,
     address: u8,
+    /// Last control byte written, or `None` if unknown.
+    control: Option<u8>,
 }
 
 impl<I2C, E> Hub<I2C>
@@ -28,7 +61,23 @@ where
 
     /// Create a new Hub instance with a custom address.
     pub fn new_with_address(i2c: I2C, address: u8) -> Self {
-        Self { i2c, address }
+        Self {
+            i2c,
+            address,
+            control: None,
+        }
+    }
+
+    /// Create a new Hub instance from the levels of its A0-A2 address pins.
+    ///
+    /// All pins low gives the default address 0x70, all pins high gives 0x77.
+    pub fn new_with_pins(i2c: I2C, a0: bool, a1: bool, a2: bool) -> Self {
+        Self::new_with_address(i2c, address_from_pins(a0, a1, a2))
+    }
+
+    /// Get the I2C address.
+    pub fn address(&self) -> u8 {
+        self.address
     }
 
     /// Select an active port channel (0 to 7).
@@ -38,19 +87,165 @@ where
         if port >= 8 {
             return Err(crate::Error::InvalidParameter);
         }
-        let control_byte = 1 << port;
-        self.i2c
-            .write(self.address, &[control_byte])
-            .map_err(crate::Error::I2c)
+        self.write_control(1 << port).map_err(crate::Error::I2c)
+    }
+
+    /// Enable several port channels at once.
+    ///
+    /// Bit `n` of `mask` enables port `n`; all other channels are disabled.
+    /// Devices behind the enabled ports must have distinct addresses.
+    pub fn select_mask(&mut self, mask: u8) -> Result<(), E> {
+        self.write_control(mask).map_err(crate::Error::I2c)
     }
 
     /// Clear/deselect all port channels.
     ///
     /// This disables all I2C channels on the multiplexer.
     pub fn clear(&mut self) -> Result<(), E> {
-        self.i2c
-            .write(self.address, &[0x00])
-            .map_err(crate::Error::I2c)
+        self.write_control(0x00).map_err(crate::Error::I2c)
+    }
+
+    /// Read back the control register.
+    ///
+    /// Returns the bitmask of enabled port channels, as reported by the device.
+    pub fn read_control(&mut self) -> Result<u8, E> {
+        let mut buf = [0u8; 1];
+        match self.i2c.read(self.address, &mut buf) {
+            Ok(()) => {
+                self.control = Some(buf[0]);
+                Ok(buf[0])
+            }
+            Err(e) => {
+                self.control = None;
+                Err(crate::Error::I2c(e))
+            }
+        }
+    }
+
+    /// List the devices behind a single port channel (0 to 7).
+    ///
+    /// The bus is scanned once with all channels disabled and once with only
+    /// `port` enabled; devices seen both times (on the main bus, including this
+    /// hub) are left out. All channels are disabled afterwards.
+    ///
+    /// If a chained hub sits behind `port`, the devices behind its currently
+    /// enabled channels are reported too.
+    ///
+    /// > [!WARNING]
+    /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
+    pub fn scan_port(&mut self, port: u8) -> Result<ScanResult, E> {
+        if port >= 8 {
+            return Err(crate::Error::InvalidParameter);
+        }
+        let baseline = self.scan_baseline()?;
+        let found = self.scan_behind(1 << port, &baseline)?;
+        self.clear()?;
+        Ok(found)
+    }
+
+    /// List the devices behind every port channel, indexed by port.
+    ///
+    /// See [`scan_port`](Self::scan_port). The main bus is only scanned once.
+    ///
+    /// > [!WARNING]
+    /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
+    pub fn scan_ports(&mut self) -> Result<[ScanResult; 8], E> {
+        let baseline = self.scan_baseline()?;
+        let mut results: [ScanResult; 8] = Default::default();
+        for (port, result) in results.iter_mut().enumerate() {
+            *result = self.scan_behind(1 << port, &baseline)?;
+        }
+        self.clear()?;
+        Ok(results)
+    }
+
+    fn scan_baseline(&mut self) -> Result<ScanResult, E> {
+        self.clear()?;
+        Ok(crate::scan(&mut self.i2c))
+    }
+
+    fn scan_behind(&mut self, mask: u8, baseline: &ScanResult) -> Result<ScanResult, E> {
+        self.select_mask(mask)?;
+        let mut found = crate::scan(&mut self.i2c);
+        subtract(&mut found, baseline);
+        Ok(found)
+    }
+
+    /// Select a port channel (0 to 7) unless it is already the only one selected.
+    ///
+    /// A mask with additional channels enabled counts as not selected, so the
+    /// control register is rewritten. This is what [`HubPort`] and [`HubChannel`] call before each transaction.
+    pub fn ensure_selected(&mut self, port: u8) -> Result<(), E> {
+        if port >= 8 {
+            return Err(crate::Error::InvalidParameter);
+        }
+        self.select_cached(port).map_err(crate::Error::I2c)
+    }
+
+    /// Forget the cached channel selection.
+    ///
+    /// Call this if something else may have reprogrammed the multiplexer (for
+    /// example after a reset), so the next port access writes the control register.
+    pub fn invalidate(&mut self) {
+        self.control = None;
+    }
+
+    /// Get a [`HubPort`] for one of the port channels (0 to 7).
+    pub fn port(&mut self, port: u8) -> Result<HubPort<'_, I2C>, E> {
+        HubPort::new(self, port)
+    }
+
+    fn write_control(&mut self, control: u8) -> core::result::Result<(), E> {
+        match self.i2c.write(self.address, &[control]) {
+            Ok(()) => {
+                self.control = Some(control);
+                Ok(())
+            }
+            Err(e) => {
+                // The mux may or may not have latched the byte
+                self.control = None;
+                Err(e)
+            }
+        }
+    }
+
+    fn select_cached(&mut self, port: u8) -> core::result::Result<(), E> {
+        let control = 1 << port;
+        if self.control == Some(control) {
+            return Ok(());
+        }
+        self.write_control(control)
+    }
+
+    fn port_transaction(
+        &mut self,
+        port: u8,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> core::result::Result<(), E> {
+        self.select_cached(port)?;
+        self.i2c.transaction(address, operations)
+    }
+
+    fn port_read(&mut self, port: u8, address: u8, read: &mut [u8]) -> core::result::Result<(), E> {
+        self.select_cached(port)?;
+        self.i2c.read(address, read)
+    }
+
+    fn port_write(&mut self, port: u8, address: u8, write: &[u8]) -> core::result::Result<(), E> {
+        self.select_cached(port)?;
+        self.i2c.write(address, write)
+    }
+
+    fn port_write_read(
+        &mut self,
+        port: u8,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> core::result::Result<(), E> {
+        self.select_cached(port)?;
+        self.i2c.write_read(address, write, read)
     }
 
     /// Release the I2C bus.
@@ -59,7 +254,38 @@ where
     }
 }
 
+impl<I2C: I2c> Modulino<I2C> for Hub<I2C> {
+    const KIND: ModulinoKind = ModulinoKind::Hub;
+    const DEFAULT_ADDRESSES: &'static [u8] = &ADDRESSES;
+    const PINSTRAP: &'static [u8] = &[];
+
+    fn new_with_address(i2c: I2C, address: u8) -> Result<Self, I2C::Error> {
+        Ok(Self::new_with_address(i2c, address))
+    }
+
+    fn address(&self) -> u8 {
+        self.address
+    }
+
+    fn release(self) -> I2C {
+        self.i2c
+    }
+}
+
 /// A helper representing a specific port of the Hub.
+///
+/// `HubPort` implements `I2c`: every transaction first selects this port (skipping
+/// the write if it is already selected), so a driver can be built directly on it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use modulino::{Buttons, Hub};
+///
+/// let mut hub = Hub::new(i2c);
+/// let mut buttons = Buttons::new(hub.port(2)?)?;
+/// buttons.set_leds(true, false, false)?;
+/// ```
 pub struct HubPort<'a, I2C> {
     hub: &'a mut Hub<I2C>,
     port: u8,
@@ -70,8 +296,19 @@ where
     I2C: I2c<Error = E>,
 {
     /// Create a new HubPort.
-    pub fn new(hub: &'a mut Hub<I2C>, port: u8) -> Self {
-        Self { hub, port }
+    ///
+    /// Returns [`Error::InvalidParameter`](crate::Error::InvalidParameter)
+    /// if `port` is not in the range 0 to 7.
+    pub fn new(hub: &'a mut Hub<I2C>, port: u8) -> Result<Self, E> {
+        if port >= 8 {
+            return Err(crate::Error::InvalidParameter);
+        }
+        Ok(Self { hub, port })
+    }
+
+    /// Get the port number.
+    pub fn port(&self) -> u8 {
+        self.port
     }
 
     /// Enable this port channel on the multiplexer.
@@ -84,3 +321,129 @@ where
         self.hub.clear()
     }
 }
+
+impl<I2C: I2c> ErrorType for HubPort<'_, I2C> {
+    type Error = I2C::Error;
+}
+
+impl<I2C: I2c> I2c for HubPort<'_, I2C> {
+    fn read(&mut self, address: u8, read: &mut [u8]) -> core::result::Result<(), Self::Error> {
+        self.hub.port_read(self.port, address, read)
+    }
+
+    fn write(&mut self, address: u8, write: &[u8]) -> core::result::Result<(), Self::Error> {
+        self.hub.port_write(self.port, address, write)
+    }
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> core::result::Result<(), Self::Error> {
+        self.hub.port_write_read(self.port, address, write, read)
+    }
+
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> core::result::Result<(), Self::Error> {
+        self.hub.port_transaction(self.port, address, operations)
+    }
+}
+
+/// A shareable handle to one port of a Hub.
+///
+/// Unlike [`HubPort`], which borrows the hub exclusively, any number of
+/// `HubChannel`s can share a `RefCell<Hub>`. This lets several drivers (for
+/// example identical modules on different ports) live side by side, each
+/// selecting its own channel before talking to its device.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use core::cell::RefCell;
+/// use modulino::{Buttons, Hub, HubChannel};
+///
+/// let hub = RefCell::new(Hub::new(i2c));
+/// let mut buttons_a = Buttons::new(HubChannel::new(&hub, 0)?)?;
+/// let mut buttons_b = Buttons::new(HubChannel::new(&hub, 1)?)?;
+///
+/// let a = buttons_a.read()?;
+/// let b = buttons_b.read()?;
+///
+/// // A second hub (A0 high, 0x71) chained behind port 7 of the first
+/// let downstream = RefCell::new(Hub::new_with_pins(HubChannel::new(&hub, 7)?, true, false, false));
+/// let mut buttons_c = Buttons::new(HubChannel::new(&downstream, 0)?)?;
+/// ```
+pub struct HubChannel<'a, I2C> {
+    hub: &'a RefCell<Hub<I2C>>,
+    port: u8,
+}
+
+impl<'a, I2C: I2c> HubChannel<'a, I2C> {
+    /// Create a new HubChannel.
+    ///
+    /// Returns [`Error::InvalidParameter`](crate::Error::InvalidParameter)
+    /// if `port` is not in the range 0 to 7.
+    pub fn new(hub: &'a RefCell<Hub<I2C>>, port: u8) -> Result<Self, I2C::Error> {
+        if port >= 8 {
+            return Err(crate::Error::InvalidParameter);
+        }
+        Ok(Self { hub, port })
+    }
+
+    /// Get the port number.
+    pub fn port(&self) -> u8 {
+        self.port
+    }
+
+    /// Enable this port channel on the multiplexer.
+    pub fn select(&self) -> Result<(), I2C::Error> {
+        self.hub.borrow_mut().select(self.port)
+    }
+}
+
+impl<I2C> Clone for HubChannel<'_, I2C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I2C> Copy for HubChannel<'_, I2C> {}
+
+impl<I2C: I2c> ErrorType for HubChannel<'_, I2C> {
+    type Error = I2C::Error;
+}
+
+impl<I2C: I2c> I2c for HubChannel<'_, I2C> {
+    fn read(&mut self, address: u8, read: &mut [u8]) -> core::result::Result<(), Self::Error> {
+        self.hub.borrow_mut().port_read(self.port, address, read)
+    }
+
+    fn write(&mut self, address: u8, write: &[u8]) -> core::result::Result<(), Self::Error> {
+        self.hub.borrow_mut().port_write(self.port, address, write)
+    }
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> core::result::Result<(), Self::Error> {
+        self.hub
+            .borrow_mut()
+            .port_write_read(self.port, address, write, read)
+    }
+
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> core::result::Result<(), Self::Error> {
+        self.hub
+            .borrow_mut()
+            .port_transaction(self.port, address, operations)
+    }
+}
@@ -100,6 +100,19 @@ where
         Ok(u16::from_be_bytes(buf))
     }
 
+    /// Read a 32-bit value from a 16-bit register (Big Endian address and value).
+    pub fn read_reg16_u32(&mut self, reg: u16) -> Result<u32, E> {
+        let reg_bytes = reg.to_be_bytes();
+        let mut buf = [0u8; 4];
+        self.write_read(&reg_bytes, &mut buf)?;
+        Ok(u32::from_be_bytes(buf))
+    }
+
+    /// Read multiple bytes starting from a 16-bit register (Big Endian address).
+    pub fn read_regs16(&mut self, reg: u16, buf: &mut [u8]) -> Result<(), E> {
+        self.write_read(&reg.to_be_bytes(), buf)
+    }
+
     /// Release the I2C bus.
     pub fn release(self) -> I2C {
         self.i2c
@@ -2,7 +2,7 @@
 //!
 //! The Modulino Joystick module is an analog joystick with a push button.
 
-use crate::{addresses, Error, I2cDevice, Result};
+use crate::{addresses, pinstrap, I2cDevice, Modulino, ModulinoKind, Result};
 use embedded_hal::i2c::I2c;
 
 /// Driver for the Modulino Joystick module.
@@ -55,15 +55,7 @@ where
     /// > [!WARNING]
     /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
     pub fn discover(i2c: &mut I2C) -> Result<u8, E> {
-        let addresses = [addresses::JOYSTICK];
-        for &addr in &addresses {
-            if i2c.write(addr, &[]).is_ok() {
-                return Ok(addr);
-            }
-        }
-        i2c.write(addresses[0], &[])
-            .map(|_| addresses[0])
-            .map_err(Error::I2c)
+        <Self as Modulino<I2C>>::probe(i2c)
     }
 
     /// Create a new Joystick instance with a custom address.
@@ -99,22 +91,7 @@ where
         let mut buf = [0u8; 4]; // 1 pinstrap + 2 axes + 1 button
         self.device.read(&mut buf)?;
 
-        // Skip first byte (pinstrap address)
-        let mut raw_x = buf[1];
-        let mut raw_y = buf[2];
-
-        // Apply joint rectangular deadzone matching reference C++ exactly:
-        // Snap to 128 (center) only if BOTH coordinates are within the deadzone threshold.
-        let dx = (raw_x as i16) - 128;
-        let dy = (raw_y as i16) - 128;
-        if dx.abs() < self.deadzone as i16 && dy.abs() < self.deadzone as i16 {
-            raw_x = 128;
-            raw_y = 128;
-        }
-
-        self.x = ((raw_x as i16) - 128) as i8;
-        self.y = ((raw_y as i16) - 128) as i8;
-        self.button_pressed = buf[3] != 0;
+        (self.x, self.y, self.button_pressed) = decode(&buf, self.deadzone);
 
         Ok(self.x != previous_x || self.y != previous_y || self.button_pressed != previous_button)
     }
@@ -158,20 +135,14 @@ where
 
     /// Get the magnitude of joystick displacement from center.
     pub fn magnitude(&self) -> f32 {
-        let x = self.x as f32;
-        let y = self.y as f32;
-        libm::sqrtf(x * x + y * y)
+        magnitude(self.x, self.y)
     }
 
     /// Get the angle of joystick displacement in radians.
     ///
     /// Returns 0 when centered. Angle is measured counter-clockwise from the positive X-axis.
     pub fn angle(&self) -> f32 {
-        if self.is_centered() {
-            0.0
-        } else {
-            libm::atan2f(self.y as f32, self.x as f32)
-        }
+        angle(self.x, self.y)
     }
 
     /// Release the I2C bus.
@@ -179,3 +150,59 @@ where
         self.device.release()
     }
 }
+
+impl<I2C: I2c> Modulino<I2C> for Joystick<I2C> {
+    const KIND: ModulinoKind = ModulinoKind::Joystick;
+    const DEFAULT_ADDRESSES: &'static [u8] = &[addresses::JOYSTICK];
+    const PINSTRAP: &'static [u8] = &[pinstrap::JOYSTICK];
+
+    fn new_with_address(i2c: I2C, address: u8) -> Result<Self, I2C::Error> {
+        Self::new_with_address(i2c, address)
+    }
+
+    fn address(&self) -> u8 {
+        self.device.address
+    }
+
+    fn release(self) -> I2C {
+        self.device.release()
+    }
+}
+
+/// Decode a raw read (1 pinstrap + 2 axes + 1 button) into `(x, y, pressed)`.
+pub(crate) fn decode(buf: &[u8; 4], deadzone: u8) -> (i8, i8, bool) {
+    // Skip first byte (pinstrap address)
+    let mut raw_x = buf[1];
+    let mut raw_y = buf[2];
+
+    // Apply joint rectangular deadzone matching reference C++ exactly:
+    // Snap to 128 (center) only if BOTH coordinates are within the deadzone threshold.
+    let dx = (raw_x as i16) - 128;
+    let dy = (raw_y as i16) - 128;
+    if dx.abs() < deadzone as i16 && dy.abs() < deadzone as i16 {
+        raw_x = 128;
+        raw_y = 128;
+    }
+
+    (
+        ((raw_x as i16) - 128) as i8,
+        ((raw_y as i16) - 128) as i8,
+        buf[3] != 0,
+    )
+}
+
+/// Euclidean distance of `(x, y)` from center.
+pub(crate) fn magnitude(x: i8, y: i8) -> f32 {
+    let x = x as f32;
+    let y = y as f32;
+    libm::sqrtf(x * x + y * y)
+}
+
+/// Angle of `(x, y)` in radians, counter-clockwise from the positive X-axis.
+pub(crate) fn angle(x: i8, y: i8) -> f32 {
+    if x == 0 && y == 0 {
+        0.0
+    } else {
+        libm::atan2f(y as f32, x as f32)
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! The Modulino Knob module is a rotary encoder with a push button.
 
-use crate::{addresses, Error, I2cDevice, Result};
+use crate::{addresses, pinstrap, Error, I2cDevice, Modulino, ModulinoKind, Result};
 use embedded_hal::i2c::I2c;
 
 /// Driver for the Modulino Knob module (rotary encoder).
@@ -53,15 +53,7 @@ where
     /// > [!WARNING]
     /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
     pub fn discover(i2c: &mut I2C) -> Result<u8, E> {
-        let addresses = addresses::KNOB;
-        for &addr in &addresses {
-            if i2c.write(addr, &[]).is_ok() {
-                return Ok(addr);
-            }
-        }
-        i2c.write(addresses[0], &[])
-            .map(|_| addresses[0])
-            .map_err(Error::I2c)
+        <Self as Modulino<I2C>>::probe(i2c)
     }
 
     /// Create a new Knob instance with a custom address.
@@ -108,12 +100,7 @@ where
     fn read_data(&mut self) -> Result<(i16, bool), E> {
         let mut buf = [0u8; 4]; // 1 pinstrap + 2 encoder + 1 button
         self.device.read(&mut buf)?;
-
-        // Skip first byte (pinstrap address)
-        let raw_value = i16::from_le_bytes([buf[1], buf[2]]);
-        let pressed = buf[3] != 0;
-
-        Ok((raw_value, pressed))
+        Ok(decode(&buf))
     }
 
     /// Update the encoder state.
@@ -163,12 +150,8 @@ where
     }
 
     /// Internal method to set the encoder value on the device.
-    fn set_value_internal(&mut self, mut value: i16) -> Result<(), E> {
-        if self.bug_on_set {
-            value = -value;
-        }
-        let bytes = value.to_le_bytes();
-        let data = [bytes[0], bytes[1], 0, 0];
+    fn set_value_internal(&mut self, value: i16) -> Result<(), E> {
+        let data = value_payload(value, self.bug_on_set);
         self.device.write(&data)?;
         Ok(())
     }
@@ -215,15 +198,7 @@ where
     /// - Negative value for counter-clockwise rotation
     /// - 0 for no rotation
     pub fn rotation_delta(&self, previous_value: i16) -> i16 {
-        // Handle wraparound
-        let diff = self.value.wrapping_sub(previous_value);
-
-        // Check for wraparound (if diff is too large, it wrapped)
-        if !(-16384..=16384).contains(&diff) {
-            diff.wrapping_add(i16::MIN)
-        } else {
-            diff
-        }
+        rotation_delta(self.value, previous_value)
     }
 
     /// Get the rotation direction since the last position with a 30ms debounce check.
@@ -261,3 +236,52 @@ where
         self.device.release()
     }
 }
+
+impl<I2C: I2c> Modulino<I2C> for Knob<I2C> {
+    const KIND: ModulinoKind = ModulinoKind::Knob;
+    const DEFAULT_ADDRESSES: &'static [u8] = &addresses::KNOB;
+    const PINSTRAP: &'static [u8] = &pinstrap::KNOB;
+
+    fn new_with_address(i2c: I2C, address: u8) -> Result<Self, I2C::Error> {
+        Self::new_with_address(i2c, address)
+    }
+
+    fn address(&self) -> u8 {
+        self.device.address
+    }
+
+    fn release(self) -> I2C {
+        self.device.release()
+    }
+}
+
+/// Decode a raw read (1 pinstrap + 2 encoder + 1 button) into `(value, pressed)`.
+pub(crate) fn decode(buf: &[u8; 4]) -> (i16, bool) {
+    // Skip first byte (pinstrap address)
+    let raw_value = i16::from_le_bytes([buf[1], buf[2]]);
+    let pressed = buf[3] != 0;
+    (raw_value, pressed)
+}
+
+/// Build the payload that sets the encoder value, compensating for the
+/// inverted set/get firmware bug when needed.
+pub(crate) fn value_payload(mut value: i16, bug_on_set: bool) -> [u8; 4] {
+    if bug_on_set {
+        value = -value;
+    }
+    let bytes = value.to_le_bytes();
+    [bytes[0], bytes[1], 0, 0]
+}
+
+/// Signed difference between two encoder values, accounting for wraparound.
+pub(crate) fn rotation_delta(value: i16, previous_value: i16) -> i16 {
+    // Handle wraparound
+    let diff = value.wrapping_sub(previous_value);
+
+    // Check for wraparound (if diff is too large, it wrapped)
+    if !(-16384..=16384).contains(&diff) {
+        diff.wrapping_add(i16::MIN)
+    } else {
+        diff
+    }
+}
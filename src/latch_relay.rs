@@ -3,7 +3,7 @@
 //! The Modulino Latch Relay module is a latching relay that maintains its state
 //! even when power is removed.
 
-use crate::{addresses, Error, I2cDevice, Result};
+use crate::{addresses, pinstrap, I2cDevice, Modulino, ModulinoKind, Result};
 use embedded_hal::i2c::I2c;
 
 /// Driver for the Modulino Latch Relay module.
@@ -48,15 +48,7 @@ where
     /// > [!WARNING]
     /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
     pub fn discover(i2c: &mut I2C) -> Result<u8, E> {
-        let addresses = [addresses::LATCH_RELAY];
-        for &addr in &addresses {
-            if i2c.write(addr, &[]).is_ok() {
-                return Ok(addr);
-            }
-        }
-        i2c.write(addresses[0], &[])
-            .map(|_| addresses[0])
-            .map_err(Error::I2c)
+        <Self as Modulino<I2C>>::probe(i2c)
     }
 
     /// Create a new LatchRelay instance with a custom address.
@@ -112,19 +104,7 @@ where
     pub fn is_on(&mut self) -> Result<Option<bool>, E> {
         let mut buf = [0u8; 4]; // 1 pinstrap + 3 status
         self.device.read(&mut buf)?;
-
-        // Skip first byte (pinstrap address)
-        let status0 = buf[1];
-        let status1 = buf[2];
-
-        // If both are 0, state is unknown (maintained from before power off)
-        if status0 == 0 && status1 == 0 {
-            Ok(None)
-        } else if status0 == 1 {
-            Ok(Some(false)) // Relay is off
-        } else {
-            Ok(Some(true)) // Relay is on
-        }
+        Ok(decode_state(&buf))
     }
 
     /// Release the I2C bus.
@@ -132,3 +112,37 @@ where
         self.device.release()
     }
 }
+
+impl<I2C: I2c> Modulino<I2C> for LatchRelay<I2C> {
+    const KIND: ModulinoKind = ModulinoKind::LatchRelay;
+    const DEFAULT_ADDRESSES: &'static [u8] = &[addresses::LATCH_RELAY];
+    const PINSTRAP: &'static [u8] = &[pinstrap::LATCH_RELAY];
+
+    fn new_with_address(i2c: I2C, address: u8) -> Result<Self, I2C::Error> {
+        Self::new_with_address(i2c, address)
+    }
+
+    fn address(&self) -> u8 {
+        self.device.address
+    }
+
+    fn release(self) -> I2C {
+        self.device.release()
+    }
+}
+
+/// Decode a raw status read (1 pinstrap + 3 status) into the relay state.
+pub(crate) fn decode_state(buf: &[u8; 4]) -> Option<bool> {
+    // Skip first byte (pinstrap address)
+    let status0 = buf[1];
+    let status1 = buf[2];
+
+    // If both are 0, state is unknown (maintained from before power off)
+    if status0 == 0 && status1 == 0 {
+        None
+    } else if status0 == 1 {
+        Some(false) // Relay is off
+    } else {
+        Some(true) // Relay is on
+    }
+}
@@ -3,7 +3,8 @@
 //! The Modulino LED Matrix module consists of a 12x8 LED matrix display (96 LEDs)
 //! driven by a firmware coprocessor over I2C.
 
-use crate::{addresses, Error, I2cDevice, Result};
+use crate::{addresses, pinstrap, Error, I2cDevice, Modulino, ModulinoKind, Result};
+use embedded_hal::delay::DelayNs;
 use embedded_hal::i2c::I2c;
 
 /// Display mode for the LED Matrix.
@@ -22,8 +23,7 @@ pub enum DisplayMode {
 /// Driver for the Modulino LED Matrix module.
 pub struct LedMatrix<I2C> {
     device: I2cDevice<I2C>,
-    mode: DisplayMode,
-    buffer: [u8; 48],
+    frame: Frame,
 }
 
 impl<I2C, E> LedMatrix<I2C>
@@ -42,23 +42,14 @@ where
     /// > [!WARNING]
     /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
     pub fn discover(i2c: &mut I2C) -> Result<u8, E> {
-        let addresses = [addresses::LED_MATRIX];
-        for &addr in &addresses {
-            if i2c.write(addr, &[]).is_ok() {
-                return Ok(addr);
-            }
-        }
-        i2c.write(addresses[0], &[])
-            .map(|_| addresses[0])
-            .map_err(Error::I2c)
+        <Self as Modulino<I2C>>::probe(i2c)
     }
 
     /// Create a new LedMatrix instance with a custom address.
     pub fn new_with_address(i2c: I2C, address: u8) -> Self {
         Self {
             device: I2cDevice::new(i2c, address),
-            mode: DisplayMode::default(),
-            buffer: [0u8; 48],
+            frame: Frame::new(),
         }
     }
 
@@ -77,52 +68,21 @@ where
         let mut query_buf = [0u8; 4];
         self.device.read(&mut query_buf)?;
 
-        let device_is_grayscale = &query_buf[1..4] == b"GS4";
-
-        // To switch the mode, the write transaction must match the size expected by
-        // the *current* device mode (48 bytes if currently in grayscale, 12 bytes if monochromatic).
-        if device_is_grayscale {
-            let mut payload = [0u8; 48];
-            match mode {
-                DisplayMode::Grayscale => {
-                    payload[0..3].copy_from_slice(b"GS4");
-                }
-                _ => {
-                    payload[0..3].copy_from_slice(b"MON");
-                }
-            }
-            self.device.write(&payload)?;
-        } else {
-            let mut payload = [0u8; 12];
-            match mode {
-                DisplayMode::Grayscale => {
-                    payload[0..3].copy_from_slice(b"GS4");
-                }
-                _ => {
-                    payload[0..3].copy_from_slice(b"MON");
-                }
-            }
-            self.device.write(&payload)?;
-        }
+        let payload = ModeSwitch::new(&query_buf, mode);
+        self.device.write(payload.as_bytes())?;
 
-        self.mode = mode;
-        self.clear_buffer();
+        self.frame.set_mode(mode);
         Ok(())
     }
 
     /// Get the current display mode.
     pub fn mode(&self) -> DisplayMode {
-        self.mode
-    }
-
-    /// Clear the local frame buffer.
-    fn clear_buffer(&mut self) {
-        self.buffer = [0u8; 48];
+        self.frame.mode
     }
 
     /// Turn off all LEDs and update the display.
     pub fn clear(&mut self) -> Result<(), E> {
-        self.clear_buffer();
+        self.frame.clear();
         self.show()
     }
 
@@ -132,14 +92,107 @@ where
     /// to the 4-bit grayscale range (0-15). In monochromatic modes, a non-zero
     /// brightness turns the LED on.
     pub fn set_pixel(&mut self, x: u8, y: u8, brightness: u8) -> Result<(), E> {
+        if !self.frame.set_pixel(x, y, brightness) {
+            return Err(Error::InvalidParameter);
+        }
+        Ok(())
+    }
+
+    /// Set the 4-bit grayscale value (0-15) of a specific pixel (Grayscale mode only).
+    pub fn set_grayscale_pixel(&mut self, x: u8, y: u8, value: u8) -> Result<(), E> {
+        if !self.frame.set_grayscale_pixel(x, y, value) {
+            return Err(Error::InvalidParameter);
+        }
+        Ok(())
+    }
+
+    /// Set the entire active frame from a slice.
+    ///
+    /// Expects 12 bytes for monochromatic modes or 48 bytes for grayscale mode.
+    pub fn set_frame(&mut self, frame: &[u8]) -> Result<(), E> {
+        if !self.frame.load(frame) {
+            return Err(Error::InvalidParameter);
+        }
+        self.show()
+    }
+
+    /// Render the current local buffer to the display.
+    pub fn show(&mut self) -> Result<(), E> {
+        let mut out = [0u8; 48];
+        let len = self.frame.render(&mut out);
+        self.device.write(&out[..len])?;
+        Ok(())
+    }
+
+    /// Get the current local buffer.
+    pub fn buffer(&self) -> &[u8] {
+        self.frame.buffer()
+    }
+
+    /// Release the I2C bus.
+    pub fn release(self) -> I2C {
+        self.device.release()
+    }
+}
+
+impl<I2C: I2c> Modulino<I2C> for LedMatrix<I2C> {
+    const KIND: ModulinoKind = ModulinoKind::LedMatrix;
+    const DEFAULT_ADDRESSES: &'static [u8] = &[addresses::LED_MATRIX];
+    const PINSTRAP: &'static [u8] = &[pinstrap::LED_MATRIX];
+
+    fn new_with_address(i2c: I2C, address: u8) -> Result<Self, I2C::Error> {
+        Ok(Self::new_with_address(i2c, address))
+    }
+
+    fn init<D: DelayNs>(&mut self, _delay: &mut D) -> Result<(), I2C::Error> {
+        LedMatrix::init(self)
+    }
+
+    fn address(&self) -> u8 {
+        self.device.address
+    }
+
+    fn release(self) -> I2C {
+        self.device.release()
+    }
+}
+
+/// Local frame buffer and display mode shared by the blocking and async drivers.
+pub(crate) struct Frame {
+    pub(crate) mode: DisplayMode,
+    buffer: [u8; 48],
+}
+
+impl Frame {
+    /// Create an empty frame in the default Monochromatic Vertical mode.
+    pub(crate) const fn new() -> Self {
+        Self {
+            mode: DisplayMode::MonochromaticVertical,
+            buffer: [0u8; 48],
+        }
+    }
+
+    /// Switch to a new mode, clearing the buffer.
+    pub(crate) fn set_mode(&mut self, mode: DisplayMode) {
+        self.mode = mode;
+        self.clear();
+    }
+
+    /// Clear the local frame buffer.
+    pub(crate) fn clear(&mut self) {
+        self.buffer = [0u8; 48];
+    }
+
+    /// Set a pixel. Returns `false` if the coordinates are out of range.
+    pub(crate) fn set_pixel(&mut self, x: u8, y: u8, brightness: u8) -> bool {
         match self.mode {
             DisplayMode::Grayscale => {
                 let val_4bit = brightness / 17; // scale 0..255 to 0..15
-                self.set_grayscale_pixel(x, y, val_4bit)?;
+                self.set_grayscale_pixel(x, y, val_4bit)
             }
             DisplayMode::MonochromaticVertical => {
                 if x >= 12 || y >= 8 {
-                    return Err(Error::InvalidParameter);
+                    return false;
                 }
                 let byte_idx = x as usize;
                 if brightness > 0 {
@@ -147,10 +200,11 @@ where
                 } else {
                     self.buffer[byte_idx] &= !(1 << y);
                 }
+                true
             }
             DisplayMode::MonochromaticHorizontal => {
                 if x >= 12 || y >= 8 {
-                    return Err(Error::InvalidParameter);
+                    return false;
                 }
                 let pixel_idx = (y * 12 + x) as usize;
                 let byte_idx = pixel_idx / 8;
@@ -160,15 +214,15 @@ where
                 } else {
                     self.buffer[byte_idx] &= !(1 << bit_offset);
                 }
+                true
             }
         }
-        Ok(())
     }
 
-    /// Set the 4-bit grayscale value (0-15) of a specific pixel (Grayscale mode only).
-    pub fn set_grayscale_pixel(&mut self, x: u8, y: u8, value: u8) -> Result<(), E> {
+    /// Set a 4-bit grayscale pixel. Returns `false` if the coordinates are out of range.
+    pub(crate) fn set_grayscale_pixel(&mut self, x: u8, y: u8, value: u8) -> bool {
         if x >= 12 || y >= 8 {
-            return Err(Error::InvalidParameter);
+            return false;
         }
         let col_offset = (x as usize) * 4;
         let byte_idx = col_offset + (y as usize) / 2;
@@ -180,69 +234,93 @@ where
         } else {
             self.buffer[byte_idx] = (self.buffer[byte_idx] & 0xF0) | val_4bit;
         }
-        Ok(())
+        true
     }
 
-    /// Set the entire active frame from a slice.
-    ///
-    /// Expects 12 bytes for monochromatic modes or 48 bytes for grayscale mode.
-    pub fn set_frame(&mut self, frame: &[u8]) -> Result<(), E> {
-        let expected_size = match self.mode {
-            DisplayMode::Grayscale => 48,
-            _ => 12,
-        };
+    /// Copy a full frame into the buffer. Returns `false` if `frame` is too short.
+    pub(crate) fn load(&mut self, frame: &[u8]) -> bool {
+        let expected_size = self.buffer().len();
         if frame.len() < expected_size {
-            return Err(Error::InvalidParameter);
+            return false;
         }
         self.buffer[0..expected_size].copy_from_slice(&frame[0..expected_size]);
-        self.show()
+        true
     }
 
-    /// Convert row-major monochromatic horizontal data to column-major vertical.
-    fn convert_to_column_major(data: &mut [u8; 12]) {
-        let mut col_major = [0u8; 12];
-        for (col, col_val) in col_major.iter_mut().enumerate() {
-            for row in 0..8 {
-                let pixel_idx = row * 12 + col;
-                let src_byte = pixel_idx / 8;
-                let src_bit = 7 - (pixel_idx % 8);
-                if ((data[src_byte] >> src_bit) & 1) != 0 {
-                    *col_val |= 1 << row;
-                }
-            }
+    /// The active part of the buffer for the current mode.
+    pub(crate) fn buffer(&self) -> &[u8] {
+        match self.mode {
+            DisplayMode::Grayscale => &self.buffer[0..48],
+            _ => &self.buffer[0..12],
         }
-        *data = col_major;
     }
 
-    /// Render the current local buffer to the display.
-    pub fn show(&mut self) -> Result<(), E> {
+    /// Render the wire format for the current mode into `out`, returning its length.
+    pub(crate) fn render(&self, out: &mut [u8; 48]) -> usize {
         match self.mode {
             DisplayMode::MonochromaticVertical => {
-                self.device.write(&self.buffer[0..12])?;
+                out[..12].copy_from_slice(&self.buffer[0..12]);
+                12
             }
             DisplayMode::MonochromaticHorizontal => {
                 let mut col_major = [0u8; 12];
                 col_major.copy_from_slice(&self.buffer[0..12]);
-                Self::convert_to_column_major(&mut col_major);
-                self.device.write(&col_major)?;
+                convert_to_column_major(&mut col_major);
+                out[..12].copy_from_slice(&col_major);
+                12
             }
             DisplayMode::Grayscale => {
-                self.device.write(&self.buffer[0..48])?;
+                out.copy_from_slice(&self.buffer);
+                48
             }
         }
-        Ok(())
     }
+}
 
-    /// Get the current local buffer.
-    pub fn buffer(&self) -> &[u8] {
-        match self.mode {
-            DisplayMode::Grayscale => &self.buffer[0..48],
-            _ => &self.buffer[0..12],
+/// Mode-switch payload sized for the mode the device is currently in.
+pub(crate) struct ModeSwitch {
+    payload: [u8; 48],
+    len: usize,
+}
+
+impl ModeSwitch {
+    /// Build the payload from a 4-byte mode query (1 pinstrap + 3 identifier).
+    pub(crate) fn new(query: &[u8; 4], mode: DisplayMode) -> Self {
+        let device_is_grayscale = &query[1..4] == b"GS4";
+
+        // To switch the mode, the write transaction must match the size expected by
+        // the *current* device mode (48 bytes if currently in grayscale, 12 bytes if monochromatic).
+        let len = if device_is_grayscale { 48 } else { 12 };
+        let mut payload = [0u8; 48];
+        match mode {
+            DisplayMode::Grayscale => {
+                payload[0..3].copy_from_slice(b"GS4");
+            }
+            _ => {
+                payload[0..3].copy_from_slice(b"MON");
+            }
         }
+        Self { payload, len }
     }
 
-    /// Release the I2C bus.
-    pub fn release(self) -> I2C {
-        self.device.release()
+    /// The bytes to write to the device.
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.payload[..self.len]
+    }
+}
+
+/// Convert row-major monochromatic horizontal data to column-major vertical.
+fn convert_to_column_major(data: &mut [u8; 12]) {
+    let mut col_major = [0u8; 12];
+    for (col, col_val) in col_major.iter_mut().enumerate() {
+        for row in 0..8 {
+            let pixel_idx = row * 12 + col;
+            let src_byte = pixel_idx / 8;
+            let src_bit = 7 - (pixel_idx % 8);
+            if ((data[src_byte] >> src_bit) & 1) != 0 {
+                *col_val |= 1 << row;
+            }
+        }
     }
+    *data = col_major;
 }
@@ -18,6 +18,9 @@
 //! - [`Pressure`] - Barometric pressure and temperature sensor
 //! - [`Light`] - RGB and ambient light sensor
 //!
+//! Every driver implements the [`Modulino`] trait, and [`scan`] finds out which
+//! modules are connected to a bus.
+//!
 //! ## Example
 //!
 //! ```rust,ignore
@@ -36,6 +39,9 @@
 //! ## Features
 //!
 //! - `defmt`: Enable `defmt` debug formatting for error types
+//! - `async`: Enable the [`asynch`] module with drivers built on `embedded-hal-async`
+//! - `sim`: Enable the [`sim`] module with register-level simulations of every Modulino
+//! - `linux`: Build the `modulino` command-line tool for Linux hosts (uses `linux-embedded-hal`)
 //!
 //! ## Hardware Requirements
 //!
@@ -47,10 +53,18 @@
 #![warn(missing_docs)]
 #![deny(unsafe_code)]
 
+mod animation;
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub mod asynch;
+mod bar_graph;
+mod button_events;
 mod buttons;
 mod buzzer;
 mod color;
+mod correction;
 mod distance;
+mod effects;
 mod error;
 mod hub;
 mod i2c_device;
@@ -59,29 +73,55 @@ mod knob;
 mod latch_relay;
 mod led_matrix;
 mod light;
+mod midi;
+mod modulino;
 mod movement;
 mod opto_relay;
+mod pitch;
+mod pixel_strip;
 mod pixels;
+mod power;
 mod pressure;
+mod rtttl;
+mod scan;
+mod sequencer;
+#[cfg(feature = "sim")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sim")))]
+pub mod sim;
 mod thermo;
 mod vibro;
 
+pub use animation::{Animation, AnimationKind, Direction, Palette};
+pub use bar_graph::{BarGraph, BarStyle};
+pub use button_events::{Button, ButtonEvent, ButtonEventList, ButtonEvents, ButtonTimings};
 pub use buttons::{ButtonLed, ButtonState, Buttons};
 pub use buzzer::{Buzzer, Note};
 pub use color::Color;
-pub use distance::Distance;
+pub use correction::ColorCorrection;
+pub use distance::{
+    DetectionMode, DetectionThresholds, Distance, DistanceMeasurement, InterruptSource, RangeStatus,
+};
+pub use effects::{EffectKind, SoundEffect};
 pub use error::{Error, Result};
-pub use hub::{Hub, HubPort};
+pub use hub::{Hub, HubChannel, HubPort};
 pub use i2c_device::I2cDevice;
 pub use joystick::Joystick;
 pub use knob::Knob;
 pub use latch_relay::LatchRelay;
 pub use led_matrix::{DisplayMode, LedMatrix};
 pub use light::{Gain, Light, LightMeasurement, MeasurementRate, Resolution};
+pub use midi::{MidiError, MidiFile, MidiPlayer, RangePolicy, MAX_TRACKS};
+pub use modulino::Modulino;
 pub use movement::{Movement, MovementValues};
 pub use opto_relay::OptoRelay;
+pub use pitch::{ParsePitchError, Pitch};
+pub use pixel_strip::PixelStrip;
 pub use pixels::Pixels;
+pub use power::PowerModel;
 pub use pressure::Pressure;
+pub use rtttl::{Rtttl, RtttlError, RtttlErrorKind, RtttlNote, RtttlNotes};
+pub use scan::{identify, scan, ModulinoKind, ScanResult, SCAN_RANGE};
+pub use sequencer::{Melody, Sequencer, ToneSource, ToneStep};
 pub use thermo::{Hs3003Error, Thermo, ThermoMeasurement};
 pub use vibro::{PowerLevel, Vibro};
 
@@ -131,6 +171,9 @@ pub mod addresses {
 
     /// Default address for Modulino LED Matrix
     pub const LED_MATRIX: u8 = 0x39;
+
+    /// Addresses for Modulino Hub (TCA9548A), selected with the A0-A2 pins
+    pub const HUB: [u8; 8] = [0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77];
 }
 
 /// Pinstrap address map for device type detection.
@@ -161,4 +204,7 @@ pub mod pinstrap {
 
     /// Pinstrap address for Vibro
     pub const VIBRO: u8 = 0x70;
+
+    /// Pinstrap address for LED Matrix
+    pub const LED_MATRIX: u8 = 0x72;
 }
@@ -9,7 +9,8 @@
 //! Note: This is an internal implementation because no stable `no_std` Rust crate
 //! currently exists for the LTR-381RGB sensor.
 
-use crate::{addresses, Error, Result};
+use crate::{addresses, Error, Modulino, ModulinoKind, Result};
+use embedded_hal::delay::DelayNs;
 use embedded_hal::i2c::I2c;
 
 /// Measurement result from the Light sensor.
@@ -229,6 +230,17 @@ pub enum MeasurementRate {
     Rate2000ms = 0x07,
 }
 
+// LTR-381RGB register addresses
+pub(crate) const REG_MAIN_CTRL: u8 = 0x00;
+pub(crate) const REG_MEAS_RATE: u8 = 0x04;
+pub(crate) const REG_GAIN: u8 = 0x05;
+pub(crate) const REG_PART_ID: u8 = 0x06;
+pub(crate) const REG_MAIN_STATUS: u8 = 0x07;
+pub(crate) const REG_DATA_IR: u8 = 0x0A;
+pub(crate) const REG_DATA_GREEN: u8 = 0x0D;
+pub(crate) const REG_DATA_RED: u8 = 0x10;
+pub(crate) const REG_DATA_BLUE: u8 = 0x13;
+
 /// Driver for the Modulino Light module (LTR-381RGB sensor).
 pub struct Light<I2C> {
     i2c: I2C,
@@ -241,16 +253,6 @@ impl<I2C, E> Light<I2C>
 where
     I2C: I2c<Error = E>,
 {
-    const REG_MAIN_CTRL: u8 = 0x00;
-    const REG_MEAS_RATE: u8 = 0x04;
-    const REG_GAIN: u8 = 0x05;
-    const REG_PART_ID: u8 = 0x06;
-    const REG_MAIN_STATUS: u8 = 0x07;
-    const REG_DATA_IR: u8 = 0x0A;
-    const REG_DATA_GREEN: u8 = 0x0D;
-    const REG_DATA_RED: u8 = 0x10;
-    const REG_DATA_BLUE: u8 = 0x13;
-
     /// Create a new Light instance.
     pub fn new(i2c: I2C) -> Self {
         Self::new_with_address(i2c, addresses::LIGHT)
@@ -263,15 +265,7 @@ where
     /// > [!WARNING]
     /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
     pub fn discover(i2c: &mut I2C) -> Result<u8, E> {
-        let addresses = [addresses::LIGHT];
-        for &addr in &addresses {
-            if i2c.write(addr, &[]).is_ok() {
-                return Ok(addr);
-            }
-        }
-        i2c.write(addresses[0], &[])
-            .map(|_| addresses[0])
-            .map_err(Error::I2c)
+        <Self as Modulino<I2C>>::probe(i2c)
     }
 
     /// Create a new Light instance with a custom address.
@@ -284,21 +278,26 @@ where
         }
     }
 
+    /// Get the I2C address.
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
     /// Initialize the sensor.
     pub fn init(&mut self) -> Result<(), E> {
         // Verify Part ID
         let mut part_id = [0u8; 1];
         self.i2c
-            .write_read(self.address, &[Self::REG_PART_ID], &mut part_id)
+            .write_read(self.address, &[REG_PART_ID], &mut part_id)
             .map_err(Error::I2c)?;
-        if (part_id[0] & 0xF0) != 0xC0 {
+        if !part_id_matches(part_id[0]) {
             return Err(Error::DeviceNotFound);
         }
 
         // Clear status
         let mut status = [0u8; 1];
         self.i2c
-            .write_read(self.address, &[Self::REG_MAIN_STATUS], &mut status)
+            .write_read(self.address, &[REG_MAIN_STATUS], &mut status)
             .map_err(Error::I2c)?;
 
         // Default config: 18x gain, 16-bit res, 25ms rate (Standard Arduino Modulino)
@@ -313,16 +312,15 @@ where
 
     /// Enable or disable the sensor measurements.
     pub fn enable(&mut self, enabled: bool) -> Result<(), E> {
-        let val = if enabled { 0x06 } else { 0x00 }; // 0x06 = RGB + ALS
         self.i2c
-            .write(self.address, &[Self::REG_MAIN_CTRL, val])
+            .write(self.address, &[REG_MAIN_CTRL, main_ctrl_value(enabled)])
             .map_err(Error::I2c)
     }
 
     /// Set sensor gain.
     pub fn set_gain(&mut self, gain: Gain) -> Result<(), E> {
         self.i2c
-            .write(self.address, &[Self::REG_GAIN, gain as u8])
+            .write(self.address, &[REG_GAIN, gain as u8])
             .map_err(Error::I2c)?;
         self.current_gain = gain;
         Ok(())
@@ -330,9 +328,8 @@ where
 
     /// Set ADC resolution and measurement rate.
     pub fn set_config(&mut self, res: Resolution, rate: MeasurementRate) -> Result<(), E> {
-        let val = ((res as u8) << 4) | (rate as u8);
         self.i2c
-            .write(self.address, &[Self::REG_MEAS_RATE, val])
+            .write(self.address, &[REG_MEAS_RATE, meas_rate_value(res, rate)])
             .map_err(Error::I2c)?;
         self.current_res = res;
         Ok(())
@@ -340,12 +337,12 @@ where
 
     /// Read the infrared channel value.
     pub fn ir(&mut self) -> Result<u32, E> {
-        self.read_channel(Self::REG_DATA_IR)
+        self.read_channel(REG_DATA_IR)
     }
 
     /// Read the raw ambient light level (Green channel).
     pub fn raw_lux(&mut self) -> Result<u32, E> {
-        self.read_channel(Self::REG_DATA_GREEN)
+        self.read_channel(REG_DATA_GREEN)
     }
 
     /// Read the calculated ambient light level in Lux.
@@ -363,9 +360,9 @@ where
     /// Read all sensor values including calculated Lux.
     pub fn read(&mut self) -> Result<LightMeasurement, E> {
         let ir = self.ir()?;
-        let green = self.read_channel(Self::REG_DATA_GREEN)?;
-        let red = self.read_channel(Self::REG_DATA_RED)?;
-        let blue = self.read_channel(Self::REG_DATA_BLUE)?;
+        let green = self.read_channel(REG_DATA_GREEN)?;
+        let red = self.read_channel(REG_DATA_RED)?;
+        let blue = self.read_channel(REG_DATA_BLUE)?;
 
         Ok(LightMeasurement {
             ir,
@@ -383,9 +380,7 @@ where
             .write_read(self.address, &[reg], &mut buf)
             .map_err(Error::I2c)?;
 
-        // Combine 3 bytes (LSB first)
-        let val = (buf[0] as u32) | ((buf[1] as u32) << 8) | ((buf[2] as u32) << 16);
-        Ok(val)
+        Ok(decode_channel(&buf))
     }
 
     /// Release the I2C bus.
@@ -394,7 +389,53 @@ where
     }
 }
 
-fn calculate_lux(ir: u32, raw_lux: u32, gain: Gain, res: Resolution) -> f32 {
+impl<I2C: I2c> Modulino<I2C> for Light<I2C> {
+    const KIND: ModulinoKind = ModulinoKind::Light;
+    const DEFAULT_ADDRESSES: &'static [u8] = &[addresses::LIGHT];
+    const PINSTRAP: &'static [u8] = &[];
+
+    fn new_with_address(i2c: I2C, address: u8) -> Result<Self, I2C::Error> {
+        Ok(Self::new_with_address(i2c, address))
+    }
+
+    fn init<D: DelayNs>(&mut self, _delay: &mut D) -> Result<(), I2C::Error> {
+        Light::init(self)
+    }
+
+    fn address(&self) -> u8 {
+        self.address
+    }
+
+    fn release(self) -> I2C {
+        self.i2c
+    }
+}
+
+/// Check the LTR-381RGB part ID (upper nibble 0xC).
+pub(crate) fn part_id_matches(part_id: u8) -> bool {
+    (part_id & 0xF0) == 0xC0
+}
+
+/// MAIN_CTRL value enabling or disabling measurements.
+pub(crate) fn main_ctrl_value(enabled: bool) -> u8 {
+    if enabled {
+        0x06 // RGB + ALS
+    } else {
+        0x00
+    }
+}
+
+/// MEAS_RATE value for a resolution and rate.
+pub(crate) fn meas_rate_value(res: Resolution, rate: MeasurementRate) -> u8 {
+    ((res as u8) << 4) | (rate as u8)
+}
+
+/// Combine 3 channel bytes (LSB first).
+pub(crate) fn decode_channel(buf: &[u8; 3]) -> u32 {
+    (buf[0] as u32) | ((buf[1] as u32) << 8) | ((buf[2] as u32) << 16)
+}
+
+pub(crate) fn calculate_lux(ir: u32, raw_lux: u32, gain: Gain, res: Resolution) -> f32 {
     if raw_lux == 0 {
         return 0.0;
     }
@@ -424,33 +465,8 @@ fn rgb_to_hsl(r: u32, g: u32, b: u32) -> (f32, f32, f32) {
     let g_n = (g as f32).min(MAX_VAL) / MAX_VAL;
     let b_n = (b as f32).min(MAX_VAL) / MAX_VAL;
 
-    let max = r_n.max(g_n).max(b_n);
-    let min = r_n.min(g_n).min(b_n);
-    let delta = max - min;
-
-    let l = (max + min) / 2.0;
-    let mut s = 0.0;
-    let mut h = 0.0;
-
-    if delta != 0.0 {
-        s = if l > 0.5 {
-            delta / (2.0 - max - min)
-        } else {
-            delta / (max + min)
-        };
-
-        if max == r_n {
-            h = (g_n - b_n) / delta + (if g_n < b_n { 6.0 } else { 0.0 });
-        } else if max == g_n {
-            h = (b_n - r_n) / delta + 2.0;
-        } else {
-            h = (r_n - g_n) / delta + 4.0;
-        }
-        h *= 60.0;
-    }
-
     // Output scales: H(0-360), S(0-100), L(0-100)
-    (h, s * 100.0, l * 100.0)
+    crate::color::hsl(r_n, g_n, b_n)
 }
 
 #[cfg(test)]
@@ -0,0 +1,569 @@
+//! Standard MIDI File playback on the Modulino Buzzer.
+//!
+//! [`MidiFile`] reads format 0 and 1 files in place (for example from
+//! `include_bytes!`) without allocating. A [`MidiPlayer`] reduces the selected
+//! track or channel to a single voice, always sounding the highest held note,
+//! and turns it into [`ToneStep`]s for a [`Sequencer`](crate::Sequencer).
+//! Tempo changes from any track are followed.
+
+use core::fmt;
+
+use crate::{Pitch, ToneSource, ToneStep};
+
+/// Maximum number of tracks a [`MidiFile`] may have.
+pub const MAX_TRACKS: usize = 16;
+
+/// Error returned when reading a MIDI file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MidiError {
+    /// The data does not start with an `MThd` header.
+    NotMidi,
+    /// The file is not format 0 or 1.
+    UnsupportedFormat,
+    /// The file uses SMPTE time division.
+    UnsupportedTiming,
+    /// The file has more than [`MAX_TRACKS`] tracks.
+    TooManyTracks,
+    /// A chunk or event runs past the end of the data.
+    Truncated,
+    /// A track contains an invalid event.
+    InvalidEvent,
+}
+
+impl fmt::Display for MidiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MidiError::NotMidi => write!(f, "not a Standard MIDI File"),
+            MidiError::UnsupportedFormat => write!(f, "only MIDI formats 0 and 1 are supported"),
+            MidiError::UnsupportedTiming => write!(f, "SMPTE time division is not supported"),
+            MidiError::TooManyTracks => write!(f, "too many tracks"),
+            MidiError::Truncated => write!(f, "truncated MIDI data"),
+            MidiError::InvalidEvent => write!(f, "invalid MIDI event"),
+        }
+    }
+}
+
+/// A Standard MIDI File (format 0 or 1) borrowed from a byte slice.
+///
+/// Every track is checked when the file is parsed, so playback cannot fail.
+#[derive(Debug, Clone, Copy)]
+pub struct MidiFile<'a> {
+    format: u16,
+    division: u16,
+    tracks: [&'a [u8]; MAX_TRACKS],
+    track_count: usize,
+}
+
+impl<'a> MidiFile<'a> {
+    /// Parse and validate a MIDI file.
+    pub fn parse(data: &'a [u8]) -> Result<Self, MidiError> {
+        let (id, header, mut rest) = chunk(data).ok_or(MidiError::NotMidi)?;
+        if id != b"MThd" || header.len() < 6 {
+            return Err(MidiError::NotMidi);
+        }
+        let format = u16::from_be_bytes([header[0], header[1]]);
+        let declared_tracks = u16::from_be_bytes([header[2], header[3]]) as usize;
+        let division = u16::from_be_bytes([header[4], header[5]]);
+        if format > 1 {
+            return Err(MidiError::UnsupportedFormat);
+        }
+        if division & 0x8000 != 0 || division == 0 {
+            return Err(MidiError::UnsupportedTiming);
+        }
+        if declared_tracks > MAX_TRACKS {
+            return Err(MidiError::TooManyTracks);
+        }
+
+        let mut file = Self {
+            format,
+            division,
+            tracks: [&[]; MAX_TRACKS],
+            track_count: 0,
+        };
+        while file.track_count < declared_tracks {
+            let (id, body, next) = chunk(rest).ok_or(MidiError::Truncated)?;
+            rest = next;
+            // Unknown chunk types must be skipped
+            if id == b"MTrk" {
+                validate_track(body)?;
+                file.tracks[file.track_count] = body;
+                file.track_count += 1;
+            }
+        }
+        Ok(file)
+    }
+
+    /// The file format (0 or 1).
+    pub fn format(&self) -> u16 {
+        self.format
+    }
+
+    /// Ticks per quarter note.
+    pub fn ticks_per_quarter(&self) -> u16 {
+        self.division
+    }
+
+    /// Number of tracks.
+    pub fn track_count(&self) -> usize {
+        self.track_count
+    }
+
+    /// Create a player for this file with the default settings.
+    pub fn player(&self) -> MidiPlayer<'a> {
+        MidiPlayer::new(*self)
+    }
+}
+
+/// Split a chunk off the front of `data`: `(id, body, rest)`.
+fn chunk(data: &[u8]) -> Option<(&[u8], &[u8], &[u8])> {
+    let header = data.get(..8)?;
+    let len = u32::from_be_bytes([header[4], header[5], header[6], header[7]]) as usize;
+    let body = data.get(8..8usize.checked_add(len)?)?;
+    Some((&header[..4], body, &data[8 + len..]))
+}
+
+fn validate_track(data: &[u8]) -> Result<(), MidiError> {
+    let mut cursor = TrackCursor::at_start(data);
+    cursor.read_delta()?;
+    while !cursor.is_done() {
+        cursor.next_event()?;
+    }
+    Ok(())
+}
+
+/// A decoded track event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Event {
+    NoteOn { channel: u8, key: u8 },
+    NoteOff { channel: u8, key: u8 },
+    Tempo(u32),
+    Other,
+}
+
+/// Reads the events of one track in order.
+#[derive(Debug, Clone, Copy)]
+struct TrackCursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+    /// Absolute tick of the next event.
+    tick: u32,
+    running_status: u8,
+}
+
+impl<'a> TrackCursor<'a> {
+    /// A cursor before the delta time of the first event.
+    fn at_start(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pos: 0,
+            tick: 0,
+            running_status: 0,
+        }
+    }
+
+    /// A cursor at the first event. A malformed track, which
+    /// `validate_track` rejects, is finished right away.
+    fn new(data: &'a [u8]) -> Self {
+        let mut cursor = Self::at_start(data);
+        if cursor.read_delta().is_err() {
+            cursor.finish();
+        }
+        cursor
+    }
+
+    fn is_done(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn finish(&mut self) {
+        self.pos = self.data.len();
+    }
+
+    fn byte(&mut self) -> Result<u8, MidiError> {
+        let byte = *self.data.get(self.pos).ok_or(MidiError::Truncated)?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), MidiError> {
+        if self.data.len() - self.pos < len {
+            return Err(MidiError::Truncated);
+        }
+        self.pos += len;
+        Ok(())
+    }
+
+    fn variable_length(&mut self) -> Result<u32, MidiError> {
+        let mut value = 0u32;
+        for _ in 0..4 {
+            let byte = self.byte()?;
+            value = (value << 7) | (byte & 0x7F) as u32;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(MidiError::InvalidEvent)
+    }
+
+    fn read_delta(&mut self) -> Result<(), MidiError> {
+        if self.is_done() {
+            return Ok(());
+        }
+        let delta = self.variable_length()?;
+        self.tick = self.tick.saturating_add(delta);
+        Ok(())
+    }
+
+    /// Decode the event at the cursor and read the delta time of the next one.
+    fn next_event(&mut self) -> Result<Event, MidiError> {
+        let result = self
+            .decode()
+            .and_then(|event| self.read_delta().map(|()| event));
+        if result.is_err() {
+            self.finish();
+        }
+        result
+    }
+
+    fn decode(&mut self) -> Result<Event, MidiError> {
+        let mut status = self.byte()?;
+        if status < 0x80 {
+            // Running status: reuse the previous status byte
+            if self.running_status == 0 {
+                return Err(MidiError::InvalidEvent);
+            }
+            self.pos -= 1;
+            status = self.running_status;
+        }
+
+        match status {
+            0xFF => {
+                // Meta and sysex events cancel running status
+                self.running_status = 0;
+                let kind = self.byte()?;
+                let len = self.variable_length()? as usize;
+                let start = self.pos;
+                self.skip(len)?;
+                match kind {
+                    0x2F => {
+                        self.finish();
+                        Ok(Event::Other)
+                    }
+                    0x51 if len == 3 => {
+                        let d = &self.data[start..start + 3];
+                        Ok(Event::Tempo(u32::from_be_bytes([0, d[0], d[1], d[2]])))
+                    }
+                    _ => Ok(Event::Other),
+                }
+            }
+            0xF0 | 0xF7 => {
+                self.running_status = 0;
+                let len = self.variable_length()? as usize;
+                self.skip(len)?;
+                Ok(Event::Other)
+            }
+            0xF1..=0xFE => Err(MidiError::InvalidEvent),
+            _ => {
+                self.running_status = status;
+                let channel = status & 0x0F;
+                match status & 0xF0 {
+                    0x80 => {
+                        let key = self.data_byte()?;
+                        self.data_byte()?;
+                        Ok(Event::NoteOff { channel, key })
+                    }
+                    0x90 => {
+                        let key = self.data_byte()?;
+                        let velocity = self.data_byte()?;
+                        if velocity == 0 {
+                            Ok(Event::NoteOff { channel, key })
+                        } else {
+                            Ok(Event::NoteOn { channel, key })
+                        }
+                    }
+                    0xC0 | 0xD0 => {
+                        self.data_byte()?;
+                        Ok(Event::Other)
+                    }
+                    _ => {
+                        self.data_byte()?;
+                        self.data_byte()?;
+                        Ok(Event::Other)
+                    }
+                }
+            }
+        }
+    }
+
+    fn data_byte(&mut self) -> Result<u8, MidiError> {
+        let byte = self.byte()?;
+        if byte & 0x80 != 0 {
+            return Err(MidiError::InvalidEvent);
+        }
+        Ok(byte)
+    }
+}
+
+/// What a [`MidiPlayer`] does with notes outside its playable range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RangePolicy {
+    /// Ignore the note.
+    #[default]
+    Drop,
+    /// Move the note by whole octaves until it is in range.
+    Fold,
+}
+
+/// The MIDI channel that carries drums in General MIDI (channel 10).
+const DRUM_CHANNEL: u8 = 9;
+
+/// Default tempo: 120 beats per minute.
+const DEFAULT_TEMPO_US: u32 = 500_000;
+
+/// Plays one voice of a [`MidiFile`] as a [`ToneSource`].
+///
+/// By default the notes of every track and channel are used except the
+/// General MIDI drum channel (channel 10); [`with_track`](Self::with_track)
+/// and [`with_channel`](Self::with_channel) narrow this down. When several
+/// notes are held, the highest one sounds.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use modulino::{Buzzer, MidiFile, RangePolicy, Sequencer};
+///
+/// let song = MidiFile::parse(include_bytes!("song.mid"))?;
+/// let player = song.player().with_channel(0).with_range_policy(RangePolicy::Fold);
+/// let mut music = Sequencer::new(player);
+///
+/// let mut buzzer = Buzzer::new(i2c)?;
+/// while music.tick(&mut buzzer, now_ms())? {
+///     // ... other work ...
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MidiPlayer<'a> {
+    file: MidiFile<'a>,
+    track: Option<usize>,
+    channel: Option<u8>,
+    policy: RangePolicy,
+    lowest: u8,
+    highest: u8,
+    gap_ms: u16,
+
+    cursors: [TrackCursor<'a>; MAX_TRACKS],
+    held: [u8; 128],
+    tempo_us: u32,
+    tick: u32,
+    time_us: u64,
+    sounding: Option<u8>,
+    segment_start_us: u64,
+    /// Part of a step too long for one `ToneStep`: `(frequency, ms left)`.
+    remainder: Option<(u16, u64)>,
+    finished: bool,
+}
+
+impl<'a> MidiPlayer<'a> {
+    /// Lowest MIDI note the buzzer can play (F#3, 185 Hz).
+    pub const LOWEST_NOTE: u8 = 54;
+
+    /// Highest MIDI note played by default (D#8, 4978 Hz).
+    pub const HIGHEST_NOTE: u8 = 111;
+
+    /// Create a player for `file`.
+    pub fn new(file: MidiFile<'a>) -> Self {
+        let mut player = Self {
+            file,
+            track: None,
+            channel: None,
+            policy: RangePolicy::Drop,
+            lowest: Self::LOWEST_NOTE,
+            highest: Self::HIGHEST_NOTE,
+            gap_ms: 0,
+            cursors: [TrackCursor::new(&[]); MAX_TRACKS],
+            held: [0; 128],
+            tempo_us: DEFAULT_TEMPO_US,
+            tick: 0,
+            time_us: 0,
+            sounding: None,
+            segment_start_us: 0,
+            remainder: None,
+            finished: false,
+        };
+        player.rewind();
+        player
+    }
+
+    /// Only play notes from track `track`.
+    pub fn with_track(mut self, track: usize) -> Self {
+        self.track = Some(track);
+        self
+    }
+
+    /// Only play notes on MIDI channel `channel` (0-15).
+    pub fn with_channel(mut self, channel: u8) -> Self {
+        self.channel = Some(channel & 0x0F);
+        self
+    }
+
+    /// Set what happens to notes outside the playable range.
+    pub fn with_range_policy(mut self, policy: RangePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Set the playable range of MIDI notes.
+    ///
+    /// `lowest` is raised to [`LOWEST_NOTE`](Self::LOWEST_NOTE), and the range
+    /// is widened to at least an octave so every note can be folded into it.
+    pub fn with_range(mut self, lowest: u8, highest: u8) -> Self {
+        self.lowest = lowest.clamp(Self::LOWEST_NOTE, 127 - 11);
+        self.highest = highest.clamp(self.lowest + 11, 127);
+        self
+    }
+
+    /// Set the silence cut from the end of every note, in milliseconds.
+    pub fn with_gap(mut self, gap_ms: u16) -> Self {
+        self.gap_ms = gap_ms;
+        self
+    }
+
+    /// Map a note into the playable range according to the policy.
+    fn playable(&self, key: u8) -> Option<u8> {
+        if (self.lowest..=self.highest).contains(&key) {
+            return Some(key);
+        }
+        match self.policy {
+            RangePolicy::Drop => None,
+            RangePolicy::Fold => {
+                let mut key = key;
+                while key < self.lowest {
+                    key += 12;
+                }
+                while key > self.highest {
+                    key -= 12;
+                }
+                Some(key)
+            }
+        }
+    }
+
+    fn wants(&self, track: usize, channel: u8) -> bool {
+        let track_ok = self.track.map_or(true, |t| t == track);
+        let channel_ok = match self.channel {
+            Some(c) => c == channel,
+            None => channel != DRUM_CHANNEL,
+        };
+        track_ok && channel_ok
+    }
+
+    fn highest_held(&self) -> Option<u8> {
+        self.held
+            .iter()
+            .rposition(|&count| count > 0)
+            .map(|k| k as u8)
+    }
+
+    /// Process every event at the next tick. Returns `false` at the end.
+    fn advance(&mut self) -> bool {
+        let count = self.file.track_count;
+        let Some(tick) = self.cursors[..count]
+            .iter()
+            .filter(|c| !c.is_done())
+            .map(|c| c.tick)
+            .min()
+        else {
+            return false;
+        };
+
+        let ticks = (tick - self.tick) as u64;
+        self.time_us += ticks * self.tempo_us as u64 / self.file.division as u64;
+        self.tick = tick;
+
+        for track in 0..count {
+            while !self.cursors[track].is_done() && self.cursors[track].tick == tick {
+                match self.cursors[track].next_event() {
+                    Ok(Event::Tempo(tempo)) if tempo > 0 => self.tempo_us = tempo,
+                    Ok(Event::NoteOn { channel, key }) if self.wants(track, channel) => {
+                        if let Some(key) = self.playable(key) {
+                            let slot = &mut self.held[key as usize];
+                            *slot = slot.saturating_add(1);
+                        }
+                    }
+                    Ok(Event::NoteOff { channel, key }) if self.wants(track, channel) => {
+                        if let Some(key) = self.playable(key) {
+                            let slot = &mut self.held[key as usize];
+                            *slot = slot.saturating_sub(1);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        true
+    }
+
+    /// Finish the current segment at the current time and start a new one.
+    fn close_segment(&mut self, next: Option<u8>) -> Option<ToneStep> {
+        let frequency = self
+            .sounding
+            .and_then(Pitch::from_midi)
+            .map_or(0, |pitch| pitch.frequency());
+        let start_ms = self.segment_start_us / 1000;
+        let end_ms = self.time_us / 1000;
+        self.sounding = next;
+        self.segment_start_us = self.time_us;
+        self.take_step(frequency, end_ms - start_ms)
+    }
+
+    fn take_step(&mut self, frequency: u16, ms: u64) -> Option<ToneStep> {
+        if ms == 0 {
+            return None;
+        }
+        let chunk = ms.min(u16::MAX as u64);
+        self.remainder = (ms > chunk).then_some((frequency, ms - chunk));
+        let step = ToneStep::new(frequency, chunk as u16);
+        Some(if self.remainder.is_some() || frequency == 0 {
+            step
+        } else {
+            step.with_gap(self.gap_ms)
+        })
+    }
+}
+
+impl ToneSource for MidiPlayer<'_> {
+    fn next_step(&mut self) -> Option<ToneStep> {
+        if let Some((frequency, ms)) = self.remainder.take() {
+            return self.take_step(frequency, ms);
+        }
+        while !self.finished {
+            if !self.advance() {
+                self.finished = true;
+                return self.close_segment(None);
+            }
+            let next = self.highest_held();
+            if next != self.sounding {
+                if let Some(step) = self.close_segment(next) {
+                    return Some(step);
+                }
+            }
+        }
+        None
+    }
+
+    fn rewind(&mut self) {
+        for (cursor, track) in self.cursors.iter_mut().zip(self.file.tracks) {
+            *cursor = TrackCursor::new(track);
+        }
+        self.held = [0; 128];
+        self.tempo_us = DEFAULT_TEMPO_US;
+        self.tick = 0;
+        self.time_us = 0;
+        self.sounding = None;
+        self.segment_start_us = 0;
+        self.remainder = None;
+        self.finished = false;
+    }
+}
@@ -0,0 +1,84 @@
+//! Common interface shared by all Modulino drivers.
+
+use crate::{Error, ModulinoKind, Result};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::I2c;
+
+/// Behaviour shared by every Modulino driver.
+///
+/// The inherent constructors of the individual drivers differ (some verify the
+/// device and return a `Result`, others return `Self` and require a separate
+/// `init()`). This trait gives them a uniform shape so that generic code can
+/// probe, construct, initialize and release any module.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use modulino::{Modulino, Buttons, Knob};
+///
+/// fn connect<I2C, M>(mut i2c: I2C, delay: &mut impl DelayNs) -> modulino::Result<M, I2C::Error>
+/// where
+///     I2C: I2c,
+///     M: Modulino<I2C>,
+/// {
+///     let address = M::probe(&mut i2c)?;
+///     let mut module = M::new_with_address(i2c, address)?;
+///     module.init(delay)?;
+///     Ok(module)
+/// }
+///
+/// let knob: Knob<_> = connect(i2c, &mut delay)?;
+/// ```
+pub trait Modulino<I2C: I2c>: Sized {
+    /// The module type.
+    const KIND: ModulinoKind;
+
+    /// Factory-default 7-bit addresses, in the order [`probe`](Self::probe) tries them.
+    const DEFAULT_ADDRESSES: &'static [u8];
+
+    /// Pinstrap bytes reported by the module firmware.
+    ///
+    /// Empty for fixed-function sensors, which have no pinstrap.
+    const PINSTRAP: &'static [u8];
+
+    /// Create a driver for a module at the given address.
+    ///
+    /// This performs the same checks as the driver's inherent constructor.
+    fn new_with_address(i2c: I2C, address: u8) -> Result<Self, I2C::Error>;
+
+    /// Bring the module into its default operating state.
+    ///
+    /// Drivers that are fully set up by their constructor do nothing here.
+    fn init<D: DelayNs>(&mut self, _delay: &mut D) -> Result<(), I2C::Error> {
+        Ok(())
+    }
+
+    /// Get the I2C address.
+    fn address(&self) -> u8;
+
+    /// Release the I2C bus.
+    fn release(self) -> I2C;
+
+    /// Probe the default addresses and return the first one that ACKs.
+    ///
+    /// > [!WARNING]
+    /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
+    fn probe(i2c: &mut I2C) -> Result<u8, I2C::Error> {
+        probe_addresses(i2c, Self::DEFAULT_ADDRESSES)
+    }
+}
+
+/// Return the first address that ACKs a zero-length write.
+///
+/// If none do, the first address is written once more so the caller gets the
+/// bus error for it.
+pub(crate) fn probe_addresses<I2C: I2c>(i2c: &mut I2C, addresses: &[u8]) -> Result<u8, I2C::Error> {
+    for &addr in addresses {
+        if i2c.write(addr, &[]).is_ok() {
+            return Ok(addr);
+        }
+    }
+    i2c.write(addresses[0], &[])
+        .map(|_| addresses[0])
+        .map_err(Error::I2c)
+}
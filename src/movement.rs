@@ -6,19 +6,36 @@
 //! Note: This driver provides a simplified interface. For full LSM6DSOX
 //! functionality, consider using a dedicated LSM6DSOX driver crate.
 
-use crate::{addresses, Error, I2cDevice, Result};
+use crate::{addresses, Error, I2cDevice, Modulino, ModulinoKind, Result};
 use embedded_hal::i2c::I2c;
 
 // LSM6DSOX register addresses
-const LSM6DSOX_CTRL1_XL: u8 = 0x10;
-const LSM6DSOX_CTRL2_G: u8 = 0x11;
-const LSM6DSOX_CTRL3_C: u8 = 0x12;
-const LSM6DSOX_STATUS_REG: u8 = 0x1E;
-const LSM6DSOX_OUTX_L_G: u8 = 0x22;
-const LSM6DSOX_OUTX_L_A: u8 = 0x28;
-const LSM6DSOX_WHO_AM_I: u8 = 0x0F;
-
-const LSM6DSOX_WHO_AM_I_VALUE: u8 = 0x6C;
+pub(crate) const LSM6DSOX_CTRL1_XL: u8 = 0x10;
+pub(crate) const LSM6DSOX_CTRL2_G: u8 = 0x11;
+pub(crate) const LSM6DSOX_CTRL3_C: u8 = 0x12;
+pub(crate) const LSM6DSOX_STATUS_REG: u8 = 0x1E;
+pub(crate) const LSM6DSOX_OUTX_L_G: u8 = 0x22;
+pub(crate) const LSM6DSOX_OUTX_L_A: u8 = 0x28;
+pub(crate) const LSM6DSOX_WHO_AM_I: u8 = 0x0F;
+
+pub(crate) const LSM6DSOX_WHO_AM_I_VALUE: u8 = 0x6C;
+
+/// Accelerometer sensitivity in mg/LSB at ±2g.
+pub(crate) const ACCEL_SENSITIVITY: f32 = 0.061;
+/// Gyroscope sensitivity in mdps/LSB at ±250dps.
+pub(crate) const GYRO_SENSITIVITY: f32 = 8.75;
+
+/// Register writes performed by `init()`, in order.
+pub(crate) const INIT_SEQUENCE: [(u8, u8); 4] = [
+    // Software reset
+    (LSM6DSOX_CTRL3_C, 0x01),
+    // Configure accelerometer: 104 Hz, ±2g
+    (LSM6DSOX_CTRL1_XL, 0x40),
+    // Configure gyroscope: 104 Hz, ±250 dps
+    (LSM6DSOX_CTRL2_G, 0x40),
+    // Enable BDU (Block Data Update)
+    (LSM6DSOX_CTRL3_C, 0x44),
+];
 
 /// 3-axis measurement values.
 #[derive(Debug, Clone, Copy, Default)]
@@ -95,15 +112,7 @@ where
     /// > [!WARNING]
     /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
     pub fn discover(i2c: &mut I2C) -> Result<u8, E> {
-        let addresses = addresses::MOVEMENT;
-        for &addr in &addresses {
-            if i2c.write(addr, &[]).is_ok() {
-                return Ok(addr);
-            }
-        }
-        i2c.write(addresses[0], &[])
-            .map(|_| addresses[0])
-            .map_err(Error::I2c)
+        <Self as Modulino<I2C>>::probe(i2c)
     }
 
     /// Create a new Movement instance with a custom address.
@@ -112,8 +121,8 @@ where
     pub fn new_with_address(i2c: I2C, address: u8) -> Result<Self, E> {
         let mut movement = Self {
             device: I2cDevice::new(i2c, address),
-            accel_sensitivity: 0.061, // mg/LSB at ±2g
-            gyro_sensitivity: 8.75,   // mdps/LSB at ±250dps
+            accel_sensitivity: ACCEL_SENSITIVITY,
+            gyro_sensitivity: GYRO_SENSITIVITY,
         };
 
         // Verify device identity
@@ -135,21 +144,12 @@ where
 
     /// Initialize the sensor with default settings.
     fn init(&mut self) -> Result<(), E> {
-        // Software reset
-        self.device.write_reg(LSM6DSOX_CTRL3_C, 0x01)?;
-
-        // Wait for reset (in a real implementation, add delay here)
-
-        // Configure accelerometer: 104 Hz, ±2g
-        self.device.write_reg(LSM6DSOX_CTRL1_XL, 0x40)?;
-        self.accel_sensitivity = 0.061; // mg/LSB at ±2g
-
-        // Configure gyroscope: 104 Hz, ±250 dps
-        self.device.write_reg(LSM6DSOX_CTRL2_G, 0x40)?;
-        self.gyro_sensitivity = 8.75; // mdps/LSB at ±250dps
-
-        // Enable BDU (Block Data Update)
-        self.device.write_reg(LSM6DSOX_CTRL3_C, 0x44)?;
+        // In a real implementation, a delay should follow the software reset.
+        for (reg, value) in INIT_SEQUENCE {
+            self.device.write_reg(reg, value)?;
+        }
+        self.accel_sensitivity = ACCEL_SENSITIVITY;
+        self.gyro_sensitivity = GYRO_SENSITIVITY;
 
         Ok(())
     }
@@ -161,17 +161,8 @@ where
         let mut buf = [0u8; 6];
         self.device.read_regs(LSM6DSOX_OUTX_L_A, &mut buf)?;
 
-        let x_raw = i16::from_le_bytes([buf[0], buf[1]]);
-        let y_raw = i16::from_le_bytes([buf[2], buf[3]]);
-        let z_raw = i16::from_le_bytes([buf[4], buf[5]]);
-
         // Convert to g
-        let scale = self.accel_sensitivity / 1000.0;
-        Ok(MovementValues {
-            x: x_raw as f32 * scale,
-            y: y_raw as f32 * scale,
-            z: z_raw as f32 * scale,
-        })
+        Ok(decode_axes(&buf, self.accel_sensitivity))
     }
 
     /// Get the magnitude of acceleration.
@@ -188,17 +179,8 @@ where
         let mut buf = [0u8; 6];
         self.device.read_regs(LSM6DSOX_OUTX_L_G, &mut buf)?;
 
-        let x_raw = i16::from_le_bytes([buf[0], buf[1]]);
-        let y_raw = i16::from_le_bytes([buf[2], buf[3]]);
-        let z_raw = i16::from_le_bytes([buf[4], buf[5]]);
-
         // Convert to dps
-        let scale = self.gyro_sensitivity / 1000.0;
-        Ok(MovementValues {
-            x: x_raw as f32 * scale,
-            y: y_raw as f32 * scale,
-            z: z_raw as f32 * scale,
-        })
+        Ok(decode_axes(&buf, self.gyro_sensitivity))
     }
 
     /// Alias for `angular_velocity()`.
@@ -209,8 +191,7 @@ where
     /// Check if new data is available.
     pub fn data_ready(&mut self) -> Result<bool, E> {
         let status = self.device.read_reg(LSM6DSOX_STATUS_REG)?;
-        // Check XLDA (bit 0) or GDA (bit 1)
-        Ok((status & 0x03) != 0)
+        Ok(status_data_ready(status))
     }
 
     /// Release the I2C bus.
@@ -218,3 +199,40 @@ where
         self.device.release()
     }
 }
+
+impl<I2C: I2c> Modulino<I2C> for Movement<I2C> {
+    const KIND: ModulinoKind = ModulinoKind::Movement;
+    const DEFAULT_ADDRESSES: &'static [u8] = &addresses::MOVEMENT;
+    const PINSTRAP: &'static [u8] = &[];
+
+    fn new_with_address(i2c: I2C, address: u8) -> Result<Self, I2C::Error> {
+        Self::new_with_address(i2c, address)
+    }
+
+    fn address(&self) -> u8 {
+        self.device.address
+    }
+
+    fn release(self) -> I2C {
+        self.device.release()
+    }
+}
+
+/// Decode three little-endian axes, scaling by a sensitivity in milli-units per LSB.
+pub(crate) fn decode_axes(buf: &[u8; 6], sensitivity: f32) -> MovementValues {
+    let x_raw = i16::from_le_bytes([buf[0], buf[1]]);
+    let y_raw = i16::from_le_bytes([buf[2], buf[3]]);
+    let z_raw = i16::from_le_bytes([buf[4], buf[5]]);
+
+    let scale = sensitivity / 1000.0;
+    MovementValues {
+        x: x_raw as f32 * scale,
+        y: y_raw as f32 * scale,
+        z: z_raw as f32 * scale,
+    }
+}
+
+/// Check XLDA (bit 0) or GDA (bit 1) in the status register.
+pub(crate) fn status_data_ready(status: u8) -> bool {
+    (status & 0x03) != 0
+}
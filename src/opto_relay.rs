@@ -6,7 +6,7 @@
 //! > **EXPERIMENTAL**: This driver is a work-in-progress and has only been verified via
 //! > unit tests using I2C mocks. It has NOT yet been tested on physical Modulino hardware.
 
-use crate::{addresses, Error, I2cDevice, Result};
+use crate::{addresses, pinstrap, I2cDevice, Modulino, ModulinoKind, Result};
 use embedded_hal::i2c::I2c;
 
 /// Driver for the Modulino Opto Relay module.
@@ -31,15 +31,7 @@ where
     /// > [!WARNING]
     /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
     pub fn discover(i2c: &mut I2C) -> Result<u8, E> {
-        let addresses = [addresses::OPTO_RELAY];
-        for &addr in &addresses {
-            if i2c.write(addr, &[]).is_ok() {
-                return Ok(addr);
-            }
-        }
-        i2c.write(addresses[0], &[])
-            .map(|_| addresses[0])
-            .map_err(Error::I2c)
+        <Self as Modulino<I2C>>::probe(i2c)
     }
 
     /// Create a new OptoRelay instance with a custom address.
@@ -114,3 +106,21 @@ where
         self.device.release()
     }
 }
+
+impl<I2C: I2c> Modulino<I2C> for OptoRelay<I2C> {
+    const KIND: ModulinoKind = ModulinoKind::OptoRelay;
+    const DEFAULT_ADDRESSES: &'static [u8] = &[addresses::OPTO_RELAY];
+    const PINSTRAP: &'static [u8] = &[pinstrap::OPTO_RELAY];
+
+    fn new_with_address(i2c: I2C, address: u8) -> Result<Self, I2C::Error> {
+        Self::new_with_address(i2c, address)
+    }
+
+    fn address(&self) -> u8 {
+        self.device.address
+    }
+
+    fn release(self) -> I2C {
+        self.device.release()
+    }
+}
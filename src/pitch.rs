@@ -0,0 +1,232 @@
+//! Musical pitch with MIDI note numbers, transposition and detuning.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::buzzer::MIN_FREQUENCY;
+use crate::Note;
+
+/// Note names used for display, indexed by pitch class.
+const NAMES: [&str; 12] = [
+    "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+];
+
+/// A pitch on the equal-tempered scale (A4 = 440 Hz), with optional detuning.
+///
+/// Pitches are identified by their MIDI note number (60 is C4, 69 is A4) and
+/// can be detuned by up to a semitone in cents. Unlike [`Note`], a pitch covers
+/// the whole MIDI range; [`is_playable`](Self::is_playable) tells whether the
+/// buzzer can play it.
+///
+/// # Example
+///
+/// ```rust
+/// use modulino::{Note, Pitch};
+///
+/// let a4: Pitch = "A4".parse().unwrap();
+/// assert_eq!(a4.midi(), 69);
+/// assert_eq!(a4.frequency(), 440);
+///
+/// let db5: Pitch = "Db5".parse().unwrap();
+/// assert_eq!(db5.to_note(), Some(Note::CS5));
+/// assert_eq!(db5.transpose(-12).unwrap().to_string(), "C#4");
+///
+/// assert_eq!(a4.with_cents(50).frequency_millihertz(), 452_893);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Pitch {
+    midi: u8,
+    cents: i8,
+}
+
+impl Pitch {
+    /// The tuning reference, A4.
+    pub const A4: Pitch = Pitch { midi: 69, cents: 0 };
+
+    /// Highest MIDI note number.
+    pub const MAX_MIDI: u8 = 127;
+
+    /// The pitch with MIDI note number `midi` (0-127).
+    pub const fn from_midi(midi: u8) -> Option<Self> {
+        if midi > Self::MAX_MIDI {
+            return None;
+        }
+        Some(Self { midi, cents: 0 })
+    }
+
+    /// The pitch of `pitch_class` (0 for C to 11 for B) in `octave` (-1 to 9).
+    pub const fn from_octave(pitch_class: u8, octave: i8) -> Option<Self> {
+        if pitch_class > 11 || octave < -1 {
+            return None;
+        }
+        let midi = (octave as i16 + 1) * 12 + pitch_class as i16;
+        if midi > Self::MAX_MIDI as i16 {
+            return None;
+        }
+        Self::from_midi(midi as u8)
+    }
+
+    /// The nearest pitch to `hz`, with the remaining difference in cents.
+    pub fn from_frequency(hz: f32) -> Option<Self> {
+        if hz.is_nan() || hz <= 0.0 {
+            return None;
+        }
+        let semitones = 69.0 + 12.0 * libm::log2f(hz / 440.0);
+        let midi = libm::roundf(semitones);
+        if !(0.0..=Self::MAX_MIDI as f32).contains(&midi) {
+            return None;
+        }
+        let cents = libm::roundf((semitones - midi) * 100.0) as i8;
+        Some(Self {
+            midi: midi as u8,
+            cents,
+        })
+    }
+
+    /// The MIDI note number.
+    pub const fn midi(&self) -> u8 {
+        self.midi
+    }
+
+    /// The detuning in cents (-100 to 100).
+    pub const fn cents(&self) -> i8 {
+        self.cents
+    }
+
+    /// The pitch class: 0 for C to 11 for B.
+    pub const fn pitch_class(&self) -> u8 {
+        self.midi % 12
+    }
+
+    /// The octave number, where octave 4 starts at middle C.
+    pub const fn octave(&self) -> i8 {
+        (self.midi / 12) as i8 - 1
+    }
+
+    /// The note name with a sharp where needed, e.g. `"C#"`.
+    pub fn name(&self) -> &'static str {
+        NAMES[self.pitch_class() as usize]
+    }
+
+    /// Detune by `cents`, clamped to a semitone either way.
+    pub const fn with_cents(mut self, cents: i8) -> Self {
+        self.cents = if cents > 100 {
+            100
+        } else if cents < -100 {
+            -100
+        } else {
+            cents
+        };
+        self
+    }
+
+    /// Move by `semitones`, or `None` if that leaves the MIDI range.
+    pub const fn transpose(self, semitones: i8) -> Option<Self> {
+        let midi = self.midi as i16 + semitones as i16;
+        if midi < 0 || midi > Self::MAX_MIDI as i16 {
+            return None;
+        }
+        Some(Self {
+            midi: midi as u8,
+            cents: self.cents,
+        })
+    }
+
+    /// Move by whole octaves, or `None` if that leaves the MIDI range.
+    pub const fn transpose_octaves(self, octaves: i8) -> Option<Self> {
+        if octaves > 10 || octaves < -10 {
+            return None;
+        }
+        self.transpose(octaves * 12)
+    }
+
+    /// Frequency in thousandths of a hertz.
+    pub fn frequency_millihertz(&self) -> u32 {
+        let semitones = self.midi as f64 - 69.0 + self.cents as f64 / 100.0;
+        let hz = 440.0 * libm::exp2(semitones / 12.0);
+        libm::round(hz * 1000.0) as u32
+    }
+
+    /// Frequency rounded to the nearest hertz.
+    pub fn frequency(&self) -> u16 {
+        let hz = (self.frequency_millihertz() + 500) / 1000;
+        hz.min(u16::MAX as u32) as u16
+    }
+
+    /// Check if the buzzer can play this pitch.
+    pub fn is_playable(&self) -> bool {
+        self.frequency() >= MIN_FREQUENCY
+    }
+
+    /// Frequency in hertz, raised to the buzzer's minimum if it is lower.
+    pub fn clamped_frequency(&self) -> u16 {
+        self.frequency().max(MIN_FREQUENCY)
+    }
+
+    /// The matching [`Note`], if the pitch is in tune and the enum has one.
+    pub fn to_note(&self) -> Option<Note> {
+        if self.cents != 0 {
+            return None;
+        }
+        Note::from_midi(self.midi)
+    }
+}
+
+impl fmt::Display for Pitch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.name(), self.octave())?;
+        if self.cents != 0 {
+            write!(f, "{:+}c", self.cents)?;
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when parsing a [`Pitch`] name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ParsePitchError;
+
+impl fmt::Display for ParsePitchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid note name")
+    }
+}
+
+impl FromStr for Pitch {
+    type Err = ParsePitchError;
+
+    /// Parse a name like `"A4"`, `"C#5"`, `"Db5"`, `"CS5"` or `"C-1"`.
+    ///
+    /// Sharps are written `#` or `S` (as in [`Note`]) and flats `b`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.trim().chars();
+        let pitch_class: u8 = match chars.next().map(|c| c.to_ascii_uppercase()) {
+            Some('C') => 0,
+            Some('D') => 2,
+            Some('E') => 4,
+            Some('F') => 5,
+            Some('G') => 7,
+            Some('A') => 9,
+            Some('B') => 11,
+            _ => return Err(ParsePitchError),
+        };
+        let rest = chars.as_str();
+        let (accidental, octave) = match rest.as_bytes().first() {
+            Some(b'#') | Some(b's') | Some(b'S') => (1, &rest[1..]),
+            Some(b'b') => (-1, &rest[1..]),
+            _ => (0, rest),
+        };
+        let octave: i8 = octave.parse().map_err(|_| ParsePitchError)?;
+        let base = Self::from_octave(pitch_class, octave).ok_or(ParsePitchError)?;
+        // Cb and B# cross into the neighbouring octave
+        base.transpose(accidental).ok_or(ParsePitchError)
+    }
+}
+
+impl From<Pitch> for u16 {
+    fn from(pitch: Pitch) -> Self {
+        pitch.frequency()
+    }
+}
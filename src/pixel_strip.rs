@@ -0,0 +1,309 @@
+//! A logical LED strip made of several Modulino Pixels.
+
+use crate::pixels::NUM_LEDS;
+use crate::power::{ua_to_ma, PowerScale};
+use crate::{Color, ColorCorrection, Error, Pixels, Result};
+use embedded_hal::i2c::I2c;
+
+/// `M` Pixels modules used as one strip of `M * 8` LEDs.
+///
+/// LED 0 is the first LED of the first module; each module continues where
+/// the previous one ended. A module mounted the other way round can be
+/// reversed so the strip still runs in one direction. Setters only update the
+/// modules' frame buffers; [`show`](Self::show) writes every module.
+///
+/// All modules share one I2C type, so modules at different addresses on the
+/// same bus need a shared bus handle (for example from `embedded-hal-bus`),
+/// and modules behind a hub can use [`HubChannel`](crate::HubChannel)s.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use modulino::{Animation, AnimationKind, Color, PixelStrip, Pixels};
+///
+/// let strip = PixelStrip::new([
+///     Pixels::new_with_address(bus.acquire_i2c(), 0x36)?,
+///     Pixels::new_with_address(bus.acquire_i2c(), 0x37)?,
+/// ]);
+/// let mut strip = strip.with_reversed(1, true);
+///
+/// strip.set_range_color(4, 11, Color::GREEN, 30);
+/// strip.show()?;
+///
+/// let mut comet = Animation::<16>::for_strip(AnimationKind::Comet);
+/// loop {
+///     comet.tick_strip(&mut strip, now_ms())?;
+/// }
+/// ```
+pub struct PixelStrip<I2C, const M: usize> {
+    segments: [Pixels<I2C>; M],
+    reversed: [bool; M],
+    power_budget_ma: Option<u16>,
+}
+
+impl<I2C, E, const M: usize> PixelStrip<I2C, M>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Number of LEDs on each module.
+    pub const LEDS_PER_SEGMENT: usize = NUM_LEDS;
+
+    /// Create a strip from modules in order along the strip.
+    pub fn new(segments: [Pixels<I2C>; M]) -> Self {
+        Self {
+            segments,
+            reversed: [false; M],
+            power_budget_ma: None,
+        }
+    }
+
+    /// Reverse the LED order of module `segment`.
+    ///
+    /// Out-of-range segments are ignored.
+    pub fn with_reversed(mut self, segment: usize, reversed: bool) -> Self {
+        self.set_reversed(segment, reversed);
+        self
+    }
+
+    /// Change whether module `segment` is reversed.
+    ///
+    /// Out-of-range segments are ignored.
+    pub fn set_reversed(&mut self, segment: usize, reversed: bool) -> &mut Self {
+        if let Some(flag) = self.reversed.get_mut(segment) {
+            *flag = reversed;
+        }
+        self
+    }
+
+    /// Check if module `segment` is reversed.
+    pub fn is_reversed(&self, segment: usize) -> bool {
+        self.reversed.get(segment).copied().unwrap_or(false)
+    }
+
+    /// Total number of LEDs.
+    pub const fn len(&self) -> usize {
+        M * NUM_LEDS
+    }
+
+    /// Check if the strip has no modules.
+    pub const fn is_empty(&self) -> bool {
+        M == 0
+    }
+
+    /// Get module `segment`.
+    pub fn segment(&self, segment: usize) -> Option<&Pixels<I2C>> {
+        self.segments.get(segment)
+    }
+
+    /// Get module `segment` mutably.
+    pub fn segment_mut(&mut self, segment: usize) -> Option<&mut Pixels<I2C>> {
+        self.segments.get_mut(segment)
+    }
+
+    /// The module number and local LED index for strip LED `index`.
+    fn map(&self, index: usize) -> Option<(usize, usize)> {
+        let segment = index / NUM_LEDS;
+        let local = index % NUM_LEDS;
+        if *self.reversed.get(segment)? {
+            Some((segment, NUM_LEDS - 1 - local))
+        } else {
+            Some((segment, local))
+        }
+    }
+
+    /// The module and local LED index for strip LED `index`.
+    fn position(&self, index: usize) -> Option<(&Pixels<I2C>, usize)> {
+        let (segment, local) = self.map(index)?;
+        Some((&self.segments[segment], local))
+    }
+
+    /// The module, mutably, and local LED index for strip LED `index`.
+    fn locate(&mut self, index: usize) -> Option<(&mut Pixels<I2C>, usize)> {
+        let (segment, local) = self.map(index)?;
+        Some((&mut self.segments[segment], local))
+    }
+
+    /// Get the color of LED `index`, or `None` if out of range.
+    pub fn get_color(&self, index: usize) -> Option<Color> {
+        let (pixels, local) = self.position(index)?;
+        pixels.get_color(local)
+    }
+
+    /// Get the brightness (0-100) of LED `index`, or `None` if out of range.
+    pub fn get_brightness(&self, index: usize) -> Option<u8> {
+        let (pixels, local) = self.position(index)?;
+        pixels.get_brightness(local)
+    }
+
+    /// Set the color of LED `index` (0 to `len() - 1`).
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - LED index along the strip
+    /// * `color` - The color to set
+    /// * `brightness` - Brightness level (0-100)
+    pub fn set_color(
+        &mut self,
+        index: usize,
+        color: Color,
+        brightness: u8,
+    ) -> Result<&mut Self, E> {
+        let (pixels, local) = self.locate(index).ok_or(Error::OutOfRange)?;
+        pixels.set_color(local, color, brightness)?;
+        Ok(self)
+    }
+
+    /// Set the color of LED `index` using RGB values.
+    pub fn set_rgb(
+        &mut self,
+        index: usize,
+        r: u8,
+        g: u8,
+        b: u8,
+        brightness: u8,
+    ) -> Result<&mut Self, E> {
+        self.set_color(index, Color::new(r, g, b), brightness)
+    }
+
+    /// Set the brightness of LED `index` without changing its color.
+    pub fn set_brightness(&mut self, index: usize, brightness: u8) -> Result<&mut Self, E> {
+        let (pixels, local) = self.locate(index).ok_or(Error::OutOfRange)?;
+        pixels.set_brightness(local, brightness)?;
+        Ok(self)
+    }
+
+    /// Set the color of a range of LEDs (both ends inclusive, clamped to the
+    /// last LED).
+    pub fn set_range_color(
+        &mut self,
+        from: usize,
+        to: usize,
+        color: Color,
+        brightness: u8,
+    ) -> &mut Self {
+        let end = to.min(self.len().saturating_sub(1));
+        for index in from..=end {
+            if let Some((pixels, local)) = self.locate(index) {
+                let _ = pixels.set_color(local, color, brightness);
+            }
+        }
+        self
+    }
+
+    /// Set the color of all LEDs.
+    pub fn set_all_color(&mut self, color: Color, brightness: u8) -> &mut Self {
+        for pixels in self.segments.iter_mut() {
+            pixels.set_all_color(color, brightness);
+        }
+        self
+    }
+
+    /// Set the brightness of all LEDs without changing their colors.
+    pub fn set_all_brightness(&mut self, brightness: u8) -> &mut Self {
+        for pixels in self.segments.iter_mut() {
+            pixels.set_all_brightness(brightness);
+        }
+        self
+    }
+
+    /// Set LEDs from 0 onwards to `colors`, all at `brightness`.
+    ///
+    /// Extra colors beyond the end of the strip are ignored.
+    pub fn set_frame(&mut self, colors: &[Color], brightness: u8) -> &mut Self {
+        for (index, color) in colors.iter().enumerate().take(self.len()) {
+            if let Some((pixels, local)) = self.locate(index) {
+                let _ = pixels.set_color(local, *color, brightness);
+            }
+        }
+        self
+    }
+
+    /// Clear (turn off) LED `index`.
+    pub fn clear(&mut self, index: usize) -> Result<&mut Self, E> {
+        self.set_color(index, Color::BLACK, 0)
+    }
+
+    /// Clear a range of LEDs.
+    pub fn clear_range(&mut self, from: usize, to: usize) -> &mut Self {
+        self.set_range_color(from, to, Color::BLACK, 0)
+    }
+
+    /// Clear all LEDs.
+    pub fn clear_all(&mut self) -> &mut Self {
+        self.set_all_color(Color::BLACK, 0)
+    }
+
+    /// Set the color correction on every module.
+    pub fn set_correction(&mut self, correction: Option<ColorCorrection>) -> &mut Self {
+        for pixels in self.segments.iter_mut() {
+            pixels.set_correction(correction.clone());
+        }
+        self
+    }
+
+    /// Limit the estimated current of the whole strip to `budget_ma`
+    /// milliamps, or `None` for no limit.
+    ///
+    /// When a frame would draw more, every LED on every module is scaled down
+    /// by the same factor. A strip budget replaces the modules' own budgets.
+    pub fn set_power_budget(&mut self, budget_ma: Option<u16>) -> &mut Self {
+        self.power_budget_ma = budget_ma;
+        self
+    }
+
+    /// Get the power budget of the whole strip in milliamps.
+    pub fn power_budget(&self) -> Option<u16> {
+        self.power_budget_ma
+    }
+
+    /// Estimated current drawn by all modules, in milliamps, before any
+    /// power budget is applied.
+    pub fn estimated_current_ma(&self) -> u32 {
+        ua_to_ma(self.segments.iter().map(|pixels| pixels.current_ua()).sum())
+    }
+
+    /// Write the modules, scaled to the strip's budget if it has one.
+    fn write(&mut self, only_if_changed: bool) -> Result<bool, E> {
+        let Some(budget) = self.power_budget_ma else {
+            let mut written = false;
+            for pixels in self.segments.iter_mut() {
+                if only_if_changed {
+                    written |= pixels.show_if_changed()?;
+                } else {
+                    pixels.show()?;
+                    written = true;
+                }
+            }
+            return Ok(written);
+        };
+
+        let draw = self.segments.iter().map(|pixels| pixels.current_ua()).sum();
+        let idle = self.segments.iter().map(|pixels| pixels.idle_ua()).sum();
+        let scale = PowerScale::for_budget(draw, idle, budget);
+        let mut written = false;
+        for pixels in self.segments.iter_mut() {
+            written |= pixels.write_frame(scale, only_if_changed)?;
+        }
+        Ok(written)
+    }
+
+    /// Write every module, in order.
+    ///
+    /// Stops at the first module that fails.
+    pub fn show(&mut self) -> Result<(), E> {
+        self.write(false)?;
+        Ok(())
+    }
+
+    /// Write only the modules whose LEDs changed since they were last written.
+    ///
+    /// Returns `true` if any module was written.
+    pub fn show_if_changed(&mut self) -> Result<bool, E> {
+        self.write(true)
+    }
+
+    /// Release the modules.
+    pub fn release(self) -> [Pixels<I2C>; M] {
+        self.segments
+    }
+}
@@ -2,7 +2,12 @@
 //!
 //! The Modulino Pixels module has 8 RGB LEDs (APA102-compatible).
 
-use crate::{addresses, Color, Error, I2cDevice, Result};
+use crate::correction::map_brightness;
+use crate::power::{ua_to_ma, PowerScale};
+use crate::{
+    addresses, pinstrap, Color, ColorCorrection, Error, I2cDevice, Modulino, ModulinoKind,
+    PowerModel, Result,
+};
 use embedded_hal::i2c::I2c;
 
 /// Number of LEDs on the Modulino Pixels.
@@ -32,7 +37,130 @@ pub const NUM_LEDS: usize = 8;
 /// ```
 pub struct Pixels<I2C> {
     device: I2cDevice<I2C>,
+    frame: Frame,
+    correction: Option<ColorCorrection>,
+    power_model: PowerModel,
+    power_budget_ma: Option<u16>,
+}
+
+/// LED state and APA102 frame buffer shared by the blocking and async drivers.
+pub(crate) struct Frame {
+    colors: [Color; NUM_LEDS],
+    brightness: [u8; NUM_LEDS],
     data: [u8; NUM_LEDS * 4],
+    /// The bytes last written to the module, if any.
+    sent: Option<[u8; NUM_LEDS * 4]>,
+}
+
+impl Frame {
+    /// Create a frame with all LEDs off (`[0xE0, 0, 0, 0]` per LED).
+    pub(crate) const fn new() -> Self {
+        let mut data = [0u8; NUM_LEDS * 4];
+        let mut i = 0;
+        while i < NUM_LEDS {
+            data[i * 4] = 0xE0;
+            i += 1;
+        }
+        Self {
+            colors: [Color::BLACK; NUM_LEDS],
+            brightness: [0; NUM_LEDS],
+            data,
+            sent: None,
+        }
+    }
+
+    /// The color of one LED, or `None` if out of range.
+    pub(crate) fn color(&self, index: usize) -> Option<Color> {
+        self.colors.get(index).copied()
+    }
+
+    /// The brightness (0-100) of one LED, or `None` if out of range.
+    pub(crate) fn brightness(&self, index: usize) -> Option<u8> {
+        self.brightness.get(index).copied()
+    }
+
+    /// Set the color and brightness of one LED. Returns `false` if out of range.
+    pub(crate) fn set_color(&mut self, index: usize, color: Color, brightness: u8) -> bool {
+        if index >= NUM_LEDS {
+            return false;
+        }
+        self.colors[index] = color;
+        self.brightness[index] = brightness.min(100);
+        true
+    }
+
+    /// Set the brightness of one LED. Returns `false` if out of range.
+    pub(crate) fn set_brightness(&mut self, index: usize, brightness: u8) -> bool {
+        if index >= NUM_LEDS {
+            return false;
+        }
+        self.brightness[index] = brightness.min(100);
+        true
+    }
+
+    /// Set a range of LEDs (inclusive, clamped to the last LED).
+    pub(crate) fn set_range_color(&mut self, from: usize, to: usize, color: Color, brightness: u8) {
+        let end = if to >= NUM_LEDS { NUM_LEDS - 1 } else { to };
+        for i in from..=end {
+            self.set_color(i, color, brightness);
+        }
+    }
+
+    /// Encode one LED, applying `correction` if set.
+    fn encode(&self, index: usize, correction: Option<&ColorCorrection>) -> [u8; 4] {
+        let (color, brightness) = (self.colors[index], self.brightness[index]);
+        match correction {
+            Some(correction) => correction.encode(color, brightness),
+            None => {
+                let data = color.to_apa102_data() | map_brightness(brightness) as u32 | 0xE0;
+                data.to_le_bytes()
+            }
+        }
+    }
+
+    /// Estimated current of all LEDs, in microamps, before any power limit.
+    pub(crate) fn current_ua(
+        &self,
+        correction: Option<&ColorCorrection>,
+        model: &PowerModel,
+    ) -> u32 {
+        (0..NUM_LEDS)
+            .map(|i| model.led_ua(self.encode(i, correction)))
+            .sum()
+    }
+
+    /// Encode the LEDs into the bytes to send, applying `correction` if set
+    /// and reducing every PWM value by `scale`.
+    pub(crate) fn render(
+        &mut self,
+        correction: Option<&ColorCorrection>,
+        scale: Option<PowerScale>,
+    ) {
+        for i in 0..NUM_LEDS {
+            let mut bytes = self.encode(i, correction);
+            if let Some(scale) = scale {
+                for pwm in &mut bytes[1..] {
+                    *pwm = scale.apply(*pwm);
+                }
+            }
+            self.data[i * 4..i * 4 + 4].copy_from_slice(&bytes);
+        }
+    }
+
+    /// The last rendered bytes.
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Check if the last rendered bytes differ from the last ones sent.
+    pub(crate) fn is_changed(&self) -> bool {
+        self.sent != Some(self.data)
+    }
+
+    /// Record the last rendered bytes as sent.
+    pub(crate) fn mark_sent(&mut self) {
+        self.sent = Some(self.data);
+    }
 }
 
 impl<I2C, E> Pixels<I2C>
@@ -51,28 +179,19 @@ where
     /// > [!WARNING]
     /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
     pub fn discover(i2c: &mut I2C) -> Result<u8, E> {
-        let addresses = [addresses::PIXELS];
-        for &addr in &addresses {
-            if i2c.write(addr, &[]).is_ok() {
-                return Ok(addr);
-            }
-        }
-        i2c.write(addresses[0], &[])
-            .map(|_| addresses[0])
-            .map_err(Error::I2c)
+        <Self as Modulino<I2C>>::probe(i2c)
     }
 
     /// Create a new Pixels instance with a custom address.
     pub fn new_with_address(i2c: I2C, address: u8) -> Result<Self, E> {
-        let mut pixels = Self {
+        // The frame starts with all LEDs cleared
+        Ok(Self {
             device: I2cDevice::new(i2c, address),
-            data: [0xE0; NUM_LEDS * 4], // Initialize with brightness bits set, LEDs off
-        };
-
-        // Clear all LEDs on init
-        pixels.clear_all();
-
-        Ok(pixels)
+            frame: Frame::new(),
+            correction: None,
+            power_model: PowerModel::APA102,
+            power_budget_ma: None,
+        })
     }
 
     /// Get the I2C address.
@@ -80,12 +199,6 @@ where
         self.device.address
     }
 
-    /// Map brightness from 0-100 to 0-31 (5-bit brightness for APA102).
-    fn map_brightness(brightness: u8) -> u8 {
-        let clamped = if brightness > 100 { 100 } else { brightness };
-        ((clamped as u16 * 31) / 100) as u8
-    }
-
     /// Set the color of a specific LED.
     ///
     /// # Arguments
@@ -99,17 +212,9 @@ where
         color: Color,
         brightness: u8,
     ) -> Result<&mut Self, E> {
-        if index >= NUM_LEDS {
+        if !self.frame.set_color(index, color, brightness) {
             return Err(Error::OutOfRange);
         }
-
-        let byte_index = index * 4;
-        let mapped_brightness = Self::map_brightness(brightness);
-        let color_data = color.to_apa102_data() | (mapped_brightness as u32) | 0xE0;
-
-        let bytes = color_data.to_le_bytes();
-        self.data[byte_index..byte_index + 4].copy_from_slice(&bytes);
-
         Ok(self)
     }
 
@@ -140,22 +245,16 @@ where
     /// * `index` - LED index (0-7)
     /// * `brightness` - Brightness level (0-100)
     pub fn set_brightness(&mut self, index: usize, brightness: u8) -> Result<&mut Self, E> {
-        if index >= NUM_LEDS {
+        if !self.frame.set_brightness(index, brightness) {
             return Err(Error::OutOfRange);
         }
-
-        let byte_index = index * 4;
-        let mapped_brightness = Self::map_brightness(brightness);
-        self.data[byte_index] = mapped_brightness | 0xE0;
-
         Ok(self)
     }
 
     /// Set the color of all LEDs.
     pub fn set_all_color(&mut self, color: Color, brightness: u8) -> &mut Self {
-        for i in 0..NUM_LEDS {
-            let _ = self.set_color(i, color, brightness);
-        }
+        self.frame
+            .set_range_color(0, NUM_LEDS - 1, color, brightness);
         self
     }
 
@@ -179,17 +278,14 @@ where
         color: Color,
         brightness: u8,
     ) -> &mut Self {
-        let end = if to >= NUM_LEDS { NUM_LEDS - 1 } else { to };
-        for i in from..=end {
-            let _ = self.set_color(i, color, brightness);
-        }
+        self.frame.set_range_color(from, to, color, brightness);
         self
     }
 
     /// Set the brightness of all LEDs without changing their colors.
     pub fn set_all_brightness(&mut self, brightness: u8) -> &mut Self {
         for i in 0..NUM_LEDS {
-            let _ = self.set_brightness(i, brightness);
+            self.frame.set_brightness(i, brightness);
         }
         self
     }
@@ -201,32 +297,123 @@ where
 
     /// Clear a range of LEDs.
     pub fn clear_range(&mut self, from: usize, to: usize) -> &mut Self {
-        let end = if to >= NUM_LEDS { NUM_LEDS - 1 } else { to };
-        for i in from..=end {
-            let _ = self.clear(i);
-        }
-        self
+        self.set_range_color(from, to, Color::BLACK, 0)
     }
 
     /// Clear all LEDs.
     pub fn clear_all(&mut self) -> &mut Self {
-        for i in 0..NUM_LEDS {
-            // Brightness 0 maps to 0 | 0xE0 = 0xE0.
-            // Color Black is 0.
-            // We want [0xE0, 0, 0, 0] in memory (LE).
-            let _ = self.set_color(i, Color::BLACK, 0);
-        }
+        // Brightness 0 maps to 0 | 0xE0 = 0xE0 and black is 0,
+        // giving [0xE0, 0, 0, 0] in memory (LE).
+        self.set_all_color(Color::BLACK, 0)
+    }
+
+    /// Get the color of a specific LED, or `None` if `index` is out of range.
+    pub fn get_color(&self, index: usize) -> Option<Color> {
+        self.frame.color(index)
+    }
+
+    /// Get the brightness (0-100) of a specific LED, or `None` if `index` is
+    /// out of range.
+    ///
+    /// This is the brightness as set, before it is reduced to the 5-bit
+    /// APA102 level.
+    pub fn get_brightness(&self, index: usize) -> Option<u8> {
+        self.frame.brightness(index)
+    }
+
+    /// Set the gamma and white balance correction applied by [`show`](Self::show),
+    /// or `None` to send colors and brightness as they are.
+    pub fn set_correction(&mut self, correction: Option<ColorCorrection>) -> &mut Self {
+        self.correction = correction;
+        self
+    }
+
+    /// Get the current color correction.
+    pub fn correction(&self) -> Option<&ColorCorrection> {
+        self.correction.as_ref()
+    }
+
+    /// Set the model used to estimate the current drawn by the LEDs.
+    pub fn set_power_model(&mut self, model: PowerModel) -> &mut Self {
+        self.power_model = model;
+        self
+    }
+
+    /// Get the power model.
+    pub fn power_model(&self) -> &PowerModel {
+        &self.power_model
+    }
+
+    /// Limit the estimated current to `budget_ma` milliamps, or `None` for no limit.
+    ///
+    /// When a frame would draw more, [`show`](Self::show) scales every LED
+    /// down by the same factor. The stored colors are not changed.
+    pub fn set_power_budget(&mut self, budget_ma: Option<u16>) -> &mut Self {
+        self.power_budget_ma = budget_ma;
         self
     }
 
+    /// Get the power budget in milliamps.
+    pub fn power_budget(&self) -> Option<u16> {
+        self.power_budget_ma
+    }
+
+    /// Estimated current drawn by the current LED states, in milliamps,
+    /// before the power budget is applied.
+    pub fn estimated_current_ma(&self) -> u32 {
+        ua_to_ma(self.current_ua())
+    }
+
+    /// Estimated current in microamps, before the power budget.
+    pub(crate) fn current_ua(&self) -> u32 {
+        self.frame
+            .current_ua(self.correction.as_ref(), &self.power_model)
+    }
+
+    /// Current of all LEDs off, in microamps.
+    pub(crate) fn idle_ua(&self) -> u32 {
+        self.power_model.idle_ua() * NUM_LEDS as u32
+    }
+
+    /// Render the frame with `scale` and write it, unless `only_if_changed`
+    /// is set and the module already shows it.
+    pub(crate) fn write_frame(
+        &mut self,
+        scale: Option<PowerScale>,
+        only_if_changed: bool,
+    ) -> Result<bool, E> {
+        self.frame.render(self.correction.as_ref(), scale);
+        if only_if_changed && !self.frame.is_changed() {
+            return Ok(false);
+        }
+        self.device.write(self.frame.as_bytes())?;
+        self.frame.mark_sent();
+        Ok(true)
+    }
+
+    /// The scale that keeps this module within its own budget.
+    fn power_scale(&self) -> Option<PowerScale> {
+        let budget = self.power_budget_ma?;
+        PowerScale::for_budget(self.current_ua(), self.idle_ua(), budget)
+    }
+
     /// Apply the current LED states to the hardware.
     ///
     /// This must be called after setting colors for changes to take effect.
     pub fn show(&mut self) -> Result<(), E> {
-        self.device.write(&self.data)?;
+        self.write_frame(self.power_scale(), false)?;
         Ok(())
     }
 
+    /// Apply the current LED states only if they differ from the last ones
+    /// written.
+    ///
+    /// Returns `true` if the module was written. The first call always writes,
+    /// as the module's state is unknown until then.
+    pub fn show_if_changed(&mut self) -> Result<bool, E> {
+        self.write_frame(self.power_scale(), true)
+    }
+
     /// Set a color and immediately show it.
     pub fn set_color_show(&mut self, index: usize, color: Color, brightness: u8) -> Result<(), E> {
         self.set_color(index, color, brightness)?;
@@ -238,3 +425,21 @@ where
         self.device.release()
     }
 }
+
+impl<I2C: I2c> Modulino<I2C> for Pixels<I2C> {
+    const KIND: ModulinoKind = ModulinoKind::Pixels;
+    const DEFAULT_ADDRESSES: &'static [u8] = &[addresses::PIXELS];
+    const PINSTRAP: &'static [u8] = &[pinstrap::PIXELS];
+
+    fn new_with_address(i2c: I2C, address: u8) -> Result<Self, I2C::Error> {
+        Self::new_with_address(i2c, address)
+    }
+
+    fn address(&self) -> u8 {
+        self.device.address
+    }
+
+    fn release(self) -> I2C {
+        self.device.release()
+    }
+}
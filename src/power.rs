@@ -0,0 +1,103 @@
+//! Current estimation and limiting for the Modulino Pixels.
+
+/// Highest APA102 global brightness.
+const MAX_GLOBAL: u32 = 31;
+
+/// Estimated supply current of an APA102 LED.
+///
+/// Each channel draws its full current at PWM 255 and global brightness 31,
+/// and proportionally less below that. Every LED also draws a small idle
+/// current, even when off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PowerModel {
+    red_ua: u32,
+    green_ua: u32,
+    blue_ua: u32,
+    idle_ua: u32,
+}
+
+impl PowerModel {
+    /// Typical APA102 figures: 20 mA per channel and 0.8 mA idle.
+    pub const APA102: PowerModel = PowerModel::new(20, 20, 20).with_idle_ua(800);
+
+    /// A model with the full-scale current of each channel, in milliamps.
+    pub const fn new(red_ma: u16, green_ma: u16, blue_ma: u16) -> Self {
+        Self {
+            red_ua: red_ma as u32 * 1000,
+            green_ua: green_ma as u32 * 1000,
+            blue_ua: blue_ma as u32 * 1000,
+            idle_ua: 0,
+        }
+    }
+
+    /// Set the current of an LED that is off, in microamps.
+    pub const fn with_idle_ua(mut self, idle_ua: u16) -> Self {
+        self.idle_ua = idle_ua as u32;
+        self
+    }
+
+    /// The idle current of one LED, in microamps.
+    pub const fn idle_ua(&self) -> u32 {
+        self.idle_ua
+    }
+
+    /// Current of one channel at `global` brightness (0-31) and `pwm`
+    /// (0-255), in microamps.
+    const fn channel_ua(full_ua: u32, global: u8, pwm: u8) -> u32 {
+        let global = if global as u32 > MAX_GLOBAL {
+            MAX_GLOBAL
+        } else {
+            global as u32
+        };
+        (full_ua as u64 * global as u64 * pwm as u64 / (MAX_GLOBAL as u64 * 255)) as u32
+    }
+
+    /// Current of one LED, including idle, for an APA102 frame
+    /// `[0xE0 | global, blue, green, red]`, in microamps.
+    pub const fn led_ua(&self, frame: [u8; 4]) -> u32 {
+        let global = frame[0] & 0x1F;
+        self.idle_ua
+            + Self::channel_ua(self.blue_ua, global, frame[1])
+            + Self::channel_ua(self.green_ua, global, frame[2])
+            + Self::channel_ua(self.red_ua, global, frame[3])
+    }
+}
+
+impl Default for PowerModel {
+    fn default() -> Self {
+        Self::APA102
+    }
+}
+
+/// A proportional reduction of every LED's PWM to stay within a budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PowerScale {
+    num: u32,
+    den: u32,
+}
+
+impl PowerScale {
+    /// The scale that brings `draw_ua` down to `budget_ma`, or `None` if it
+    /// already fits. `idle_ua` is the part of the draw that cannot be reduced.
+    pub(crate) fn for_budget(draw_ua: u32, idle_ua: u32, budget_ma: u16) -> Option<Self> {
+        let budget_ua = budget_ma as u32 * 1000;
+        if draw_ua <= budget_ua || draw_ua <= idle_ua {
+            return None;
+        }
+        Some(Self {
+            num: budget_ua.saturating_sub(idle_ua),
+            den: draw_ua - idle_ua,
+        })
+    }
+
+    /// Apply the scale to a PWM value, rounding down.
+    pub(crate) fn apply(&self, pwm: u8) -> u8 {
+        (pwm as u64 * self.num as u64 / self.den as u64) as u8
+    }
+}
+
+/// Round microamps up to milliamps.
+pub(crate) fn ua_to_ma(ua: u32) -> u32 {
+    ua.div_ceil(1000)
+}
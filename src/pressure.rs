@@ -13,9 +13,20 @@
 //!
 //! If the `lps22hb` crate is updated to EH 1.0, this can be moved to an external wrapper.
 
-use crate::{addresses, Error, Result};
+use crate::{addresses, Error, Modulino, ModulinoKind, Result};
+use embedded_hal::delay::DelayNs;
 use embedded_hal::i2c::I2c;
 
+// LPS22HB register addresses
+pub(crate) const REG_WHO_AM_I: u8 = 0x0F;
+pub(crate) const REG_CTRL_REG1: u8 = 0x10;
+pub(crate) const REG_CTRL_REG2: u8 = 0x11;
+pub(crate) const _REG_STATUS_REG: u8 = 0x27;
+pub(crate) const REG_OUT_P_XL: u8 = 0x28;
+pub(crate) const REG_OUT_T_L: u8 = 0x2B;
+
+pub(crate) const WHO_AM_I_VALUE: u8 = 0xB1;
+
 /// Driver for the Modulino Pressure module (LPS22HB sensor).
 pub struct Pressure<I2C> {
     i2c: I2C,
@@ -26,21 +37,9 @@ impl<I2C, E> Pressure<I2C>
 where
     I2C: I2c<Error = E>,
 {
-    const REG_WHO_AM_I: u8 = 0x0F;
-    const REG_CTRL_REG1: u8 = 0x10;
-    const REG_CTRL_REG2: u8 = 0x11;
-    const _REG_STATUS_REG: u8 = 0x27;
-    const REG_OUT_P_XL: u8 = 0x28;
-    const REG_OUT_T_L: u8 = 0x2B;
-
-    const WHO_AM_I_VALUE: u8 = 0xB1;
-
     /// Create a new Pressure instance.
     pub fn new(i2c: I2C) -> Self {
-        Self {
-            i2c,
-            address: addresses::PRESSURE,
-        }
+        Self::new_with_address(i2c, addresses::PRESSURE)
     }
 
     /// Discover if a Pressure module is connected.
@@ -50,30 +49,32 @@ where
     /// > [!WARNING]
     /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
     pub fn discover(i2c: &mut I2C) -> Result<u8, E> {
-        let addresses = [addresses::PRESSURE];
-        for &addr in &addresses {
-            if i2c.write(addr, &[]).is_ok() {
-                return Ok(addr);
-            }
-        }
-        i2c.write(addresses[0], &[])
-            .map(|_| addresses[0])
-            .map_err(Error::I2c)
+        <Self as Modulino<I2C>>::probe(i2c)
+    }
+
+    /// Create a new Pressure instance with a custom address.
+    pub fn new_with_address(i2c: I2C, address: u8) -> Self {
+        Self { i2c, address }
+    }
+
+    /// Get the I2C address.
+    pub fn address(&self) -> u8 {
+        self.address
     }
 
     /// Initialize the sensor.
     pub fn init(&mut self) -> Result<(), E> {
         let mut id = [0u8; 1];
         self.i2c
-            .write_read(self.address, &[Self::REG_WHO_AM_I], &mut id)
+            .write_read(self.address, &[REG_WHO_AM_I], &mut id)
             .map_err(Error::I2c)?;
-        if id[0] != Self::WHO_AM_I_VALUE {
+        if id[0] != WHO_AM_I_VALUE {
             return Err(Error::DeviceNotFound);
         }
 
         // Set ODR to 10Hz, enable block data update
         self.i2c
-            .write(self.address, &[Self::REG_CTRL_REG1, 0x22])
+            .write(self.address, &[REG_CTRL_REG1, 0x22])
             .map_err(Error::I2c)?;
 
         Ok(())
@@ -83,30 +84,26 @@ where
     pub fn pressure(&mut self) -> Result<f32, E> {
         let mut buf = [0u8; 3];
         self.i2c
-            .write_read(self.address, &[Self::REG_OUT_P_XL], &mut buf)
+            .write_read(self.address, &[REG_OUT_P_XL], &mut buf)
             .map_err(Error::I2c)?;
 
-        let raw = (buf[0] as u32) | ((buf[1] as u32) << 8) | ((buf[2] as u32) << 16);
-        // Sensitivity is 4096 LSB/hPa
-        Ok(raw as f32 / 4096.0)
+        Ok(decode_pressure(&buf))
     }
 
     /// Read ambient temperature in degrees Celsius.
     pub fn temperature(&mut self) -> Result<f32, E> {
         let mut buf = [0u8; 2];
         self.i2c
-            .write_read(self.address, &[Self::REG_OUT_T_L], &mut buf)
+            .write_read(self.address, &[REG_OUT_T_L], &mut buf)
             .map_err(Error::I2c)?;
 
-        let raw = (buf[0] as u16 | ((buf[1] as u16) << 8)) as i16;
-        // Sensitivity is 100 LSB/°C
-        Ok(raw as f32 / 100.0)
+        Ok(decode_temperature(&buf))
     }
 
     /// Perform a software reset.
     pub fn reset(&mut self) -> Result<(), E> {
         self.i2c
-            .write(self.address, &[Self::REG_CTRL_REG2, 0x04])
+            .write(self.address, &[REG_CTRL_REG2, 0x04])
             .map_err(Error::I2c)
     }
 
@@ -115,3 +112,39 @@ where
         self.i2c
     }
 }
+
+impl<I2C: I2c> Modulino<I2C> for Pressure<I2C> {
+    const KIND: ModulinoKind = ModulinoKind::Pressure;
+    const DEFAULT_ADDRESSES: &'static [u8] = &[addresses::PRESSURE];
+    const PINSTRAP: &'static [u8] = &[];
+
+    fn new_with_address(i2c: I2C, address: u8) -> Result<Self, I2C::Error> {
+        Ok(Self::new_with_address(i2c, address))
+    }
+
+    fn init<D: DelayNs>(&mut self, _delay: &mut D) -> Result<(), I2C::Error> {
+        Pressure::init(self)
+    }
+
+    fn address(&self) -> u8 {
+        self.address
+    }
+
+    fn release(self) -> I2C {
+        self.i2c
+    }
+}
+
+/// Decode a 24-bit pressure reading into hPa.
+pub(crate) fn decode_pressure(buf: &[u8; 3]) -> f32 {
+    let raw = (buf[0] as u32) | ((buf[1] as u32) << 8) | ((buf[2] as u32) << 16);
+    // Sensitivity is 4096 LSB/hPa
+    raw as f32 / 4096.0
+}
+
+/// Decode a 16-bit temperature reading into degrees Celsius.
+pub(crate) fn decode_temperature(buf: &[u8; 2]) -> f32 {
+    let raw = (buf[0] as u16 | ((buf[1] as u16) << 8)) as i16;
+    // Sensitivity is 100 LSB/°C
+    raw as f32 / 100.0
+}
@@ -0,0 +1,416 @@
+//! RTTTL (Nokia ringtone) parsing.
+//!
+//! An RTTTL string has three colon-separated sections: a name, defaults for
+//! duration (`d`), octave (`o`) and tempo (`b`), and a comma-separated list of
+//! notes such as `8c#6.` (eighth-note C#6, dotted) or `p` (a rest):
+//!
+//! ```text
+//! Tetris:d=4,o=5,b=160:e6,8b,8c6,8d6,16e6,16d6,8c6,8b
+//! ```
+//!
+//! Parsing borrows the input string and does not allocate. The whole string is
+//! validated up front, so iterating over the notes cannot fail.
+
+use core::fmt;
+
+use crate::{Note, Pitch, ToneSource, ToneStep};
+
+/// What was wrong with an RTTTL string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RtttlErrorKind {
+    /// The string does not have the `name:defaults:notes` sections.
+    MissingSection,
+    /// A default is not `d=`, `o=` or `b=` with a valid value.
+    InvalidDefault,
+    /// A duration is not 1, 2, 4, 8, 16 or 32.
+    InvalidDuration,
+    /// A note is not one of `a`-`h` or `p`, or has stray characters.
+    InvalidNote,
+    /// An octave is outside 0-8.
+    InvalidOctave,
+    /// The tempo is outside 1-900 beats per minute.
+    InvalidTempo,
+    /// The note is below the buzzer's minimum frequency.
+    BelowMinimumFrequency,
+}
+
+/// Error returned when parsing an RTTTL string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RtttlError {
+    /// What was wrong.
+    pub kind: RtttlErrorKind,
+    /// Byte offset of the offending item in the input string.
+    pub position: usize,
+}
+
+impl fmt::Display for RtttlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let what = match self.kind {
+            RtttlErrorKind::MissingSection => "missing name, defaults or notes section",
+            RtttlErrorKind::InvalidDefault => "invalid default",
+            RtttlErrorKind::InvalidDuration => "invalid duration",
+            RtttlErrorKind::InvalidNote => "invalid note",
+            RtttlErrorKind::InvalidOctave => "invalid octave",
+            RtttlErrorKind::InvalidTempo => "invalid tempo",
+            RtttlErrorKind::BelowMinimumFrequency => "note below the minimum buzzer frequency",
+        };
+        write!(f, "{} at byte {}", what, self.position)
+    }
+}
+
+/// A note from an RTTTL string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RtttlNote {
+    /// The pitch, or `None` for a rest.
+    pub pitch: Option<Pitch>,
+    /// Frequency in Hz, or 0 for a rest.
+    pub frequency: u16,
+    /// Duration in milliseconds.
+    pub duration_ms: u16,
+}
+
+impl RtttlNote {
+    /// Check if this is a rest.
+    pub fn is_rest(&self) -> bool {
+        self.pitch.is_none()
+    }
+
+    /// The matching [`Note`], if the enum has one ([`Note::Rest`] for rests).
+    ///
+    /// Notes outside the enum's range only have a [`frequency`](Self::frequency).
+    pub fn note(&self) -> Option<Note> {
+        match self.pitch {
+            Some(pitch) => pitch.to_note(),
+            None => Some(Note::Rest),
+        }
+    }
+}
+
+/// A parsed RTTTL ringtone.
+///
+/// [`notes`](Self::notes) iterates over the notes, and the ringtone is also a
+/// [`ToneSource`], so it can be played in the background by a
+/// [`Sequencer`](crate::Sequencer).
+///
+/// # Example
+///
+/// ```rust
+/// use modulino::{Note, Rtttl};
+///
+/// let tune = Rtttl::parse("Tetris:d=4,o=5,b=160:e6,8b,8c6,8d6").unwrap();
+/// assert_eq!(tune.name(), "Tetris");
+///
+/// let first = tune.notes().next().unwrap();
+/// assert_eq!(first.note(), Some(Note::E6));
+/// assert_eq!(first.duration_ms, 375);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rtttl<'a> {
+    name: &'a str,
+    defaults: Defaults,
+    /// The notes section and its byte offset in the input.
+    body: &'a str,
+    body_offset: usize,
+    gap_ms: u16,
+    cursor: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Defaults {
+    duration: u8,
+    octave: u8,
+    bpm: u16,
+}
+
+impl<'a> Rtttl<'a> {
+    /// Parse and validate an RTTTL string.
+    ///
+    /// Missing defaults use the RTTTL standard values `d=4`, `o=6`, `b=63`.
+    pub fn parse(text: &'a str) -> Result<Self, RtttlError> {
+        let missing = RtttlError {
+            kind: RtttlErrorKind::MissingSection,
+            position: text.len(),
+        };
+        let (name, rest) = text.split_once(':').ok_or(missing)?;
+        let (defaults, body) = rest.split_once(':').ok_or(missing)?;
+        let defaults_offset = name.len() + 1;
+        let body_offset = defaults_offset + defaults.len() + 1;
+
+        let defaults = parse_defaults(defaults, defaults_offset)?;
+        let rtttl = Self {
+            name: name.trim(),
+            defaults,
+            body,
+            body_offset,
+            gap_ms: 0,
+            cursor: 0,
+        };
+
+        for (position, token) in rtttl.tokens(0) {
+            parse_note(token, defaults).map_err(|kind| RtttlError { kind, position })?;
+        }
+
+        Ok(rtttl)
+    }
+
+    /// Set the silence cut from the end of every note when played by a
+    /// [`Sequencer`](crate::Sequencer), in milliseconds.
+    pub fn with_gap(mut self, gap_ms: u16) -> Self {
+        self.gap_ms = gap_ms;
+        self
+    }
+
+    /// The ringtone name.
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The default note duration (4 for a quarter note).
+    pub fn default_duration(&self) -> u8 {
+        self.defaults.duration
+    }
+
+    /// The default octave.
+    pub fn default_octave(&self) -> u8 {
+        self.defaults.octave
+    }
+
+    /// The tempo in beats per minute.
+    pub fn tempo(&self) -> u16 {
+        self.defaults.bpm
+    }
+
+    /// Iterate over the notes.
+    pub fn notes(&self) -> RtttlNotes<'a> {
+        RtttlNotes {
+            body: self.body,
+            offset: 0,
+            defaults: self.defaults,
+        }
+    }
+
+    /// Total length of the ringtone, in milliseconds.
+    pub fn duration_ms(&self) -> u32 {
+        self.notes().map(|note| note.duration_ms as u32).sum()
+    }
+
+    /// Iterate over `(position, token)` pairs of the notes section from `from`.
+    fn tokens(&self, from: usize) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        let offset = self.body_offset;
+        Tokens {
+            body: self.body,
+            offset: from,
+        }
+        .map(move |(start, token)| (offset + start, token))
+    }
+}
+
+impl ToneSource for Rtttl<'_> {
+    fn next_step(&mut self) -> Option<ToneStep> {
+        let mut notes = RtttlNotes {
+            body: self.body,
+            offset: self.cursor,
+            defaults: self.defaults,
+        };
+        let note = notes.next()?;
+        self.cursor = notes.offset;
+        Some(ToneStep::new(note.frequency, note.duration_ms).with_gap(self.gap_ms))
+    }
+
+    fn rewind(&mut self) {
+        self.cursor = 0;
+    }
+}
+
+/// Iterator over the notes of an [`Rtttl`].
+#[derive(Debug, Clone)]
+pub struct RtttlNotes<'a> {
+    body: &'a str,
+    offset: usize,
+    defaults: Defaults,
+}
+
+impl Iterator for RtttlNotes<'_> {
+    type Item = RtttlNote;
+
+    fn next(&mut self) -> Option<RtttlNote> {
+        let mut tokens = Tokens {
+            body: self.body,
+            offset: self.offset,
+        };
+        let (_, token) = tokens.next()?;
+        self.offset = tokens.offset;
+        // Validated by `Rtttl::parse`
+        parse_note(token, self.defaults).ok()
+    }
+}
+
+/// Splits the notes section on commas, skipping whitespace and empty items.
+struct Tokens<'a> {
+    body: &'a str,
+    offset: usize,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<(usize, &'a str)> {
+        while self.offset < self.body.len() {
+            let rest = &self.body[self.offset..];
+            let end = rest.find(',').unwrap_or(rest.len());
+            let start = self.offset;
+            self.offset += (end + 1).min(rest.len());
+            let item = &rest[..end];
+            let token = item.trim();
+            if !token.is_empty() {
+                let leading = item.len() - item.trim_start().len();
+                return Some((start + leading, token));
+            }
+        }
+        None
+    }
+}
+
+fn parse_defaults(section: &str, offset: usize) -> Result<Defaults, RtttlError> {
+    let mut defaults = Defaults {
+        duration: 4,
+        octave: 6,
+        bpm: 63,
+    };
+    let mut position = offset;
+    for item in section.split(',') {
+        let error = |kind| RtttlError { kind, position };
+        let trimmed = item.trim();
+        if !trimmed.is_empty() {
+            let (key, value) = trimmed
+                .split_once('=')
+                .ok_or(error(RtttlErrorKind::InvalidDefault))?;
+            let value = value.trim();
+            match key.trim() {
+                "d" | "D" => {
+                    defaults.duration = value
+                        .parse()
+                        .ok()
+                        .filter(|&d| valid_duration(d))
+                        .ok_or(error(RtttlErrorKind::InvalidDuration))?;
+                }
+                "o" | "O" => {
+                    defaults.octave = value
+                        .parse()
+                        .ok()
+                        .filter(|&o| o <= 8)
+                        .ok_or(error(RtttlErrorKind::InvalidOctave))?;
+                }
+                "b" | "B" => {
+                    defaults.bpm = value
+                        .parse()
+                        .ok()
+                        .filter(|&b| (1..=900).contains(&b))
+                        .ok_or(error(RtttlErrorKind::InvalidTempo))?;
+                }
+                _ => return Err(error(RtttlErrorKind::InvalidDefault)),
+            }
+        }
+        position += item.len() + 1;
+    }
+    Ok(defaults)
+}
+
+fn valid_duration(duration: u8) -> bool {
+    matches!(duration, 1 | 2 | 4 | 8 | 16 | 32)
+}
+
+/// Parse one note token: `[duration]letter[#][.][octave][.]`.
+fn parse_note(token: &str, defaults: Defaults) -> Result<RtttlNote, RtttlErrorKind> {
+    let bytes = token.as_bytes();
+    let mut i = 0;
+
+    let digits = bytes.iter().take_while(|b| b.is_ascii_digit()).count();
+    let duration = if digits > 0 {
+        let d = token[..digits]
+            .parse()
+            .ok()
+            .filter(|&d| valid_duration(d))
+            .ok_or(RtttlErrorKind::InvalidDuration)?;
+        i = digits;
+        d
+    } else {
+        defaults.duration
+    };
+
+    let semitone = match bytes.get(i).map(u8::to_ascii_lowercase) {
+        Some(b'c') => Some(0),
+        Some(b'd') => Some(2),
+        Some(b'e') => Some(4),
+        Some(b'f') => Some(5),
+        Some(b'g') => Some(7),
+        Some(b'a') => Some(9),
+        // `h` is the German name for B, found in some ringtone collections
+        Some(b'b') | Some(b'h') => Some(11),
+        Some(b'p') => None,
+        _ => return Err(RtttlErrorKind::InvalidNote),
+    };
+    i += 1;
+
+    let mut sharp = false;
+    if bytes.get(i) == Some(&b'#') {
+        sharp = true;
+        i += 1;
+    }
+
+    let mut dotted = false;
+    if bytes.get(i) == Some(&b'.') {
+        dotted = true;
+        i += 1;
+    }
+
+    let octave = match bytes.get(i) {
+        Some(b) if b.is_ascii_digit() => {
+            i += 1;
+            let octave = b - b'0';
+            if octave > 8 {
+                return Err(RtttlErrorKind::InvalidOctave);
+            }
+            octave
+        }
+        _ => defaults.octave,
+    };
+
+    if bytes.get(i) == Some(&b'.') {
+        dotted = true;
+        i += 1;
+    }
+
+    if i != bytes.len() {
+        return Err(RtttlErrorKind::InvalidNote);
+    }
+
+    // A whole note lasts four beats
+    let mut duration_ms = 240_000 / (defaults.bpm as u32 * duration as u32);
+    if dotted {
+        duration_ms += duration_ms / 2;
+    }
+    let duration_ms = duration_ms.min(u16::MAX as u32) as u16;
+
+    let pitch = match semitone {
+        Some(semitone) => {
+            let pitch = Pitch::from_octave(semitone, octave as i8)
+                .and_then(|pitch| pitch.transpose(sharp as i8))
+                .ok_or(RtttlErrorKind::InvalidOctave)?;
+            if !pitch.is_playable() {
+                return Err(RtttlErrorKind::BelowMinimumFrequency);
+            }
+            Some(pitch)
+        }
+        None => None,
+    };
+
+    Ok(RtttlNote {
+        pitch,
+        frequency: pitch.map_or(0, |pitch| pitch.frequency()),
+        duration_ms,
+    })
+}
@@ -0,0 +1,314 @@
+//! I2C bus enumeration.
+//!
+//! [`scan`] probes every 7-bit address on the bus and identifies what is
+//! plugged in. MCU-based Modulinos are recognised by the pinstrap byte they
+//! return as the first byte of any read; fixed-function sensors are recognised
+//! by their identity registers.
+//!
+//! > [!WARNING]
+//! > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
+
+use core::fmt;
+use core::ops::RangeInclusive;
+
+use crate::distance::VL53L4CD_IDENTIFICATION_MODEL_ID;
+use crate::{addresses, hub, light, movement, pinstrap, pressure};
+use embedded_hal::i2c::I2c;
+
+/// Addresses probed by [`scan`].
+///
+/// 0x00 is the general call address and 0x78-0x7F are reserved for 10-bit
+/// addressing. The low reserved range is still probed because the Latch Relay
+/// lives at 0x02.
+pub const SCAN_RANGE: RangeInclusive<u8> = 0x01..=0x77;
+
+/// The type of a device found on the bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ModulinoKind {
+    /// Modulino Buttons
+    Buttons,
+    /// Modulino Buzzer
+    Buzzer,
+    /// Modulino Pixels
+    Pixels,
+    /// Modulino Distance (VL53L4CD)
+    Distance,
+    /// Modulino Movement (LSM6DSOX)
+    Movement,
+    /// Modulino Knob
+    Knob,
+    /// Modulino Thermo (HS3003)
+    Thermo,
+    /// Modulino Joystick
+    Joystick,
+    /// Modulino Latch Relay
+    LatchRelay,
+    /// Modulino Opto Relay
+    OptoRelay,
+    /// Modulino Vibro
+    Vibro,
+    /// Modulino LED Matrix
+    LedMatrix,
+    /// Modulino Pressure (LPS22HB)
+    Pressure,
+    /// Modulino Light (LTR-381RGB)
+    Light,
+    /// Modulino Hub (TCA9548A multiplexer)
+    Hub,
+    /// A device that ACKs but could not be identified
+    Unknown,
+}
+
+impl ModulinoKind {
+    /// Map a pinstrap byte to the module type it identifies.
+    pub fn from_pinstrap(value: u8) -> Option<Self> {
+        match value {
+            pinstrap::BUTTONS => Some(Self::Buttons),
+            pinstrap::BUZZER => Some(Self::Buzzer),
+            pinstrap::PIXELS => Some(Self::Pixels),
+            pinstrap::JOYSTICK => Some(Self::Joystick),
+            pinstrap::LATCH_RELAY => Some(Self::LatchRelay),
+            pinstrap::OPTO_RELAY => Some(Self::OptoRelay),
+            pinstrap::VIBRO => Some(Self::Vibro),
+            pinstrap::LED_MATRIX => Some(Self::LedMatrix),
+            v if pinstrap::KNOB.contains(&v) => Some(Self::Knob),
+            _ => None,
+        }
+    }
+
+    /// A human-readable module name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Buttons => "Buttons",
+            Self::Buzzer => "Buzzer",
+            Self::Pixels => "Pixels",
+            Self::Distance => "Distance",
+            Self::Movement => "Movement",
+            Self::Knob => "Knob",
+            Self::Thermo => "Thermo",
+            Self::Joystick => "Joystick",
+            Self::LatchRelay => "Latch Relay",
+            Self::OptoRelay => "Opto Relay",
+            Self::Vibro => "Vibro",
+            Self::LedMatrix => "LED Matrix",
+            Self::Pressure => "Pressure",
+            Self::Light => "Light",
+            Self::Hub => "Hub",
+            Self::Unknown => "Unknown",
+        }
+    }
+}
+
+impl fmt::Display for ModulinoKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The devices found by [`scan`], indexed by address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanResult {
+    kinds: [Option<ModulinoKind>; 128],
+}
+
+impl ScanResult {
+    /// Create an empty result.
+    pub const fn new() -> Self {
+        Self { kinds: [None; 128] }
+    }
+
+    /// Record a device at `address`.
+    pub fn insert(&mut self, address: u8, kind: ModulinoKind) {
+        if let Some(slot) = self.kinds.get_mut(address as usize) {
+            *slot = Some(kind);
+        }
+    }
+
+    /// Forget the device at `address`, returning it if there was one.
+    pub fn remove(&mut self, address: u8) -> Option<ModulinoKind> {
+        self.kinds.get_mut(address as usize).and_then(Option::take)
+    }
+
+    /// The device found at `address`, if any.
+    pub fn get(&self, address: u8) -> Option<ModulinoKind> {
+        self.kinds.get(address as usize).copied().flatten()
+    }
+
+    /// Iterate over `(address, kind)` pairs in address order.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, ModulinoKind)> + '_ {
+        self.kinds
+            .iter()
+            .enumerate()
+            .filter_map(|(addr, kind)| kind.map(|k| (addr as u8, k)))
+    }
+
+    /// The address of the first device of the given kind.
+    pub fn find(&self, kind: ModulinoKind) -> Option<u8> {
+        self.iter().find(|&(_, k)| k == kind).map(|(addr, _)| addr)
+    }
+
+    /// Check if a device of the given kind was found.
+    pub fn contains(&self, kind: ModulinoKind) -> bool {
+        self.find(kind).is_some()
+    }
+
+    /// Number of devices found.
+    pub fn len(&self) -> usize {
+        self.kinds.iter().filter(|k| k.is_some()).count()
+    }
+
+    /// Check if no devices were found.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for ScanResult {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How a fixed-function sensor is recognised at its address.
+#[derive(Clone, Copy)]
+pub(crate) enum Identity {
+    /// No readable ID; the address alone identifies the device.
+    Address,
+    /// An 8-bit register whose masked value must match.
+    Reg8 { reg: u8, mask: u8, value: u8 },
+    /// A 16-bit register with a 16-bit index that must match.
+    Reg16 { reg: u16, value: u16 },
+}
+
+/// Fixed-function sensors, checked before falling back to the pinstrap byte.
+pub(crate) const SENSORS: [(u8, ModulinoKind, Identity); 6] = [
+    (
+        addresses::DISTANCE,
+        ModulinoKind::Distance,
+        Identity::Reg16 {
+            reg: VL53L4CD_IDENTIFICATION_MODEL_ID,
+            value: 0xEBAA,
+        },
+    ),
+    (addresses::THERMO, ModulinoKind::Thermo, Identity::Address),
+    (
+        addresses::LIGHT,
+        ModulinoKind::Light,
+        Identity::Reg8 {
+            reg: light::REG_PART_ID,
+            mask: 0xF0,
+            value: 0xC0,
+        },
+    ),
+    (
+        addresses::PRESSURE,
+        ModulinoKind::Pressure,
+        Identity::Reg8 {
+            reg: pressure::REG_WHO_AM_I,
+            mask: 0xFF,
+            value: pressure::WHO_AM_I_VALUE,
+        },
+    ),
+    (
+        addresses::MOVEMENT[0],
+        ModulinoKind::Movement,
+        Identity::Reg8 {
+            reg: movement::LSM6DSOX_WHO_AM_I,
+            mask: 0xFF,
+            value: movement::LSM6DSOX_WHO_AM_I_VALUE,
+        },
+    ),
+    (
+        addresses::MOVEMENT[1],
+        ModulinoKind::Movement,
+        Identity::Reg8 {
+            reg: movement::LSM6DSOX_WHO_AM_I,
+            mask: 0xFF,
+            value: movement::LSM6DSOX_WHO_AM_I_VALUE,
+        },
+    ),
+];
+
+/// Check if `address` is one of the TCA9548A A0-A2 variants.
+pub(crate) fn is_hub_address(address: u8) -> bool {
+    hub::ADDRESSES.contains(&address)
+}
+
+/// Scan the bus and identify every Modulino on it.
+///
+/// Every address in [`SCAN_RANGE`] is probed with a zero-length write. For each
+/// device that ACKs:
+///
+/// 1. Known sensor addresses are checked against their identity registers.
+/// 2. Addresses 0x70-0x77 are reported as [`ModulinoKind::Hub`], since the
+///    TCA9548A has no ID register.
+/// 3. Otherwise a single byte is read and matched against the [`pinstrap`] map.
+///
+/// Anything left is reported as [`ModulinoKind::Unknown`]. Bus errors are treated
+/// as "no device", so this never fails.
+///
+/// > [!WARNING]
+/// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use modulino::{scan, ModulinoKind};
+///
+/// let found = modulino::scan(&mut i2c);
+/// for (address, kind) in found.iter() {
+///     println!("0x{:02X}: {}", address, kind);
+/// }
+/// if let Some(addr) = found.find(ModulinoKind::Knob) {
+///     let knob = modulino::Knob::new_with_address(i2c, addr)?;
+/// }
+/// ```
+pub fn scan<I2C: I2c>(i2c: &mut I2C) -> ScanResult {
+    let mut result = ScanResult::new();
+    for address in SCAN_RANGE {
+        if i2c.write(address, &[]).is_ok() {
+            result.insert(address, identify(i2c, address));
+        }
+    }
+    result
+}
+
+/// Identify the device that ACKed at `address`.
+///
+/// See [`scan`] for the order of checks.
+pub fn identify<I2C: I2c>(i2c: &mut I2C, address: u8) -> ModulinoKind {
+    for (addr, kind, identity) in SENSORS {
+        if addr != address {
+            continue;
+        }
+        let matches = match identity {
+            Identity::Address => true,
+            Identity::Reg8 { reg, mask, value } => {
+                let mut buf = [0u8; 1];
+                i2c.write_read(address, &[reg], &mut buf).is_ok() && buf[0] & mask == value
+            }
+            Identity::Reg16 { reg, value } => {
+                let mut buf = [0u8; 2];
+                i2c.write_read(address, &reg.to_be_bytes(), &mut buf)
+                    .is_ok()
+                    && u16::from_be_bytes(buf) == value
+            }
+        };
+        if matches {
+            return kind;
+        }
+    }
+
+    if is_hub_address(address) {
+        return ModulinoKind::Hub;
+    }
+
+    let mut buf = [0u8; 1];
+    if i2c.read(address, &mut buf).is_ok() {
+        if let Some(kind) = ModulinoKind::from_pinstrap(buf[0]) {
+            return kind;
+        }
+    }
+    ModulinoKind::Unknown
+}
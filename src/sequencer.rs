@@ -0,0 +1,319 @@
+//! Non-blocking tone sequencing for the Modulino Buzzer.
+//!
+//! The buzzer firmware plays one tone at a time and times it itself, so a tune
+//! only needs the next `tone()` call to be issued at the right moment. A
+//! [`Sequencer`] does that from a caller-supplied millisecond timestamp, taking
+//! its steps from any [`ToneSource`] such as a [`Melody`].
+
+use crate::{Buzzer, Note, Result};
+use embedded_hal::i2c::I2c;
+
+/// One step of a tune: a tone (or rest) and the time until the next step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ToneStep {
+    /// Frequency in Hz, or 0 for a rest.
+    pub frequency: u16,
+    /// How long the tone sounds, in milliseconds.
+    pub sound_ms: u16,
+    /// Time from the start of this step to the start of the next, in milliseconds.
+    pub duration_ms: u16,
+}
+
+impl ToneStep {
+    /// A tone that sounds for its whole duration.
+    pub const fn new(frequency: u16, duration_ms: u16) -> Self {
+        Self {
+            frequency,
+            sound_ms: duration_ms,
+            duration_ms,
+        }
+    }
+
+    /// A silent step.
+    pub const fn rest(duration_ms: u16) -> Self {
+        Self::new(0, duration_ms)
+    }
+
+    /// Shorten the sounding part by `gap_ms`, leaving silence before the next step.
+    pub const fn with_gap(mut self, gap_ms: u16) -> Self {
+        self.sound_ms = self.duration_ms.saturating_sub(gap_ms);
+        self
+    }
+
+    /// Check if this step is silent.
+    pub const fn is_rest(&self) -> bool {
+        self.frequency == 0 || self.sound_ms == 0
+    }
+}
+
+/// A source of [`ToneStep`]s for a [`Sequencer`].
+pub trait ToneSource {
+    /// The next step, or `None` at the end of the tune.
+    fn next_step(&mut self) -> Option<ToneStep>;
+
+    /// Go back to the first step.
+    fn rewind(&mut self);
+}
+
+/// A tune stored as a slice of `(Note, length)` steps.
+///
+/// Lengths are in ticks, with [`Melody::TICKS_PER_BEAT`] ticks per beat (a
+/// quarter note); the associated constants name the common lengths. The tempo
+/// sets the beats per minute, and an optional gap is cut from the end of every
+/// note so repeated notes are heard separately.
+///
+/// # Example
+///
+/// ```rust
+/// use modulino::{Melody, Note, ToneSource};
+///
+/// const TUNE: &[(Note, u16)] = &[
+///     (Note::E5, Melody::QUARTER),
+///     (Note::B4, Melody::EIGHTH),
+///     (Note::Rest, Melody::EIGHTH),
+///     (Note::C5, Melody::HALF),
+/// ];
+///
+/// let mut melody = Melody::new(TUNE).with_tempo(120).with_gap(20);
+/// let first = melody.next_step().unwrap();
+/// assert_eq!(first.frequency, 659);
+/// assert_eq!(first.duration_ms, 500);
+/// assert_eq!(first.sound_ms, 480);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Melody<'a> {
+    steps: &'a [(Note, u16)],
+    tempo: u16,
+    gap_ms: u16,
+    position: usize,
+}
+
+impl<'a> Melody<'a> {
+    /// Ticks per beat (quarter note).
+    pub const TICKS_PER_BEAT: u16 = 8;
+    /// Whole note length.
+    pub const WHOLE: u16 = 32;
+    /// Half note length.
+    pub const HALF: u16 = 16;
+    /// Quarter note length.
+    pub const QUARTER: u16 = 8;
+    /// Eighth note length.
+    pub const EIGHTH: u16 = 4;
+    /// Sixteenth note length.
+    pub const SIXTEENTH: u16 = 2;
+    /// Thirty-second note length.
+    pub const THIRTY_SECOND: u16 = 1;
+    /// Dotted half note length.
+    pub const DOTTED_HALF: u16 = 24;
+    /// Dotted quarter note length.
+    pub const DOTTED_QUARTER: u16 = 12;
+    /// Dotted eighth note length.
+    pub const DOTTED_EIGHTH: u16 = 6;
+
+    /// Default tempo in beats per minute.
+    pub const DEFAULT_TEMPO: u16 = 120;
+
+    /// Create a melody at the default tempo, without gaps.
+    pub const fn new(steps: &'a [(Note, u16)]) -> Self {
+        Self {
+            steps,
+            tempo: Self::DEFAULT_TEMPO,
+            gap_ms: 0,
+            position: 0,
+        }
+    }
+
+    /// Set the tempo in beats per minute (at least 1).
+    pub const fn with_tempo(mut self, bpm: u16) -> Self {
+        self.tempo = if bpm == 0 { 1 } else { bpm };
+        self
+    }
+
+    /// Set the silence cut from the end of every note, in milliseconds.
+    pub const fn with_gap(mut self, gap_ms: u16) -> Self {
+        self.gap_ms = gap_ms;
+        self
+    }
+
+    /// Get the tempo in beats per minute.
+    pub fn tempo(&self) -> u16 {
+        self.tempo
+    }
+
+    /// Get the gap in milliseconds.
+    pub fn gap(&self) -> u16 {
+        self.gap_ms
+    }
+
+    /// Number of steps.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Check if the melody has no steps.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Convert a length in ticks to milliseconds at the current tempo.
+    pub fn ticks_to_ms(&self, ticks: u16) -> u16 {
+        let ms = ticks as u32 * 60_000 / (self.tempo as u32 * Self::TICKS_PER_BEAT as u32);
+        ms.min(u16::MAX as u32) as u16
+    }
+
+    /// Total length of one pass through the melody, in milliseconds.
+    pub fn duration_ms(&self) -> u32 {
+        self.steps
+            .iter()
+            .map(|&(_, ticks)| self.ticks_to_ms(ticks) as u32)
+            .sum()
+    }
+}
+
+impl ToneSource for Melody<'_> {
+    fn next_step(&mut self) -> Option<ToneStep> {
+        let &(note, ticks) = self.steps.get(self.position)?;
+        self.position += 1;
+        Some(ToneStep::new(note.frequency(), self.ticks_to_ms(ticks)).with_gap(self.gap_ms))
+    }
+
+    fn rewind(&mut self) {
+        self.position = 0;
+    }
+}
+
+/// Plays a [`ToneSource`] on a buzzer without blocking.
+///
+/// Call [`tick`](Self::tick) from the main loop with the current time; it
+/// issues a `tone()` call whenever the next step is due and returns straight
+/// away otherwise. With async drivers, use [`poll`](Self::poll) and pass the
+/// returned step to `Buzzer::play_step`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use modulino::{Buzzer, Melody, Note, Sequencer};
+///
+/// const JINGLE: &[(Note, u16)] = &[(Note::C5, Melody::EIGHTH), (Note::G5, Melody::QUARTER)];
+///
+/// let mut buzzer = Buzzer::new(i2c)?;
+/// let mut music = Sequencer::new(Melody::new(JINGLE).with_gap(10)).with_looping(true);
+///
+/// loop {
+///     music.tick(&mut buzzer, now_ms())?;
+///     // ... poll sensors ...
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sequencer<S> {
+    source: S,
+    looping: bool,
+    next_at: Option<u32>,
+    finished: bool,
+}
+
+impl<S: ToneSource> Sequencer<S> {
+    /// Create a sequencer that plays `source` once, starting on the first tick.
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            looping: false,
+            next_at: None,
+            finished: false,
+        }
+    }
+
+    /// Start over from the beginning when the source runs out.
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Change whether the source is looped.
+    pub fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+    }
+
+    /// Check if the source is looped.
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Check if the last step has played out.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Rewind the source and play again from the next tick.
+    pub fn restart(&mut self) {
+        self.source.rewind();
+        self.next_at = None;
+        self.finished = false;
+    }
+
+    /// Get the source.
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// Get the source mutably.
+    pub fn source_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+
+    /// Consume the sequencer and return the source.
+    pub fn into_source(self) -> S {
+        self.source
+    }
+
+    /// Return the step to play if one is due at `now_ms`.
+    ///
+    /// `now_ms` may wrap around. Steps are scheduled from the previous step's
+    /// start time, so late calls do not make the tune drift.
+    pub fn poll(&mut self, now_ms: u32) -> Option<ToneStep> {
+        if self.finished {
+            return None;
+        }
+        if let Some(due) = self.next_at {
+            if (now_ms.wrapping_sub(due) as i32) < 0 {
+                return None;
+            }
+        }
+
+        let step = match self.source.next_step() {
+            Some(step) => step,
+            None if self.looping => {
+                self.source.rewind();
+                match self.source.next_step() {
+                    Some(step) => step,
+                    None => {
+                        self.finished = true;
+                        return None;
+                    }
+                }
+            }
+            None => {
+                self.finished = true;
+                return None;
+            }
+        };
+
+        let start = self.next_at.unwrap_or(now_ms);
+        self.next_at = Some(start.wrapping_add(step.duration_ms as u32));
+        Some(step)
+    }
+
+    /// Play the next step on `buzzer` if it is due at `now_ms`.
+    ///
+    /// Returns `true` while the tune is still playing.
+    pub fn tick<I2C, E>(&mut self, buzzer: &mut Buzzer<I2C>, now_ms: u32) -> Result<bool, E>
+    where
+        I2C: I2c<Error = E>,
+    {
+        if let Some(step) = self.poll(now_ms) {
+            buzzer.play_step(step)?;
+        }
+        Ok(!self.finished)
+    }
+}
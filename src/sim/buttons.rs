@@ -0,0 +1,68 @@
+//! Simulated Modulino Buttons.
+
+use super::{firmware_read, SimDevice};
+use crate::addresses;
+
+/// Simulated Modulino Buttons firmware.
+///
+/// Reads return the pinstrap byte followed by the three button states; writes
+/// set the three LEDs.
+pub struct Buttons {
+    address: u8,
+    pressed: [bool; 3],
+    leds: [bool; 3],
+}
+
+impl Buttons {
+    /// Create a module at the default address.
+    pub fn new() -> Self {
+        Self::with_address(addresses::BUTTONS)
+    }
+
+    /// Create a module at a custom address.
+    pub fn with_address(address: u8) -> Self {
+        Self {
+            address,
+            pressed: [false; 3],
+            leds: [false; 3],
+        }
+    }
+
+    /// Set which buttons are held down.
+    pub fn press(&mut self, a: bool, b: bool, c: bool) {
+        self.pressed = [a, b, c];
+    }
+
+    /// Release all buttons.
+    pub fn release_all(&mut self) {
+        self.pressed = [false; 3];
+    }
+
+    /// The LED states last written by the host (A, B, C).
+    pub fn leds(&self) -> [bool; 3] {
+        self.leds
+    }
+}
+
+impl Default for Buttons {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimDevice for Buttons {
+    fn acknowledges(&self, address: u8) -> bool {
+        address == self.address
+    }
+
+    fn write(&mut self, _address: u8, data: &[u8]) {
+        for (led, &value) in self.leds.iter_mut().zip(data) {
+            *led = value != 0;
+        }
+    }
+
+    fn read(&mut self, _address: u8, buf: &mut [u8]) {
+        let data = self.pressed.map(u8::from);
+        firmware_read(self.address, &data, buf);
+    }
+}
@@ -0,0 +1,68 @@
+//! Simulated Modulino Buzzer.
+
+use super::{firmware_read, SimDevice};
+use crate::addresses;
+
+/// Simulated Modulino Buzzer firmware.
+///
+/// Writes carry the frequency and duration as two little-endian `u32`s. A
+/// frequency of 0 silences the buzzer.
+pub struct Buzzer {
+    address: u8,
+    frequency: u32,
+    duration_ms: u32,
+}
+
+impl Buzzer {
+    /// Create a module at the default address.
+    pub fn new() -> Self {
+        Self::with_address(addresses::BUZZER)
+    }
+
+    /// Create a module at a custom address.
+    pub fn with_address(address: u8) -> Self {
+        Self {
+            address,
+            frequency: 0,
+            duration_ms: 0,
+        }
+    }
+
+    /// The frequency last requested by the host, in Hz (0 when silent).
+    pub fn frequency(&self) -> u32 {
+        self.frequency
+    }
+
+    /// The duration last requested by the host, in milliseconds (0 for continuous).
+    pub fn duration_ms(&self) -> u32 {
+        self.duration_ms
+    }
+
+    /// Check if a tone is playing.
+    pub fn is_playing(&self) -> bool {
+        self.frequency != 0
+    }
+}
+
+impl Default for Buzzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimDevice for Buzzer {
+    fn acknowledges(&self, address: u8) -> bool {
+        address == self.address
+    }
+
+    fn write(&mut self, _address: u8, data: &[u8]) {
+        if let Some(bytes) = data.get(0..8) {
+            self.frequency = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            self.duration_ms = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+        }
+    }
+
+    fn read(&mut self, _address: u8, buf: &mut [u8]) {
+        firmware_read(self.address, &[], buf);
+    }
+}
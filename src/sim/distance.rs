@@ -0,0 +1,262 @@
+//! Simulated Modulino Distance (VL53L4CD).
+
+use super::SimDevice;
+use crate::addresses;
+use crate::distance::{
+    offset_from_register, DetectionMode, VL53L4CD_DEFAULT_CONFIGURATION,
+    VL53L4CD_DEFAULT_CONFIGURATION_START, VL53L4CD_FIRMWARE_SYSTEM_STATUS,
+    VL53L4CD_GPIO_HV_MUX_CTRL, VL53L4CD_GPIO_TIO_HV_STATUS, VL53L4CD_IDENTIFICATION_MODEL_ID,
+    VL53L4CD_OSC_FREQUENCY, VL53L4CD_RANGE_OFFSET_MM,
+    VL53L4CD_RESULT_FINAL_CROSSTALK_CORRECTED_RANGE_MM_SD0, VL53L4CD_RESULT_OSC_CALIBRATE_VAL,
+    VL53L4CD_RESULT_RANGE_STATUS, VL53L4CD_SYSTEM_INTERRUPT, VL53L4CD_SYSTEM_INTERRUPT_CLEAR,
+    VL53L4CD_SYSTEM_START, VL53L4CD_THRESH_HIGH, VL53L4CD_THRESH_LOW,
+};
+
+/// Size of the simulated register map (covers 0x0000-0x01FF).
+const MAP_SIZE: usize = 0x200;
+
+/// Model ID reported at IDENTIFICATION__MODEL_ID.
+const MODEL_ID: u16 = 0xEBAA;
+
+/// Typical OSC_FREQUENCY value.
+const OSC_FREQUENCY: u16 = 0xBE00;
+
+/// Typical RESULT__OSC_CALIBRATE_VAL value.
+const OSC_CALIBRATE_VAL: u16 = 0x0240;
+
+/// Raw RESULT__RANGE_STATUS value for a valid measurement.
+const RANGE_STATUS_VALID: u8 = 0x09;
+
+/// Simulated VL53L4CD time-of-flight sensor.
+///
+/// Registers use 16-bit big-endian indices and auto-increment. The model
+/// follows the flow used by the driver:
+///
+/// - FIRMWARE__SYSTEM_STATUS reads 0 while booting and 3 once booted.
+/// - Writing 0x40 (autonomous), 0x21 (back to back) or 0x10 (single shot)
+///   to SYSTEM_START starts ranging; 0x00 stops it.
+/// - A measurement becomes ready after a configurable number of polls of
+///   GPIO__TIO_HV_STATUS, which then reports the interrupt with the polarity
+///   set in GPIO_HV_MUX__CTRL.
+/// - With detection thresholds set in SYSTEM__INTERRUPT, only measurements
+///   that meet them raise the interrupt; the others are discarded.
+/// - Writing 0x01 to SYSTEM__INTERRUPT_CLEAR acknowledges it. In continuous
+///   mode the next measurement is then started.
+/// - The result block reports the range status and distance set with
+///   [`set_distance`](Self::set_distance), plus the offset in RANGE_OFFSET_MM.
+pub struct Distance {
+    regs: [u8; MAP_SIZE],
+    pointer: usize,
+    boot_polls: u32,
+    measurement_polls: u32,
+    countdown: u32,
+    ranging: bool,
+    continuous: bool,
+    ready: bool,
+    distance_mm: u16,
+    range_status: u8,
+    measurements: u32,
+}
+
+impl Distance {
+    /// Create a booted sensor measuring 100 mm.
+    pub fn new() -> Self {
+        let mut regs = [0u8; MAP_SIZE];
+        let start = VL53L4CD_DEFAULT_CONFIGURATION_START as usize;
+        regs[start..start + VL53L4CD_DEFAULT_CONFIGURATION.len()]
+            .copy_from_slice(&VL53L4CD_DEFAULT_CONFIGURATION);
+        let id = VL53L4CD_IDENTIFICATION_MODEL_ID as usize;
+        regs[id..id + 2].copy_from_slice(&MODEL_ID.to_be_bytes());
+        let osc = VL53L4CD_OSC_FREQUENCY as usize;
+        regs[osc..osc + 2].copy_from_slice(&OSC_FREQUENCY.to_be_bytes());
+        let calibrate = VL53L4CD_RESULT_OSC_CALIBRATE_VAL as usize;
+        regs[calibrate..calibrate + 2].copy_from_slice(&OSC_CALIBRATE_VAL.to_be_bytes());
+        Self {
+            regs,
+            pointer: 0,
+            boot_polls: 0,
+            measurement_polls: 0,
+            countdown: 0,
+            ranging: false,
+            continuous: false,
+            ready: false,
+            distance_mm: 100,
+            range_status: RANGE_STATUS_VALID,
+            measurements: 0,
+        }
+    }
+
+    /// Report "booting" for the first `polls` reads of FIRMWARE__SYSTEM_STATUS.
+    pub fn with_boot_polls(mut self, polls: u32) -> Self {
+        self.boot_polls = polls;
+        self
+    }
+
+    /// Report "not ready" for `polls` interrupt polls after each measurement starts.
+    pub fn with_measurement_polls(mut self, polls: u32) -> Self {
+        self.measurement_polls = polls;
+        self
+    }
+
+    /// Set the distance reported by subsequent measurements, with a valid status.
+    pub fn set_distance(&mut self, distance_mm: u16) {
+        self.distance_mm = distance_mm;
+        self.range_status = RANGE_STATUS_VALID;
+    }
+
+    /// Set the raw RESULT__RANGE_STATUS reported by subsequent measurements.
+    pub fn set_range_status(&mut self, raw: u8) {
+        self.range_status = raw;
+    }
+
+    /// Check if the sensor is ranging.
+    pub fn is_ranging(&self) -> bool {
+        self.ranging
+    }
+
+    /// Number of measurements completed so far.
+    pub fn measurements(&self) -> u32 {
+        self.measurements
+    }
+
+    /// The value of a register, as last written by the host.
+    pub fn register(&self, reg: u16) -> u8 {
+        self.regs.get(reg as usize).copied().unwrap_or(0)
+    }
+
+    /// Set the value of a register, e.g. to preset other parts of the result block.
+    pub fn set_register(&mut self, reg: u16, value: u8) {
+        if let Some(slot) = self.regs.get_mut(reg as usize) {
+            *slot = value;
+        }
+    }
+
+    fn start_measurement(&mut self) {
+        self.ready = false;
+        self.countdown = self.measurement_polls;
+    }
+
+    fn word(&self, reg: u16) -> u16 {
+        let reg = reg as usize;
+        u16::from_be_bytes([self.regs[reg], self.regs[reg + 1]])
+    }
+
+    fn complete_measurement(&mut self) {
+        self.measurements += 1;
+        self.regs[VL53L4CD_RESULT_RANGE_STATUS as usize] = self.range_status;
+        let offset = offset_from_register(self.word(VL53L4CD_RANGE_OFFSET_MM));
+        let distance_mm =
+            (self.distance_mm as i32 + offset as i32).clamp(0, u16::MAX as i32) as u16;
+        let distance = VL53L4CD_RESULT_FINAL_CROSSTALK_CORRECTED_RANGE_MM_SD0 as usize;
+        self.regs[distance..distance + 2].copy_from_slice(&distance_mm.to_be_bytes());
+
+        let config = self.regs[VL53L4CD_SYSTEM_INTERRUPT as usize];
+        let raised = DetectionMode::from_config(config).map_or(true, |mode| {
+            mode.matches(
+                distance_mm,
+                self.word(VL53L4CD_THRESH_LOW),
+                self.word(VL53L4CD_THRESH_HIGH),
+            )
+        });
+        if raised {
+            self.ready = true;
+            if !self.continuous {
+                self.ranging = false;
+            }
+        } else if self.continuous {
+            self.start_measurement();
+        } else {
+            self.ranging = false;
+        }
+    }
+
+    fn interrupt_polarity(&self) -> u8 {
+        (self.regs[VL53L4CD_GPIO_HV_MUX_CTRL as usize] & 0x10) >> 4
+    }
+
+    fn write_reg(&mut self, reg: usize, value: u8) {
+        match reg {
+            r if r == VL53L4CD_SYSTEM_START as usize => match value {
+                0x40 | 0x21 | 0x10 => {
+                    self.ranging = true;
+                    self.continuous = value != 0x10;
+                    self.start_measurement();
+                }
+                _ => {
+                    self.ranging = false;
+                    self.ready = false;
+                }
+            },
+            r if r == VL53L4CD_SYSTEM_INTERRUPT_CLEAR as usize => {
+                if value & 0x01 != 0 {
+                    self.ready = false;
+                    if self.ranging {
+                        self.start_measurement();
+                    }
+                }
+            }
+            r => {
+                if let Some(slot) = self.regs.get_mut(r) {
+                    *slot = value;
+                }
+            }
+        }
+    }
+
+    fn read_reg(&mut self, reg: usize) -> u8 {
+        match reg {
+            r if r == VL53L4CD_FIRMWARE_SYSTEM_STATUS as usize => {
+                if self.boot_polls > 0 {
+                    self.boot_polls -= 1;
+                    0x00
+                } else {
+                    0x03
+                }
+            }
+            r if r == VL53L4CD_GPIO_TIO_HV_STATUS as usize => {
+                if self.ranging && !self.ready {
+                    if self.countdown == 0 {
+                        self.complete_measurement();
+                    } else {
+                        self.countdown -= 1;
+                    }
+                }
+                let polarity = self.interrupt_polarity();
+                let status = if self.ready { polarity ^ 1 } else { polarity };
+                (self.regs[r] & !0x01) | status
+            }
+            r => self.regs.get(r).copied().unwrap_or(0),
+        }
+    }
+}
+
+impl Default for Distance {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimDevice for Distance {
+    fn acknowledges(&self, address: u8) -> bool {
+        address == addresses::DISTANCE
+    }
+
+    fn write(&mut self, _address: u8, data: &[u8]) {
+        if data.len() < 2 {
+            return;
+        }
+        let start = u16::from_be_bytes([data[0], data[1]]) as usize;
+        for (i, &value) in data[2..].iter().enumerate() {
+            self.write_reg(start + i, value);
+        }
+        self.pointer = start + data.len() - 2;
+    }
+
+    fn read(&mut self, _address: u8, buf: &mut [u8]) {
+        let start = self.pointer;
+        for (i, byte) in buf.iter_mut().enumerate() {
+            *byte = self.read_reg(start + i);
+        }
+        self.pointer = start + buf.len();
+    }
+}
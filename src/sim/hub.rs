@@ -0,0 +1,99 @@
+//! Simulated Modulino Hub (TCA9548A).
+
+use core::cell::RefCell;
+
+use super::{find, SimDevice};
+use crate::hub::DEFAULT_ADDRESS;
+
+/// Simulated TCA9548A I2C multiplexer.
+///
+/// Writing a byte to the hub's own address sets the control register; reading
+/// returns it. Transfers to any other address are forwarded to the first device
+/// that ACKs it on one of the enabled channels.
+pub struct Hub<'a> {
+    address: u8,
+    control: u8,
+    channels: [&'a [&'a RefCell<dyn SimDevice + 'a>]; 8],
+}
+
+impl<'a> Hub<'a> {
+    /// Create a hub at the default address (0x70) with nothing connected.
+    pub fn new() -> Self {
+        Self::with_address(DEFAULT_ADDRESS)
+    }
+
+    /// Create a hub at a custom address with nothing connected.
+    pub fn with_address(address: u8) -> Self {
+        Self {
+            address,
+            control: 0,
+            channels: [&[]; 8],
+        }
+    }
+
+    /// Connect devices to a channel (0 to 7).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not in the range 0 to 7.
+    pub fn with_channel(
+        mut self,
+        port: u8,
+        devices: &'a [&'a RefCell<dyn SimDevice + 'a>],
+    ) -> Self {
+        self.channels[port as usize] = devices;
+        self
+    }
+
+    /// The control register (bitmask of enabled channels).
+    pub fn control(&self) -> u8 {
+        self.control
+    }
+
+    fn downstream(&self, address: u8) -> Option<&'a RefCell<dyn SimDevice + 'a>> {
+        self.channels
+            .iter()
+            .enumerate()
+            .filter(|(port, _)| self.control & (1 << port) != 0)
+            .find_map(|(_, devices)| find(devices, address))
+    }
+}
+
+impl Default for Hub<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimDevice for Hub<'_> {
+    fn acknowledges(&self, address: u8) -> bool {
+        address == self.address || self.downstream(address).is_some()
+    }
+
+    fn write(&mut self, address: u8, data: &[u8]) {
+        if address == self.address {
+            if let Some(&control) = data.last() {
+                self.control = control;
+            }
+        } else if let Some(device) = self.downstream(address) {
+            device.borrow_mut().write(address, data);
+        }
+    }
+
+    fn read(&mut self, address: u8, buf: &mut [u8]) {
+        if address == self.address {
+            buf.fill(self.control);
+        } else if let Some(device) = self.downstream(address) {
+            device.borrow_mut().read(address, buf);
+        }
+    }
+
+    fn write_read(&mut self, address: u8, write: &[u8], read: &mut [u8]) {
+        if address == self.address {
+            self.write(address, write);
+            self.read(address, read);
+        } else if let Some(device) = self.downstream(address) {
+            device.borrow_mut().write_read(address, write, read);
+        }
+    }
+}
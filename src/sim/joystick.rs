@@ -0,0 +1,66 @@
+//! Simulated Modulino Joystick.
+
+use super::{firmware_read, SimDevice};
+use crate::addresses;
+
+/// Simulated Modulino Joystick firmware.
+///
+/// Reads return the pinstrap byte, the raw X and Y axes (0-255, centered on
+/// 128) and the button state.
+pub struct Joystick {
+    address: u8,
+    x: u8,
+    y: u8,
+    pressed: bool,
+}
+
+impl Joystick {
+    /// Create a module at the default address.
+    pub fn new() -> Self {
+        Self::with_address(addresses::JOYSTICK)
+    }
+
+    /// Create a module at a custom address.
+    pub fn with_address(address: u8) -> Self {
+        Self {
+            address,
+            x: 128,
+            y: 128,
+            pressed: false,
+        }
+    }
+
+    /// Move the stick, as an offset from center (-128 to 127).
+    pub fn set_position(&mut self, x: i8, y: i8) {
+        self.x = (x as i16 + 128) as u8;
+        self.y = (y as i16 + 128) as u8;
+    }
+
+    /// Return the stick to center.
+    pub fn center(&mut self) {
+        self.set_position(0, 0);
+    }
+
+    /// Set whether the stick is pressed.
+    pub fn set_pressed(&mut self, pressed: bool) {
+        self.pressed = pressed;
+    }
+}
+
+impl Default for Joystick {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimDevice for Joystick {
+    fn acknowledges(&self, address: u8) -> bool {
+        address == self.address
+    }
+
+    fn write(&mut self, _address: u8, _data: &[u8]) {}
+
+    fn read(&mut self, _address: u8, buf: &mut [u8]) {
+        firmware_read(self.address, &[self.x, self.y, self.pressed as u8], buf);
+    }
+}
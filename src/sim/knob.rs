@@ -0,0 +1,85 @@
+//! Simulated Modulino Knob.
+
+use super::{firmware_read, SimDevice};
+use crate::addresses;
+
+/// Simulated Modulino Knob firmware.
+///
+/// Reads return the pinstrap byte, the encoder value (little-endian `i16`) and
+/// the button state; writes set the encoder value.
+///
+/// Some firmware revisions store the negated value on a write. Enable that
+/// behaviour with [`with_inverted_set`](Self::with_inverted_set) to exercise the
+/// driver's detection of it.
+pub struct Knob {
+    address: u8,
+    value: i16,
+    pressed: bool,
+    inverted_set: bool,
+}
+
+impl Knob {
+    /// Create a module at the default address.
+    pub fn new() -> Self {
+        Self::with_address(addresses::KNOB[0])
+    }
+
+    /// Create a module at a custom address.
+    pub fn with_address(address: u8) -> Self {
+        Self {
+            address,
+            value: 0,
+            pressed: false,
+            inverted_set: false,
+        }
+    }
+
+    /// Emulate the firmware bug that negates values written by the host.
+    pub fn with_inverted_set(mut self) -> Self {
+        self.inverted_set = true;
+        self
+    }
+
+    /// Turn the knob by `steps` detents (positive is clockwise).
+    pub fn rotate(&mut self, steps: i16) {
+        self.value = self.value.wrapping_add(steps);
+    }
+
+    /// Set whether the knob is pressed.
+    pub fn set_pressed(&mut self, pressed: bool) {
+        self.pressed = pressed;
+    }
+
+    /// The encoder value as stored by the firmware.
+    pub fn value(&self) -> i16 {
+        self.value
+    }
+}
+
+impl Default for Knob {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimDevice for Knob {
+    fn acknowledges(&self, address: u8) -> bool {
+        address == self.address
+    }
+
+    fn write(&mut self, _address: u8, data: &[u8]) {
+        if let Some(bytes) = data.get(0..2) {
+            let value = i16::from_le_bytes([bytes[0], bytes[1]]);
+            self.value = if self.inverted_set {
+                value.wrapping_neg()
+            } else {
+                value
+            };
+        }
+    }
+
+    fn read(&mut self, _address: u8, buf: &mut [u8]) {
+        let value = self.value.to_le_bytes();
+        firmware_read(self.address, &[value[0], value[1], self.pressed as u8], buf);
+    }
+}
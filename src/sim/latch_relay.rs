@@ -0,0 +1,61 @@
+//! Simulated Modulino Latch Relay.
+
+use super::{firmware_read, SimDevice};
+use crate::addresses;
+
+/// Simulated Modulino Latch Relay firmware.
+///
+/// Writing `1` sets the relay, `0` resets it. Reads report the two coil
+/// status bytes; both are 0 until the first command after power-up, since the
+/// latched state is unknown.
+pub struct LatchRelay {
+    address: u8,
+    state: Option<bool>,
+}
+
+impl LatchRelay {
+    /// Create a module at the default address.
+    pub fn new() -> Self {
+        Self::with_address(addresses::LATCH_RELAY)
+    }
+
+    /// Create a module at a custom address.
+    pub fn with_address(address: u8) -> Self {
+        Self {
+            address,
+            state: None,
+        }
+    }
+
+    /// The relay state, or `None` if it has not been commanded since power-up.
+    pub fn is_on(&self) -> Option<bool> {
+        self.state
+    }
+}
+
+impl Default for LatchRelay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimDevice for LatchRelay {
+    fn acknowledges(&self, address: u8) -> bool {
+        address == self.address
+    }
+
+    fn write(&mut self, _address: u8, data: &[u8]) {
+        if let Some(&command) = data.first() {
+            self.state = Some(command != 0);
+        }
+    }
+
+    fn read(&mut self, _address: u8, buf: &mut [u8]) {
+        let status = match self.state {
+            None => [0, 0],
+            Some(false) => [1, 0],
+            Some(true) => [0, 1],
+        };
+        firmware_read(self.address, &status, buf);
+    }
+}
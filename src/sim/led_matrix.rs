@@ -0,0 +1,108 @@
+//! Simulated Modulino LED Matrix.
+
+use super::{firmware_read, SimDevice};
+use crate::addresses;
+
+/// Simulated Modulino LED Matrix firmware.
+///
+/// Reads return the pinstrap byte followed by the current mode identifier
+/// (`MON` or `GS4`). A write of the size expected by the current mode (12 bytes
+/// monochrome, 48 bytes grayscale) that starts with `MON` or `GS4` switches
+/// mode; any other write of that size is a frame. The stored frame is always
+/// column-major, as on the device.
+pub struct LedMatrix {
+    address: u8,
+    grayscale: bool,
+    frame: [u8; 48],
+}
+
+impl LedMatrix {
+    /// Create a module at the default address.
+    pub fn new() -> Self {
+        Self::with_address(addresses::LED_MATRIX)
+    }
+
+    /// Create a module at a custom address.
+    pub fn with_address(address: u8) -> Self {
+        Self {
+            address,
+            grayscale: false,
+            frame: [0u8; 48],
+        }
+    }
+
+    /// Check if the firmware is in 4-bit grayscale mode.
+    pub fn is_grayscale(&self) -> bool {
+        self.grayscale
+    }
+
+    /// The last frame written by the host (12 or 48 bytes depending on mode).
+    pub fn frame(&self) -> &[u8] {
+        if self.grayscale {
+            &self.frame
+        } else {
+            &self.frame[..12]
+        }
+    }
+
+    /// The brightness of a pixel: 0 or 1 in monochrome mode, 0-15 in grayscale.
+    ///
+    /// Returns 0 for coordinates outside the 12x8 matrix.
+    pub fn pixel(&self, x: u8, y: u8) -> u8 {
+        if x >= 12 || y >= 8 {
+            return 0;
+        }
+        if self.grayscale {
+            let byte = self.frame[x as usize * 4 + y as usize / 2];
+            if y % 2 == 0 {
+                byte & 0x0F
+            } else {
+                byte >> 4
+            }
+        } else {
+            (self.frame[x as usize] >> y) & 1
+        }
+    }
+
+    fn frame_len(&self) -> usize {
+        if self.grayscale {
+            48
+        } else {
+            12
+        }
+    }
+}
+
+impl Default for LedMatrix {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimDevice for LedMatrix {
+    fn acknowledges(&self, address: u8) -> bool {
+        address == self.address
+    }
+
+    fn write(&mut self, _address: u8, data: &[u8]) {
+        if data.len() != self.frame_len() {
+            return;
+        }
+        match &data[..3] {
+            b"MON" => {
+                self.grayscale = false;
+                self.frame = [0u8; 48];
+            }
+            b"GS4" => {
+                self.grayscale = true;
+                self.frame = [0u8; 48];
+            }
+            _ => self.frame[..data.len()].copy_from_slice(data),
+        }
+    }
+
+    fn read(&mut self, _address: u8, buf: &mut [u8]) {
+        let id = if self.grayscale { b"GS4" } else { b"MON" };
+        firmware_read(self.address, id, buf);
+    }
+}
@@ -0,0 +1,111 @@
+//! Simulated Modulino Light (LTR-381RGB).
+
+use super::{Registers, SimDevice};
+use crate::addresses;
+use crate::light::{
+    REG_DATA_BLUE, REG_DATA_GREEN, REG_DATA_IR, REG_DATA_RED, REG_MAIN_CTRL, REG_MAIN_STATUS,
+    REG_PART_ID,
+};
+
+/// Part ID reported by the simulated sensor (part 0xC, revision 2).
+const PART_ID: u8 = 0xC2;
+
+/// MAIN_STATUS bits.
+const STATUS_POWER_ON: u8 = 0x20;
+const STATUS_DATA: u8 = 0x08;
+
+/// Simulated LTR-381RGB color and ambient light sensor.
+///
+/// The four channels are 20-bit little-endian values spread over three
+/// registers each. They read as zero until measurements are enabled in
+/// MAIN_CTRL. MAIN_STATUS reports power-on and new-data flags and is cleared
+/// by reading it.
+pub struct Light {
+    registers: Registers<0x30>,
+    channels: [u32; 4],
+}
+
+impl Light {
+    /// Create a sensor in its power-on state, with all channels dark.
+    pub fn new() -> Self {
+        let mut registers = Registers::new();
+        registers.regs[REG_PART_ID as usize] = PART_ID;
+        registers.regs[REG_MAIN_STATUS as usize] = STATUS_POWER_ON;
+        Self {
+            registers,
+            channels: [0; 4],
+        }
+    }
+
+    /// Set the raw channel counts (masked to 20 bits).
+    pub fn set_channels(&mut self, ir: u32, green: u32, red: u32, blue: u32) {
+        self.channels = [ir, green, red, blue].map(|value| value & 0x000F_FFFF);
+        self.registers.regs[REG_MAIN_STATUS as usize] |= STATUS_DATA;
+    }
+
+    /// Check if the host enabled measurements.
+    pub fn is_enabled(&self) -> bool {
+        self.registers.regs[REG_MAIN_CTRL as usize] & 0x02 != 0
+    }
+
+    /// The value of a register, as last written by the host.
+    pub fn register(&self, reg: u8) -> u8 {
+        self.registers.regs.get(reg as usize).copied().unwrap_or(0)
+    }
+
+    fn read_reg(&mut self, reg: usize) -> u8 {
+        let channels = [REG_DATA_IR, REG_DATA_GREEN, REG_DATA_RED, REG_DATA_BLUE];
+        for (channel, &base) in channels.iter().enumerate() {
+            let base = base as usize;
+            if (base..base + 3).contains(&reg) {
+                if !self.is_enabled() {
+                    return 0;
+                }
+                return self.channels[channel].to_le_bytes()[reg - base];
+            }
+        }
+        let value = self.registers.regs.get(reg).copied().unwrap_or(0);
+        if reg == REG_MAIN_STATUS as usize {
+            self.registers.regs[reg] = 0;
+        }
+        value
+    }
+}
+
+impl Default for Light {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimDevice for Light {
+    fn acknowledges(&self, address: u8) -> bool {
+        address == addresses::LIGHT
+    }
+
+    fn write(&mut self, _address: u8, data: &[u8]) {
+        // PART_ID and MAIN_STATUS are read-only
+        let part_id = self.registers.regs[REG_PART_ID as usize];
+        let status = self.registers.regs[REG_MAIN_STATUS as usize];
+        let written = self.registers.write(data);
+        self.registers.regs[REG_PART_ID as usize] = part_id;
+        self.registers.regs[REG_MAIN_STATUS as usize] = status;
+
+        // MAIN_CTRL bit 4 is a software reset
+        if written.contains(&(REG_MAIN_CTRL as usize))
+            && self.registers.regs[REG_MAIN_CTRL as usize] & 0x10 != 0
+        {
+            let channels = self.channels;
+            *self = Self::new();
+            self.channels = channels;
+        }
+    }
+
+    fn read(&mut self, _address: u8, buf: &mut [u8]) {
+        let start = self.registers.pointer;
+        for (i, byte) in buf.iter_mut().enumerate() {
+            *byte = self.read_reg(start + i);
+        }
+        self.registers.pointer = start + buf.len();
+    }
+}
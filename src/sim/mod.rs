@@ -0,0 +1,263 @@
+//! Software simulation of Modulino devices.
+//!
+//! This module provides virtual devices that behave like the real module
+//! firmware and sensors at the register level, and a [`SimBus`] that implements
+//! `embedded_hal::i2c::I2c` on top of them. Any driver in this crate (or
+//! application code built on it) can run against the simulated bus on a host,
+//! without hand-written transaction lists.
+//!
+//! Devices are shared with the bus through `RefCell`s, so a test can change
+//! the simulated inputs (press a button, turn the knob, move an object in front
+//! of the distance sensor) and inspect the outputs (LED states, buzzer tone,
+//! relay state) while drivers are using the bus.
+//!
+//! # Example
+//!
+//! ```rust
+//! use core::cell::RefCell;
+//! use modulino::sim::{self, SimBus, SimDevice};
+//! use modulino::Buttons;
+//!
+//! let device = RefCell::new(sim::Buttons::new());
+//! let devices: [&RefCell<dyn SimDevice>; 1] = [&device];
+//! let bus = SimBus::new(&devices);
+//!
+//! let mut buttons = Buttons::new(bus).unwrap();
+//! device.borrow_mut().press(true, false, false);
+//! assert!(buttons.read().unwrap().a);
+//!
+//! buttons.set_leds(false, true, false).unwrap();
+//! assert_eq!(device.borrow().leds(), [false, true, false]);
+//! ```
+
+use core::cell::RefCell;
+
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation};
+
+mod buttons;
+mod buzzer;
+mod distance;
+mod hub;
+mod joystick;
+mod knob;
+mod latch_relay;
+mod led_matrix;
+mod light;
+mod movement;
+mod opto_relay;
+mod pixels;
+mod pressure;
+mod thermo;
+mod vibro;
+
+pub use buttons::Buttons;
+pub use buzzer::Buzzer;
+pub use distance::Distance;
+pub use hub::Hub;
+pub use joystick::Joystick;
+pub use knob::Knob;
+pub use latch_relay::LatchRelay;
+pub use led_matrix::LedMatrix;
+pub use light::Light;
+pub use movement::Movement;
+pub use opto_relay::OptoRelay;
+pub use pixels::Pixels;
+pub use pressure::Pressure;
+pub use thermo::Thermo;
+pub use vibro::Vibro;
+
+/// A virtual device attached to a [`SimBus`].
+///
+/// Implementations only see transfers addressed to them: the bus checks
+/// [`acknowledges`](Self::acknowledges) before forwarding a read or write.
+pub trait SimDevice {
+    /// Check if the device ACKs `address`.
+    fn acknowledges(&self, address: u8) -> bool;
+
+    /// Handle a write of `data` to `address`.
+    fn write(&mut self, address: u8, data: &[u8]);
+
+    /// Handle a read from `address` into `buf`.
+    fn read(&mut self, address: u8, buf: &mut [u8]);
+
+    /// Handle a write followed by a repeated-start read.
+    fn write_read(&mut self, address: u8, write: &[u8], read: &mut [u8]) {
+        self.write(address, write);
+        self.read(address, read);
+    }
+}
+
+/// Error returned by a [`SimBus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SimError {
+    /// No simulated device acknowledged the address.
+    NoAcknowledge,
+}
+
+impl embedded_hal::i2c::Error for SimError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            SimError::NoAcknowledge => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+        }
+    }
+}
+
+/// A simulated I2C bus.
+///
+/// The bus is `Copy`, so it can be handed to as many drivers as needed; all
+/// copies talk to the same devices. A transfer goes to the first device that
+/// acknowledges its address, and fails with [`SimError::NoAcknowledge`] if
+/// there is none.
+#[derive(Clone, Copy)]
+pub struct SimBus<'a> {
+    devices: &'a [&'a RefCell<dyn SimDevice + 'a>],
+}
+
+impl<'a> SimBus<'a> {
+    /// Create a bus with the given devices attached.
+    pub fn new(devices: &'a [&'a RefCell<dyn SimDevice + 'a>]) -> Self {
+        Self { devices }
+    }
+
+    /// Check if any device ACKs `address`.
+    pub fn acknowledges(&self, address: u8) -> bool {
+        self.device(address).is_some()
+    }
+
+    fn device(&self, address: u8) -> Option<&'a RefCell<dyn SimDevice + 'a>> {
+        find(self.devices, address)
+    }
+
+    fn target(&self, address: u8) -> Result<&'a RefCell<dyn SimDevice + 'a>, SimError> {
+        self.device(address).ok_or(SimError::NoAcknowledge)
+    }
+}
+
+/// The first device in `devices` that ACKs `address`.
+pub(crate) fn find<'a>(
+    devices: &[&'a RefCell<dyn SimDevice + 'a>],
+    address: u8,
+) -> Option<&'a RefCell<dyn SimDevice + 'a>> {
+    devices
+        .iter()
+        .find(|device| device.borrow().acknowledges(address))
+        .copied()
+}
+
+impl ErrorType for SimBus<'_> {
+    type Error = SimError;
+}
+
+impl I2c for SimBus<'_> {
+    fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        self.target(address)?.borrow_mut().read(address, read);
+        Ok(())
+    }
+
+    fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        self.target(address)?.borrow_mut().write(address, write);
+        Ok(())
+    }
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.target(address)?
+            .borrow_mut()
+            .write_read(address, write, read);
+        Ok(())
+    }
+
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let device = self.target(address)?;
+        let mut device = device.borrow_mut();
+        for operation in operations {
+            match operation {
+                Operation::Read(buf) => device.read(address, buf),
+                Operation::Write(data) => device.write(address, data),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::i2c::I2c for SimBus<'_> {
+    async fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        I2c::read(self, address, read)
+    }
+
+    async fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        I2c::write(self, address, write)
+    }
+
+    async fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        I2c::write_read(self, address, write, read)
+    }
+
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        I2c::transaction(self, address, operations)
+    }
+}
+
+/// Fill `buf` the way the Modulino firmware answers a read: the pinstrap
+/// byte (the 8-bit form of `address`) followed by `data`, then zeros.
+pub(crate) fn firmware_read(address: u8, data: &[u8], buf: &mut [u8]) {
+    buf.fill(0);
+    if let Some((first, rest)) = buf.split_first_mut() {
+        *first = address << 1;
+        let len = rest.len().min(data.len());
+        rest[..len].copy_from_slice(&data[..len]);
+    }
+}
+
+/// A byte-addressed register file with an auto-incrementing pointer.
+///
+/// Used by the sensor models that expose 8-bit register maps.
+pub(crate) struct Registers<const N: usize> {
+    pub(crate) regs: [u8; N],
+    pub(crate) pointer: usize,
+}
+
+impl<const N: usize> Registers<N> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            regs: [0u8; N],
+            pointer: 0,
+        }
+    }
+
+    /// Handle a write: the first byte sets the pointer, the rest are stored.
+    ///
+    /// Returns the register addresses that were written, in order.
+    pub(crate) fn write(&mut self, data: &[u8]) -> core::ops::Range<usize> {
+        let Some((&reg, values)) = data.split_first() else {
+            return 0..0;
+        };
+        let start = reg as usize;
+        for (i, &value) in values.iter().enumerate() {
+            if let Some(slot) = self.regs.get_mut(start + i) {
+                *slot = value;
+            }
+        }
+        self.pointer = start + values.len();
+        start..start + values.len()
+    }
+}
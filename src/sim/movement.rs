@@ -0,0 +1,159 @@
+//! Simulated Modulino Movement (LSM6DSOX).
+
+use super::{Registers, SimDevice};
+use crate::addresses;
+use crate::movement::{
+    ACCEL_SENSITIVITY, GYRO_SENSITIVITY, LSM6DSOX_CTRL1_XL, LSM6DSOX_CTRL2_G, LSM6DSOX_CTRL3_C,
+    LSM6DSOX_OUTX_L_A, LSM6DSOX_OUTX_L_G, LSM6DSOX_STATUS_REG, LSM6DSOX_WHO_AM_I,
+    LSM6DSOX_WHO_AM_I_VALUE,
+};
+
+/// CTRL3_C bits.
+const CTRL3_SW_RESET: u8 = 0x01;
+const CTRL3_IF_INC: u8 = 0x04;
+
+/// Simulated LSM6DSOX accelerometer and gyroscope.
+///
+/// Output registers hold the simulated motion while the corresponding sensor
+/// has a non-zero output data rate, and zero while it is powered down.
+/// STATUS_REG reports XLDA/GDA for powered sensors; reading an output
+/// register clears its flag. Writing SW_RESET to CTRL3_C restores the
+/// power-on register values.
+pub struct Movement {
+    address: u8,
+    registers: Registers<0x80>,
+    acceleration: [i16; 3],
+    angular_velocity: [i16; 3],
+}
+
+impl Movement {
+    /// Create a sensor at the default address, lying flat and still.
+    pub fn new() -> Self {
+        Self::with_address(addresses::MOVEMENT[0])
+    }
+
+    /// Create a sensor at a custom address.
+    pub fn with_address(address: u8) -> Self {
+        let mut sensor = Self {
+            address,
+            registers: Registers::new(),
+            acceleration: [0, 0, 0],
+            angular_velocity: [0, 0, 0],
+        };
+        sensor.reset();
+        sensor.set_acceleration(0.0, 0.0, 1.0);
+        sensor
+    }
+
+    /// Set the simulated acceleration in g (±2 g at the default full scale).
+    pub fn set_acceleration(&mut self, x: f32, y: f32, z: f32) {
+        self.acceleration = [x, y, z].map(|g| to_raw(g, ACCEL_SENSITIVITY));
+        self.registers.regs[LSM6DSOX_STATUS_REG as usize] |= 0x01;
+    }
+
+    /// Set the simulated angular velocity in dps (±250 dps at the default full scale).
+    pub fn set_angular_velocity(&mut self, x: f32, y: f32, z: f32) {
+        self.angular_velocity = [x, y, z].map(|dps| to_raw(dps, GYRO_SENSITIVITY));
+        self.registers.regs[LSM6DSOX_STATUS_REG as usize] |= 0x02;
+    }
+
+    /// The value of a register, as last written by the host.
+    pub fn register(&self, reg: u8) -> u8 {
+        self.registers.regs.get(reg as usize).copied().unwrap_or(0)
+    }
+
+    fn reset(&mut self) {
+        self.registers = Registers::new();
+        self.registers.regs[LSM6DSOX_WHO_AM_I as usize] = LSM6DSOX_WHO_AM_I_VALUE;
+        self.registers.regs[LSM6DSOX_CTRL3_C as usize] = CTRL3_IF_INC;
+        self.registers.regs[LSM6DSOX_STATUS_REG as usize] = 0x03;
+    }
+
+    fn accel_enabled(&self) -> bool {
+        self.registers.regs[LSM6DSOX_CTRL1_XL as usize] & 0xF0 != 0
+    }
+
+    fn gyro_enabled(&self) -> bool {
+        self.registers.regs[LSM6DSOX_CTRL2_G as usize] & 0xF0 != 0
+    }
+
+    fn read_reg(&mut self, reg: usize) -> u8 {
+        let gyro = LSM6DSOX_OUTX_L_G as usize;
+        let accel = LSM6DSOX_OUTX_L_A as usize;
+        match reg {
+            r if (gyro..gyro + 6).contains(&r) => {
+                self.registers.regs[LSM6DSOX_STATUS_REG as usize] &= !0x02;
+                if !self.gyro_enabled() {
+                    return 0;
+                }
+                axis_byte(&self.angular_velocity, r - gyro)
+            }
+            r if (accel..accel + 6).contains(&r) => {
+                self.registers.regs[LSM6DSOX_STATUS_REG as usize] &= !0x01;
+                if !self.accel_enabled() {
+                    return 0;
+                }
+                axis_byte(&self.acceleration, r - accel)
+            }
+            r if r == LSM6DSOX_STATUS_REG as usize => {
+                let mut status = self.registers.regs[r];
+                if !self.accel_enabled() {
+                    status &= !0x01;
+                }
+                if !self.gyro_enabled() {
+                    status &= !0x02;
+                }
+                status
+            }
+            r => self.registers.regs.get(r).copied().unwrap_or(0),
+        }
+    }
+}
+
+/// Convert a physical value to raw counts for a sensitivity in milli-units per LSB.
+fn to_raw(value: f32, sensitivity: f32) -> i16 {
+    let raw = libm::roundf(value * 1000.0 / sensitivity);
+    raw.clamp(i16::MIN as f32, i16::MAX as f32) as i16
+}
+
+/// Byte `offset` of three little-endian axes.
+fn axis_byte(axes: &[i16; 3], offset: usize) -> u8 {
+    axes[offset / 2].to_le_bytes()[offset % 2]
+}
+
+impl Default for Movement {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimDevice for Movement {
+    fn acknowledges(&self, address: u8) -> bool {
+        address == self.address
+    }
+
+    fn write(&mut self, _address: u8, data: &[u8]) {
+        let who_am_i = self.registers.regs[LSM6DSOX_WHO_AM_I as usize];
+        let written = self.registers.write(data);
+        self.registers.regs[LSM6DSOX_WHO_AM_I as usize] = who_am_i;
+
+        if written.contains(&(LSM6DSOX_CTRL3_C as usize))
+            && self.registers.regs[LSM6DSOX_CTRL3_C as usize] & CTRL3_SW_RESET != 0
+        {
+            // SW_RESET self-clears once the reset completes
+            self.reset();
+        }
+    }
+
+    fn read(&mut self, _address: u8, buf: &mut [u8]) {
+        let auto_increment = self.registers.regs[LSM6DSOX_CTRL3_C as usize] & CTRL3_IF_INC != 0;
+        let start = self.registers.pointer;
+        for (i, byte) in buf.iter_mut().enumerate() {
+            let reg = if auto_increment { start + i } else { start };
+            *byte = self.read_reg(reg);
+        }
+        if auto_increment {
+            self.registers.pointer = start + buf.len();
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Simulated Modulino Opto Relay.
+
+use super::{firmware_read, SimDevice};
+use crate::addresses;
+
+/// Simulated Modulino Opto Relay firmware.
+///
+/// Writing `1` closes the relay and `0` opens it; reads report the state.
+pub struct OptoRelay {
+    address: u8,
+    on: bool,
+}
+
+impl OptoRelay {
+    /// Create a module at the default address.
+    pub fn new() -> Self {
+        Self::with_address(addresses::OPTO_RELAY)
+    }
+
+    /// Create a module at a custom address.
+    pub fn with_address(address: u8) -> Self {
+        Self { address, on: false }
+    }
+
+    /// Check if the relay is closed.
+    pub fn is_on(&self) -> bool {
+        self.on
+    }
+}
+
+impl Default for OptoRelay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimDevice for OptoRelay {
+    fn acknowledges(&self, address: u8) -> bool {
+        address == self.address
+    }
+
+    fn write(&mut self, _address: u8, data: &[u8]) {
+        if let Some(&command) = data.first() {
+            self.on = command != 0;
+        }
+    }
+
+    fn read(&mut self, _address: u8, buf: &mut [u8]) {
+        firmware_read(self.address, &[self.on as u8], buf);
+    }
+}
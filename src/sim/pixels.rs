@@ -0,0 +1,78 @@
+//! Simulated Modulino Pixels.
+
+use super::{firmware_read, SimDevice};
+use crate::pixels::NUM_LEDS;
+use crate::{addresses, Color};
+
+/// Simulated Modulino Pixels firmware.
+///
+/// Writes carry one APA102 frame (`[0xE0 | brightness, b, g, r]`) per LED.
+pub struct Pixels {
+    address: u8,
+    frame: [[u8; 4]; NUM_LEDS],
+}
+
+impl Pixels {
+    /// Create a module at the default address.
+    pub fn new() -> Self {
+        Self::with_address(addresses::PIXELS)
+    }
+
+    /// Create a module at a custom address.
+    pub fn with_address(address: u8) -> Self {
+        Self {
+            address,
+            frame: [[0xE0, 0, 0, 0]; NUM_LEDS],
+        }
+    }
+
+    /// The color shown by an LED.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than 8.
+    pub fn color(&self, index: usize) -> Color {
+        let [_, b, g, r] = self.frame[index];
+        Color::new(r, g, b)
+    }
+
+    /// The 5-bit APA102 brightness (0-31) of an LED.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than 8.
+    pub fn brightness(&self, index: usize) -> u8 {
+        self.frame[index][0] & 0x1F
+    }
+
+    /// Check if an LED is lit (non-zero brightness and color).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than 8.
+    pub fn is_lit(&self, index: usize) -> bool {
+        self.brightness(index) != 0 && self.color(index) != Color::BLACK
+    }
+}
+
+impl Default for Pixels {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimDevice for Pixels {
+    fn acknowledges(&self, address: u8) -> bool {
+        address == self.address
+    }
+
+    fn write(&mut self, _address: u8, data: &[u8]) {
+        for (led, chunk) in self.frame.iter_mut().zip(data.chunks_exact(4)) {
+            led.copy_from_slice(chunk);
+        }
+    }
+
+    fn read(&mut self, _address: u8, buf: &mut [u8]) {
+        firmware_read(self.address, &[], buf);
+    }
+}
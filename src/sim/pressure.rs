@@ -0,0 +1,106 @@
+//! Simulated Modulino Pressure (LPS22HB).
+
+use super::{Registers, SimDevice};
+use crate::addresses;
+use crate::pressure::{REG_CTRL_REG2, REG_OUT_P_XL, REG_OUT_T_L, REG_WHO_AM_I, WHO_AM_I_VALUE};
+
+const REG_STATUS: usize = 0x27;
+
+/// Simulated LPS22HB barometer.
+///
+/// Registers auto-increment on multi-byte access. Writing SWRESET to
+/// CTRL_REG2 restores the power-on register values.
+pub struct Pressure {
+    registers: Registers<0x40>,
+    pressure: f32,
+    temperature: f32,
+}
+
+impl Pressure {
+    /// Create a sensor reading 1013.25 hPa and 25°C.
+    pub fn new() -> Self {
+        let mut sensor = Self {
+            registers: Registers::new(),
+            pressure: 1013.25,
+            temperature: 25.0,
+        };
+        sensor.reset();
+        sensor
+    }
+
+    /// Set the simulated pressure in hPa (260 to 1260).
+    pub fn set_pressure(&mut self, hpa: f32) {
+        self.pressure = hpa;
+        self.registers.regs[REG_STATUS] |= 0x01;
+    }
+
+    /// Set the simulated temperature in degrees Celsius.
+    pub fn set_temperature(&mut self, temperature: f32) {
+        self.temperature = temperature;
+        self.registers.regs[REG_STATUS] |= 0x02;
+    }
+
+    /// The value of a register, as last written by the host.
+    pub fn register(&self, reg: u8) -> u8 {
+        self.registers.regs.get(reg as usize).copied().unwrap_or(0)
+    }
+
+    fn reset(&mut self) {
+        self.registers = Registers::new();
+        self.registers.regs[REG_WHO_AM_I as usize] = WHO_AM_I_VALUE;
+        // CTRL_REG2: IF_ADD_INC
+        self.registers.regs[REG_CTRL_REG2 as usize] = 0x10;
+        self.registers.regs[REG_STATUS] = 0x03;
+    }
+
+    fn read_reg(&mut self, reg: usize) -> u8 {
+        let pressure = (self.pressure * 4096.0) as u32;
+        let temperature = (self.temperature * 100.0) as i16 as u16;
+        let p_base = REG_OUT_P_XL as usize;
+        let t_base = REG_OUT_T_L as usize;
+        match reg {
+            r if (p_base..p_base + 3).contains(&r) => {
+                self.registers.regs[REG_STATUS] &= !0x01;
+                pressure.to_le_bytes()[r - p_base]
+            }
+            r if (t_base..t_base + 2).contains(&r) => {
+                self.registers.regs[REG_STATUS] &= !0x02;
+                temperature.to_le_bytes()[r - t_base]
+            }
+            r => self.registers.regs.get(r).copied().unwrap_or(0),
+        }
+    }
+}
+
+impl Default for Pressure {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimDevice for Pressure {
+    fn acknowledges(&self, address: u8) -> bool {
+        address == addresses::PRESSURE
+    }
+
+    fn write(&mut self, _address: u8, data: &[u8]) {
+        let written = self.registers.write(data);
+        if written.contains(&(REG_CTRL_REG2 as usize))
+            && self.registers.regs[REG_CTRL_REG2 as usize] & 0x04 != 0
+        {
+            self.reset();
+        }
+    }
+
+    fn read(&mut self, _address: u8, buf: &mut [u8]) {
+        let auto_increment = self.registers.regs[REG_CTRL_REG2 as usize] & 0x10 != 0;
+        let start = self.registers.pointer;
+        for (i, byte) in buf.iter_mut().enumerate() {
+            let reg = if auto_increment { start + i } else { start };
+            *byte = self.read_reg(reg);
+        }
+        if auto_increment {
+            self.registers.pointer = start + buf.len();
+        }
+    }
+}
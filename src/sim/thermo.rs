@@ -0,0 +1,76 @@
+//! Simulated Modulino Thermo (HS3003).
+
+use super::SimDevice;
+use crate::addresses;
+
+/// Simulated HS3003 temperature and humidity sensor.
+///
+/// Any write is a measurement request. A read returns humidity (14 bits, with
+/// the two status bits on top) and temperature (14 bits, left-aligned). The
+/// status bits read `01` (stale) if no measurement was requested since the
+/// last read.
+pub struct Thermo {
+    temperature: f32,
+    humidity: f32,
+    fresh: bool,
+}
+
+impl Thermo {
+    /// Create a sensor reading 25°C and 50% relative humidity.
+    pub fn new() -> Self {
+        Self {
+            temperature: 25.0,
+            humidity: 50.0,
+            fresh: false,
+        }
+    }
+
+    /// Set the simulated temperature in degrees Celsius (-40 to 125).
+    pub fn set_temperature(&mut self, temperature: f32) {
+        self.temperature = temperature;
+    }
+
+    /// Set the simulated relative humidity in percent (0 to 100).
+    pub fn set_humidity(&mut self, humidity: f32) {
+        self.humidity = humidity;
+    }
+
+    fn raw(&self) -> [u8; 4] {
+        let humidity = scale(self.humidity / 100.0);
+        let temperature = scale((self.temperature + 40.0) / 165.0) << 2;
+        let status = if self.fresh { 0x00 } else { 0x40 };
+        let h = humidity.to_be_bytes();
+        let t = temperature.to_be_bytes();
+        [h[0] | status, h[1], t[0], t[1]]
+    }
+}
+
+/// Map a 0.0-1.0 fraction onto the 14-bit range.
+fn scale(fraction: f32) -> u16 {
+    let clamped = fraction.clamp(0.0, 1.0);
+    libm::roundf(clamped * 16383.0) as u16
+}
+
+impl Default for Thermo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimDevice for Thermo {
+    fn acknowledges(&self, address: u8) -> bool {
+        address == addresses::THERMO
+    }
+
+    fn write(&mut self, _address: u8, _data: &[u8]) {
+        self.fresh = true;
+    }
+
+    fn read(&mut self, _address: u8, buf: &mut [u8]) {
+        let raw = self.raw();
+        buf.fill(0);
+        let len = buf.len().min(raw.len());
+        buf[..len].copy_from_slice(&raw[..len]);
+        self.fresh = false;
+    }
+}
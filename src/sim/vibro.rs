@@ -0,0 +1,78 @@
+//! Simulated Modulino Vibro.
+
+use super::{firmware_read, SimDevice};
+use crate::addresses;
+
+/// Simulated Modulino Vibro firmware.
+///
+/// Writes carry the PWM frequency, duration and power as three little-endian
+/// `u32`s. A power of 0 stops the motor.
+pub struct Vibro {
+    address: u8,
+    frequency: u32,
+    duration_ms: u32,
+    power: u32,
+}
+
+impl Vibro {
+    /// Create a module at the default address.
+    pub fn new() -> Self {
+        Self::with_address(addresses::VIBRO)
+    }
+
+    /// Create a module at a custom address.
+    pub fn with_address(address: u8) -> Self {
+        Self {
+            address,
+            frequency: 0,
+            duration_ms: 0,
+            power: 0,
+        }
+    }
+
+    /// The PWM frequency last requested by the host, in Hz.
+    pub fn frequency(&self) -> u32 {
+        self.frequency
+    }
+
+    /// The duration last requested by the host, in milliseconds.
+    pub fn duration_ms(&self) -> u32 {
+        self.duration_ms
+    }
+
+    /// The power last requested by the host (0-100).
+    pub fn power(&self) -> u32 {
+        self.power
+    }
+
+    /// Check if the motor is running.
+    pub fn is_on(&self) -> bool {
+        self.power != 0
+    }
+}
+
+impl Default for Vibro {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimDevice for Vibro {
+    fn acknowledges(&self, address: u8) -> bool {
+        address == self.address
+    }
+
+    fn write(&mut self, _address: u8, data: &[u8]) {
+        if let Some(bytes) = data.get(0..12) {
+            let word =
+                |i: usize| u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+            self.frequency = word(0);
+            self.duration_ms = word(4);
+            self.power = word(8);
+        }
+    }
+
+    fn read(&mut self, _address: u8, buf: &mut [u8]) {
+        firmware_read(self.address, &[], buf);
+    }
+}
@@ -11,7 +11,11 @@ use embedded_hal::i2c::I2c;
 pub use hs3003::Error as Hs3003Error;
 use hs3003::{Hs3003, Measurement};
 
-use crate::{addresses, Error, Result};
+use crate::{addresses, Error, Modulino, ModulinoKind, Result};
+
+/// Time the HS3003 needs to complete a measurement (matches the `hs3003` crate).
+#[cfg(feature = "async")]
+pub(crate) const MEASUREMENT_TIME_MS: u32 = 100;
 
 /// Temperature and humidity measurement.
 ///
@@ -41,6 +45,21 @@ impl ThermoMeasurement {
             && self.humidity >= 0.0
             && self.humidity <= 100.0
     }
+
+    /// Decode a raw 4-byte HS3003 read.
+    ///
+    /// Humidity is the lower 14 bits of bytes 0-1 (top 2 bits are status),
+    /// temperature is the upper 14 bits of bytes 2-3.
+    #[cfg(feature = "async")]
+    pub(crate) fn from_bytes(data: &[u8; 4]) -> Self {
+        let humidity_raw = u16::from_be_bytes([data[0] & 0x3F, data[1]]);
+        let humidity = (f32::from(humidity_raw) / 16383.0) * 100.0;
+
+        let temp_raw = u16::from_be_bytes([data[2], data[3]]) >> 2;
+        let temperature = ((f32::from(temp_raw) / 16383.0) * 165.0) - 40.0;
+
+        Self::new(temperature, humidity)
+    }
 }
 
 impl From<Measurement> for ThermoMeasurement {
@@ -93,15 +112,7 @@ where
     /// > [!WARNING]
     /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
     pub fn discover(i2c: &mut I2C) -> Result<u8, E> {
-        let addresses = [addresses::THERMO];
-        for &addr in &addresses {
-            if i2c.write(addr, &[]).is_ok() {
-                return Ok(addr);
-            }
-        }
-        i2c.write(addresses[0], &[])
-            .map(|_| addresses[0])
-            .map_err(Error::I2c)
+        <Self as Modulino<I2C>>::probe(i2c)
     }
 
     /// Get the I2C address.
@@ -161,3 +172,25 @@ where
         &mut self.sensor
     }
 }
+
+impl<I2C: I2c> Modulino<I2C> for Thermo<I2C> {
+    const KIND: ModulinoKind = ModulinoKind::Thermo;
+    const DEFAULT_ADDRESSES: &'static [u8] = &[addresses::THERMO];
+    const PINSTRAP: &'static [u8] = &[];
+
+    fn new_with_address(i2c: I2C, address: u8) -> Result<Self, I2C::Error> {
+        // The HS3003 address is fixed in hardware
+        if address != addresses::THERMO {
+            return Err(Error::InvalidAddress);
+        }
+        Ok(Self::new(i2c))
+    }
+
+    fn address(&self) -> u8 {
+        addresses::THERMO
+    }
+
+    fn release(self) -> I2C {
+        self.sensor.destroy()
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! The Modulino Vibro module contains a vibration motor.
 
-use crate::{addresses, Error, I2cDevice, Result};
+use crate::{addresses, pinstrap, I2cDevice, Modulino, ModulinoKind, Result};
 use embedded_hal::i2c::I2c;
 
 /// Predefined power levels for the vibration motor.
@@ -82,15 +82,7 @@ where
     /// > [!WARNING]
     /// > **EXPERIMENTAL**: This feature is a work-in-progress and has NOT yet been tested on physical hardware.
     pub fn discover(i2c: &mut I2C) -> Result<u8, E> {
-        let addresses = [addresses::VIBRO];
-        for &addr in &addresses {
-            if i2c.write(addr, &[]).is_ok() {
-                return Ok(addr);
-            }
-        }
-        i2c.write(addresses[0], &[])
-            .map(|_| addresses[0])
-            .map_err(Error::I2c)
+        <Self as Modulino<I2C>>::probe(i2c)
     }
 
     /// Create a new Vibro instance with a custom address.
@@ -138,25 +130,7 @@ where
     /// * `duration_ms` - Duration in milliseconds (0xFFFF for indefinite)
     /// * `power` - Power level (0-100)
     pub fn on_with_power(&mut self, duration_ms: u16, power: u8) -> Result<(), E> {
-        let freq_bytes = self.frequency.to_le_bytes();
-        let duration_bytes = (duration_ms as u32).to_le_bytes();
-        let power_bytes = (power as u32).to_le_bytes();
-
-        let data = [
-            freq_bytes[0],
-            freq_bytes[1],
-            freq_bytes[2],
-            freq_bytes[3],
-            duration_bytes[0],
-            duration_bytes[1],
-            duration_bytes[2],
-            duration_bytes[3],
-            power_bytes[0],
-            power_bytes[1],
-            power_bytes[2],
-            power_bytes[3],
-        ];
-
+        let data = on_payload(self.frequency, duration_ms, power);
         self.device.write(&data)?;
         Ok(())
     }
@@ -193,3 +167,43 @@ where
         self.device.release()
     }
 }
+
+impl<I2C: I2c> Modulino<I2C> for Vibro<I2C> {
+    const KIND: ModulinoKind = ModulinoKind::Vibro;
+    const DEFAULT_ADDRESSES: &'static [u8] = &[addresses::VIBRO];
+    const PINSTRAP: &'static [u8] = &[pinstrap::VIBRO];
+
+    fn new_with_address(i2c: I2C, address: u8) -> Result<Self, I2C::Error> {
+        Self::new_with_address(i2c, address)
+    }
+
+    fn address(&self) -> u8 {
+        self.device.address
+    }
+
+    fn release(self) -> I2C {
+        self.device.release()
+    }
+}
+
+/// Build the 12-byte payload (frequency, duration and power, all little-endian u32).
+pub(crate) fn on_payload(frequency: u32, duration_ms: u16, power: u8) -> [u8; 12] {
+    let freq_bytes = frequency.to_le_bytes();
+    let duration_bytes = (duration_ms as u32).to_le_bytes();
+    let power_bytes = (power as u32).to_le_bytes();
+
+    [
+        freq_bytes[0],
+        freq_bytes[1],
+        freq_bytes[2],
+        freq_bytes[3],
+        duration_bytes[0],
+        duration_bytes[1],
+        duration_bytes[2],
+        duration_bytes[3],
+        power_bytes[0],
+        power_bytes[1],
+        power_bytes[2],
+        power_bytes[3],
+    ]
+}
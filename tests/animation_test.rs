@@ -0,0 +1,133 @@
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+use modulino::{Animation, AnimationKind, Color, Direction, Palette, Pixels};
+
+const W: Color = Color::WHITE;
+const K: Color = Color::BLACK;
+
+/// Frames at consecutive steps, starting at time 0.
+fn frames(mut animation: Animation, count: u32) -> Vec<[Color; 8]> {
+    let step = animation.speed() as u32;
+    (0..count)
+        .map(|i| animation.poll(i * step).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_palette_sampling() {
+    let palette = Palette::new(&[Color::BLACK, Color::WHITE]);
+    assert_eq!(palette.len(), 2);
+    assert_eq!(palette.sample(0), Color::BLACK);
+    assert_eq!(palette.sample(255), Color::WHITE);
+    assert_eq!(palette.sample(128), Color::new(128, 128, 128));
+    assert_eq!(Palette::RAINBOW.sample(0), Color::RED);
+    assert_eq!(Palette::RAINBOW.sample(85), Color::GREEN);
+    assert_eq!(Palette::RAINBOW.color(8), Color::YELLOW);
+
+    assert_eq!(Palette::solid(Color::BLUE).sample(200), Color::BLUE);
+    assert!(Palette::new(&[]).is_empty());
+    assert_eq!(Palette::new(&[]).sample(10), Color::BLACK);
+}
+
+#[test]
+fn test_frames_follow_time() {
+    let mut rainbow = Animation::new(AnimationKind::Rainbow).with_speed(10);
+    let first = rainbow.poll(1000).unwrap();
+    assert_eq!(first[0], Color::RED);
+    assert!(rainbow.poll(1009).is_none());
+    assert!(rainbow.poll(1010).is_some());
+    // Late calls skip steps
+    let late = rainbow.poll(1100).unwrap();
+    assert_eq!(late[0], Palette::RAINBOW.sample(10));
+
+    rainbow.restart();
+    assert_eq!(rainbow.poll(5000).unwrap(), first);
+}
+
+#[test]
+fn test_step_counter_wraps_without_overflow() {
+    let mut rainbow = Animation::new(AnimationKind::Rainbow).with_speed(1);
+    rainbow.poll(0);
+    let frame = rainbow.poll(u32::MAX).unwrap();
+    assert_eq!(frame[0], Palette::RAINBOW.sample(255));
+    assert_eq!(frame[1], Palette::RAINBOW.sample(31));
+
+    let mut chase = Animation::new(AnimationKind::TheaterChase).with_speed(1);
+    chase.poll(0);
+    let frame = chase.poll(u32::MAX).unwrap();
+    assert_eq!(frame[0], Palette::RAINBOW.sample(248));
+}
+
+#[test]
+fn test_theater_chase_and_direction() {
+    let chase = Animation::new(AnimationKind::TheaterChase).with_palette(W);
+    let f = frames(chase, 2);
+    assert_eq!(f[0], [W, K, K, W, K, K, W, K]);
+    assert_eq!(f[1], [K, W, K, K, W, K, K, W]);
+
+    let reverse = Animation::new(AnimationKind::TheaterChase)
+        .with_palette(W)
+        .with_direction(Direction::Reverse);
+    assert_eq!(frames(reverse, 2)[1], [W, K, K, W, K, K, W, K]);
+}
+
+#[test]
+fn test_comet_and_scanner() {
+    let comet = Animation::new(AnimationKind::Comet).with_palette(W);
+    let f = frames(comet, 3);
+    assert_eq!(f[0], [W, K, K, K, K, K, K, K]);
+    assert_eq!(f[2], [W.scale(15), W.scale(63), W, K, K, K, K, K]);
+
+    let scanner = Animation::new(AnimationKind::Scanner).with_palette(W);
+    let heads: Vec<usize> = frames(scanner, 16)
+        .iter()
+        .map(|frame| frame.iter().position(|&c| c == W).unwrap())
+        .collect();
+    assert_eq!(heads, vec![0, 1, 2, 3, 4, 5, 6, 7, 6, 5, 4, 3, 2, 1, 0, 1]);
+}
+
+#[test]
+fn test_breathe_and_color_wipe() {
+    let breathe = Animation::new(AnimationKind::Breathe).with_palette(Color::RED);
+    let f = frames(breathe, Animation::BREATH_STEPS / 2 + 1);
+    assert_eq!(f[0], [K; 8]);
+    assert_eq!(f[Animation::BREATH_STEPS as usize / 2], [Color::RED; 8]);
+
+    let wipe = Animation::new(AnimationKind::ColorWipe).with_palette(W);
+    let f = frames(wipe, 17);
+    assert_eq!(f[0], [W, K, K, K, K, K, K, K]);
+    assert_eq!(f[7], [W; 8]);
+    // Then black wipes over white, and white again
+    assert_eq!(f[9], [K, K, W, W, W, W, W, W]);
+    assert_eq!(f[16], [W, K, K, K, K, K, K, K]);
+}
+
+#[test]
+fn test_random_effects_are_seeded() {
+    for kind in [AnimationKind::Twinkle, AnimationKind::Fire] {
+        let a = frames(Animation::new(kind).with_seed(42), 50);
+        let b = frames(Animation::new(kind).with_seed(42), 50);
+        let c = frames(Animation::new(kind).with_seed(7), 50);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(a.iter().flatten().any(|&color| color != K));
+    }
+}
+
+#[test]
+fn test_tick_shows_only_new_frames() {
+    let addr = 0x36;
+    let mut data = Vec::new();
+    data.extend_from_slice(&[0xE3, 0xFF, 0xFF, 0xFF]);
+    for _ in 1..8 {
+        data.extend_from_slice(&[0xE3, 0x00, 0x00, 0x00]);
+    }
+    let expectations = [I2cTransaction::write(addr, data)];
+    let mut pixels = Pixels::new(I2cMock::new(&expectations)).unwrap();
+
+    let mut comet = Animation::new(AnimationKind::Comet)
+        .with_palette(W)
+        .with_brightness(10);
+    assert!(comet.tick(&mut pixels, 0).unwrap());
+    assert!(!comet.tick(&mut pixels, 79).unwrap());
+    pixels.release().done();
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "async")]
+
+use embassy_futures::block_on;
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::digital::{
+    Mock as PinMock, State as PinState, Transaction as PinTransaction,
+};
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+use modulino::asynch::{Buttons, Distance, Pixels, Thermo};
+use modulino::{Color, DetectionMode};
+
+#[test]
+fn test_async_buttons_and_leds() {
+    let addr = 0x3E;
+    let expectations = [
+        I2cTransaction::read(addr, vec![0x7C, 0x00, 0x01, 0x00]),
+        I2cTransaction::write(addr, vec![0x00, 0x01, 0x00]),
+    ];
+    block_on(async {
+        let mut buttons = Buttons::new(I2cMock::new(&expectations)).await.unwrap();
+        assert!(buttons.button_b_pressed());
+        buttons.set_leds(false, true, false).await.unwrap();
+        buttons.release().done();
+    });
+}
+
+#[test]
+fn test_async_pixels_show() {
+    let addr = 0x36;
+    let mut expected = [0xE0, 0, 0, 0].repeat(8);
+    // Red at brightness 100 -> 0xFF global byte, then B, G, R
+    expected[0..4].copy_from_slice(&[0xFF, 0x00, 0x00, 0xFF]);
+    let expectations = [I2cTransaction::write(addr, expected)];
+    block_on(async {
+        let mut pixels = Pixels::new(I2cMock::new(&expectations)).unwrap();
+        pixels.set_color(0, Color::RED, 100).unwrap();
+        pixels.show().await.unwrap();
+        pixels.release().done();
+    });
+}
+
+#[test]
+fn test_async_thermo_read() {
+    let addr = 0x44;
+    let expectations = [
+        I2cTransaction::write(addr, vec![0x00]),
+        // Humidity raw 0x1FFF (~50 %), temperature raw 6454 << 2 (~25 °C)
+        I2cTransaction::read(addr, vec![0x1F, 0xFF, 0x64, 0xD8]),
+    ];
+    block_on(async {
+        let mut thermo = Thermo::new(I2cMock::new(&expectations));
+        let m = thermo.read(&mut NoopDelay::new()).await.unwrap();
+        assert!((m.humidity - 50.0).abs() < 0.1);
+        assert!((m.temperature - 25.0).abs() < 0.1);
+        thermo.release().done();
+    });
+}
+
+#[test]
+fn test_async_distance_read() {
+    let addr = 0x29;
+    let expectations = [
+        I2cTransaction::write_read(addr, vec![0x00, 0x89], vec![0x09]),
+        I2cTransaction::write_read(addr, vec![0x00, 0x96], vec![0x01, 0x2C]),
+        I2cTransaction::write(addr, vec![0x00, 0x86, 0x01]),
+    ];
+    block_on(async {
+        let mut distance = Distance::new(I2cMock::new(&expectations));
+        assert_eq!(distance.read_distance().await.unwrap(), Some(300));
+        distance.release().done();
+    });
+}
+
+#[test]
+fn test_async_distance_wait_for_interrupt() {
+    let addr = 0x29;
+    let expectations = [
+        I2cTransaction::write(addr, vec![0x00, 0x46, 0x00]),
+        I2cTransaction::write(addr, vec![0x00, 0x72, 0x00, 0x00]),
+        I2cTransaction::write(addr, vec![0x00, 0x74, 0x00, 0xC8]),
+    ];
+    block_on(async {
+        let mut distance = Distance::new(I2cMock::new(&expectations));
+        let mut gpio1 = PinMock::new(&[PinTransaction::wait_for_state(PinState::Low)]);
+        distance
+            .set_detection_thresholds(200, 0, DetectionMode::Below)
+            .await
+            .unwrap();
+        distance.wait_for_interrupt(&mut gpio1).await.unwrap();
+        gpio1.done();
+        distance.release().done();
+    });
+}
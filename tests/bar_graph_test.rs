@@ -0,0 +1,131 @@
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+use modulino::{BarGraph, BarStyle, Color, Palette, PixelStrip, Pixels};
+
+const W: Color = Color::WHITE;
+const K: Color = Color::BLACK;
+const HALF: Color = Color::new(128, 128, 128);
+
+#[test]
+fn test_bar_with_anti_aliasing() {
+    let mut bar = BarGraph::new(0.0, 8.0).with_palette(W);
+    assert_eq!(bar.style(), BarStyle::Bar);
+    assert_eq!(bar.render(3.5, 0), [W, W, W, HALF, K, K, K, K]);
+    assert_eq!(bar.render(0.0, 0), [K; 8]);
+    // Values outside the range are clamped
+    assert_eq!(bar.render(-5.0, 0), [K; 8]);
+    assert_eq!(bar.render(100.0, 0), [W; 8]);
+
+    let mut stepped = BarGraph::new(0.0, 8.0)
+        .with_palette(W)
+        .with_anti_aliasing(false);
+    assert_eq!(stepped.render(3.5, 0), [W, W, W, W, K, K, K, K]);
+    assert_eq!(stepped.render(3.4, 0), [W, W, W, K, K, K, K, K]);
+
+    let mut on_blue = BarGraph::new(0.0, 8.0)
+        .with_palette(W)
+        .with_background(Color::BLUE);
+    assert_eq!(on_blue.render(7.0, 0)[7], Color::BLUE);
+}
+
+#[test]
+fn test_gradient() {
+    let mut bar = BarGraph::new(0.0, 1.0);
+    let frame = bar.render(1.0, 0);
+    assert_eq!(frame[0], Color::GREEN);
+    assert_eq!(frame[7], Color::RED);
+    assert_eq!(frame[4], Palette::LEVEL.sample(146));
+}
+
+#[test]
+fn test_gauge() {
+    let mut gauge = BarGraph::gauge(0.0, 7.0).with_palette(W);
+    assert_eq!(
+        gauge.render(2.25, 0),
+        [
+            K,
+            K,
+            Color::new(191, 191, 191),
+            Color::new(64, 64, 64),
+            K,
+            K,
+            K,
+            K
+        ]
+    );
+    assert_eq!(gauge.render(0.0, 0), [W, K, K, K, K, K, K, K]);
+    assert_eq!(gauge.render(7.0, 0), [K, K, K, K, K, K, K, W]);
+}
+
+#[test]
+fn test_center_zero() {
+    let mut x = BarGraph::center_zero(-4.0, 4.0).with_palette(W);
+    assert_eq!(x.render(0.0, 0), [K; 8]);
+    assert_eq!(x.render(2.0, 0), [K, K, K, K, W, W, K, K]);
+    assert_eq!(x.render(-1.0, 0), [K, K, K, W, K, K, K, K]);
+    assert_eq!(x.render(-4.0, 0), [W, W, W, W, K, K, K, K]);
+
+    // Asymmetric ranges such as a joystick axis scale each side separately
+    let mut joystick = BarGraph::center_zero(-128.0, 127.0).with_palette(W);
+    assert_eq!(joystick.render(127.0, 0), [K, K, K, K, W, W, W, W]);
+    assert_eq!(joystick.render(-128.0, 0), [W, W, W, W, K, K, K, K]);
+
+    // The gradient runs from the middle outwards
+    let mut gradient = BarGraph::center_zero(-4.0, 4.0);
+    let frame = gradient.render(-4.0, 0);
+    assert_eq!(frame[0], Color::RED);
+    assert_eq!(frame[3], Palette::LEVEL.sample(36));
+}
+
+#[test]
+fn test_peak_hold() {
+    let mut vu = BarGraph::vu_meter(0.0, 8.0).with_palette(W);
+    assert_eq!(vu.render(6.0, 0), [W, W, W, W, W, W, K, K]);
+    // The peak stays for a second
+    assert_eq!(vu.render(2.0, 500), [W, W, K, K, K, W, K, K]);
+    assert_eq!(vu.render(0.0, 999), [K, K, K, K, K, W, K, K]);
+    // Then follows the level down
+    assert_eq!(vu.render(2.0, 1000), [W, W, K, K, K, K, K, K]);
+    assert_eq!(vu.render(0.0, 1100), [K, W, K, K, K, K, K, K]);
+
+    vu.reset_peak();
+    assert_eq!(vu.render(0.0, 1200), [K; 8]);
+
+    let mut x = BarGraph::center_zero(-4.0, 4.0)
+        .with_palette(W)
+        .with_peak_hold(1000);
+    x.render(-3.0, 0);
+    assert_eq!(x.render(1.0, 10), [K, W, K, K, W, K, K, K]);
+}
+
+#[test]
+fn test_draw_writes_changes_only() {
+    let addr = 0x36;
+    let mut data = Vec::new();
+    for i in 0..8 {
+        let level = if i < 2 { 0xFF } else { 0x00 };
+        data.extend_from_slice(&[0xE3, level, level, level]);
+    }
+    let expectations = [I2cTransaction::write(addr, data.clone())];
+    let mut pixels = Pixels::new(I2cMock::new(&expectations)).unwrap();
+
+    let mut bar = BarGraph::new(0.0, 80.0).with_palette(W).with_brightness(10);
+    assert!(bar.draw(&mut pixels, 20.0, 0).unwrap());
+    assert!(!bar.draw(&mut pixels, 20.0, 10).unwrap());
+    pixels.release().done();
+
+    // A 16-LED bar across two modules
+    let expectations = [I2cTransaction::write(addr, data)];
+    let first = Pixels::new(I2cMock::new(&expectations)).unwrap();
+    let blank: Vec<u8> = [0xE3, 0, 0, 0].repeat(8);
+    let expectations = [I2cTransaction::write(0x37, blank)];
+    let second = Pixels::new_with_address(I2cMock::new(&expectations), 0x37).unwrap();
+    let mut strip = PixelStrip::new([first, second]);
+
+    let mut long = BarGraph::<16>::for_strip(0.0, 160.0)
+        .with_palette(W)
+        .with_brightness(10);
+    assert!(long.draw_strip(&mut strip, 20.0, 0).unwrap());
+    for pixels in strip.release() {
+        pixels.release().done();
+    }
+}
@@ -0,0 +1,189 @@
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+use modulino::{Button, ButtonEvent, ButtonEvents, ButtonState, ButtonTimings, Buttons};
+
+const UP: ButtonState = ButtonState {
+    a: false,
+    b: false,
+    c: false,
+};
+const A: ButtonState = ButtonState {
+    a: true,
+    b: false,
+    c: false,
+};
+const A_C: ButtonState = ButtonState {
+    a: true,
+    b: false,
+    c: true,
+};
+
+/// Feed `state` every 10 ms over `from..to` and collect all events.
+fn run(events: &mut ButtonEvents, state: ButtonState, from: u32, to: u32) -> Vec<ButtonEvent> {
+    (from..to)
+        .step_by(10)
+        .flat_map(|t| events.update(state, t).iter().collect::<Vec<_>>())
+        .collect()
+}
+
+#[test]
+fn test_debounce_ignores_glitches() {
+    let mut events = ButtonEvents::new();
+    assert!(events.update(A, 0).is_empty());
+    assert!(events.update(UP, 10).is_empty());
+    assert!(run(&mut events, UP, 20, 100).is_empty());
+
+    let got = run(&mut events, A, 100, 130);
+    assert_eq!(got, vec![ButtonEvent::Pressed(Button::A)]);
+    assert!(events.state().a);
+}
+
+#[test]
+fn test_click_is_reported_after_double_click_window() {
+    let mut events = ButtonEvents::new();
+    run(&mut events, A, 0, 100);
+    let got = run(&mut events, UP, 100, 400);
+    assert_eq!(got, vec![ButtonEvent::Released(Button::A)]);
+
+    let got = run(&mut events, UP, 400, 500);
+    assert_eq!(got, vec![ButtonEvent::Click(Button::A)]);
+}
+
+#[test]
+fn test_double_click() {
+    let mut events = ButtonEvents::new();
+    run(&mut events, A, 0, 100);
+    run(&mut events, UP, 100, 200);
+    run(&mut events, A, 200, 300);
+    let got = run(&mut events, UP, 300, 1000);
+    assert_eq!(
+        got,
+        vec![
+            ButtonEvent::Released(Button::A),
+            ButtonEvent::DoubleClick(Button::A)
+        ]
+    );
+}
+
+#[test]
+fn test_immediate_click_without_double_click_window() {
+    let mut events = ButtonEvents::with_timings(ButtonTimings {
+        double_click_ms: 0,
+        ..ButtonTimings::default()
+    });
+    run(&mut events, A, 0, 100);
+    let got = run(&mut events, UP, 100, 130);
+    assert_eq!(
+        got,
+        vec![
+            ButtonEvent::Released(Button::A),
+            ButtonEvent::Click(Button::A)
+        ]
+    );
+}
+
+#[test]
+fn test_long_press_and_held_repeats() {
+    let mut events = ButtonEvents::new();
+    let got = run(&mut events, A, 0, 1250);
+    assert_eq!(
+        got,
+        vec![
+            ButtonEvent::Pressed(Button::A),
+            ButtonEvent::LongPress(Button::A),
+            ButtonEvent::Held(Button::A),
+            ButtonEvent::Held(Button::A),
+        ]
+    );
+
+    // No click after a long press
+    let got = run(&mut events, UP, 1250, 2000);
+    assert_eq!(got, vec![ButtonEvent::Released(Button::A)]);
+}
+
+#[test]
+fn test_chord_suppresses_clicks() {
+    let mut events = ButtonEvents::new();
+    run(&mut events, A, 0, 50);
+    let got = run(&mut events, A_C, 50, 1500);
+    assert_eq!(
+        got,
+        vec![ButtonEvent::Pressed(Button::C), ButtonEvent::Chord(A_C)]
+    );
+
+    let got = run(&mut events, UP, 1500, 2000);
+    assert_eq!(
+        got,
+        vec![
+            ButtonEvent::Released(Button::A),
+            ButtonEvent::Released(Button::C)
+        ]
+    );
+}
+
+#[test]
+fn test_click_before_long_press_in_double_click_window() {
+    let mut events = ButtonEvents::new();
+    run(&mut events, A, 0, 100);
+    run(&mut events, UP, 100, 200);
+    let got = run(&mut events, A, 200, 1100);
+    assert_eq!(
+        got,
+        vec![
+            ButtonEvent::Pressed(Button::A),
+            ButtonEvent::Click(Button::A),
+            ButtonEvent::LongPress(Button::A),
+        ]
+    );
+
+    let got = run(&mut events, UP, 1100, 2000);
+    assert_eq!(got, vec![ButtonEvent::Released(Button::A)]);
+}
+
+#[test]
+fn test_click_before_chord_in_double_click_window() {
+    let mut events = ButtonEvents::new();
+    run(&mut events, A, 0, 100);
+    run(&mut events, UP, 100, 200);
+    let got = run(&mut events, A_C, 200, 300);
+    assert_eq!(
+        got,
+        vec![
+            ButtonEvent::Pressed(Button::A),
+            ButtonEvent::Pressed(Button::C),
+            ButtonEvent::Click(Button::A),
+            ButtonEvent::Chord(A_C),
+        ]
+    );
+}
+
+#[test]
+fn test_timestamps_wrap_around() {
+    let mut events = ButtonEvents::new();
+    let start = u32::MAX - 15;
+    events.update(A, start);
+    let got = events.update(A, start.wrapping_add(20));
+    assert!(got.contains(ButtonEvent::Pressed(Button::A)));
+}
+
+#[test]
+fn test_buttons_poll() {
+    let addr = 0x3E;
+    let expectations = [
+        I2cTransaction::read(addr, vec![0x7C, 0x00, 0x00, 0x00]),
+        I2cTransaction::read(addr, vec![0x7C, 0x00, 0x01, 0x00]),
+        I2cTransaction::read(addr, vec![0x7C, 0x00, 0x01, 0x00]),
+    ];
+    let mut buttons = Buttons::new(I2cMock::new(&expectations)).unwrap();
+    buttons.set_event_timings(ButtonTimings {
+        debounce_ms: 5,
+        ..ButtonTimings::default()
+    });
+
+    assert!(buttons.poll(0).unwrap().is_empty());
+    assert!(buttons
+        .poll(10)
+        .unwrap()
+        .contains(ButtonEvent::Pressed(Button::B)));
+    assert_eq!(buttons.event_timings().debounce_ms, 5);
+    buttons.release().done();
+}
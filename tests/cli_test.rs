@@ -0,0 +1,100 @@
+#![cfg(feature = "linux")]
+
+use std::process::{Command, Output};
+
+fn modulino(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_modulino"))
+        .arg("--sim")
+        .args(args)
+        .output()
+        .expect("failed to run modulino")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn test_cli_scan() {
+    let output = modulino(&["scan"]);
+    assert!(output.status.success());
+    let text = stdout(&output);
+    assert!(text.contains("0x3E  Buttons"));
+    assert!(text.contains("0x44  Thermo"));
+    assert!(text.contains("0x6A  Movement"));
+    assert_eq!(text.lines().count(), 14);
+}
+
+#[test]
+fn test_cli_read_sensors() {
+    let output = modulino(&["read", "thermo"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("temperature: 25.0 °C"));
+
+    let output = modulino(&["read", "distance"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("distance: 100 mm"));
+
+    let output = modulino(&["read", "movement"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("z=1.000 g"));
+
+    let output = modulino(&["read", "light"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("ir: 120"));
+
+    let output = modulino(&["read", "pressure"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("pressure: 1013.25 hPa"));
+}
+
+#[test]
+fn test_cli_outputs() {
+    let output = modulino(&["pixels", "3", "#00ff00", "80"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("LED 3 set to 00FF00 at 80%"));
+
+    let output = modulino(&["buzzer", "A4", "250"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("440 Hz for 250 ms"));
+
+    let output = modulino(&["buzzer", "C#5"]);
+    assert!(stdout(&output).contains("554 Hz"));
+
+    let output = modulino(&["matrix", "pixel", "11", "7"]);
+    assert!(output.status.success());
+
+    let output = modulino(&["relay", "latch", "toggle"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("latch relay: on"));
+
+    let output = modulino(&["relay", "opto", "on"]);
+    assert!(stdout(&output).contains("opto relay: on"));
+}
+
+#[test]
+fn test_cli_rejects_bad_arguments() {
+    for args in [
+        &["read", "humidity"][..],
+        &["pixels", "8", "red"],
+        &["buzzer", "H2"],
+        &["matrix", "pixel", "12", "0"],
+        &["matrix", "frame", "00ff"],
+        &["relay", "latch", "maybe"],
+        &[],
+    ] {
+        let output = modulino(args);
+        assert_eq!(output.status.code(), Some(2), "args: {:?}", args);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Usage:"));
+    }
+}
+
+#[test]
+fn test_cli_missing_bus() {
+    let output = Command::new(env!("CARGO_BIN_EXE_modulino"))
+        .args(["--bus", "/dev/i2c-does-not-exist", "scan"])
+        .output()
+        .expect("failed to run modulino");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot open"));
+}
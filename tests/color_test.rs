@@ -0,0 +1,84 @@
+use modulino::Color;
+
+fn close(actual: (f32, f32, f32), expected: (f32, f32, f32)) -> bool {
+    (actual.0 - expected.0).abs() < 0.5
+        && (actual.1 - expected.1).abs() < 0.5
+        && (actual.2 - expected.2).abs() < 0.5
+}
+
+#[test]
+fn test_from_hsv() {
+    assert_eq!(Color::from_hsv(0.0, 100.0, 100.0), Color::RED);
+    assert_eq!(Color::from_hsv(120.0, 100.0, 100.0), Color::GREEN);
+    assert_eq!(Color::from_hsv(240.0, 100.0, 100.0), Color::BLUE);
+    assert_eq!(Color::from_hsv(60.0, 100.0, 100.0), Color::YELLOW);
+    assert_eq!(Color::from_hsv(0.0, 0.0, 100.0), Color::WHITE);
+    assert_eq!(Color::from_hsv(200.0, 80.0, 0.0), Color::BLACK);
+    assert_eq!(Color::from_hsv(30.0, 100.0, 50.0), Color::new(128, 64, 0));
+
+    // Hue wraps, saturation and value are clamped
+    assert_eq!(Color::from_hsv(480.0, 100.0, 100.0), Color::GREEN);
+    assert_eq!(Color::from_hsv(-60.0, 150.0, 200.0), Color::MAGENTA);
+}
+
+#[test]
+fn test_to_hsv() {
+    assert!(close(Color::RED.to_hsv(), (0.0, 100.0, 100.0)));
+    assert!(close(Color::CYAN.to_hsv(), (180.0, 100.0, 100.0)));
+    assert!(close(Color::BLACK.to_hsv(), (0.0, 0.0, 0.0)));
+    assert!(close(Color::new(128, 64, 0).to_hsv(), (30.0, 100.0, 50.2)));
+
+    let orange = Color::ORANGE;
+    let (h, s, v) = orange.to_hsv();
+    assert_eq!(Color::from_hsv(h, s, v), orange);
+}
+
+#[test]
+fn test_hsl() {
+    assert_eq!(Color::from_hsl(0.0, 100.0, 50.0), Color::RED);
+    assert_eq!(Color::from_hsl(300.0, 100.0, 25.0), Color::new(128, 0, 128));
+    assert_eq!(Color::from_hsl(90.0, 0.0, 100.0), Color::WHITE);
+    assert!(close(Color::PURPLE.to_hsl(), (300.0, 100.0, 25.1)));
+    assert!(close(Color::WHITE.to_hsl(), (0.0, 0.0, 100.0)));
+
+    let (h, s, l) = Color::new(12, 200, 99).to_hsl();
+    assert_eq!(Color::from_hsl(h, s, l), Color::new(12, 200, 99));
+}
+
+#[test]
+fn test_lerp_and_blend() {
+    assert_eq!(Color::BLACK.lerp(Color::WHITE, 0.0), Color::BLACK);
+    assert_eq!(
+        Color::BLACK.lerp(Color::WHITE, 0.5),
+        Color::new(128, 128, 128)
+    );
+    assert_eq!(Color::RED.lerp(Color::BLUE, 1.0), Color::BLUE);
+    assert_eq!(Color::RED.lerp(Color::BLUE, 2.0), Color::BLUE);
+
+    assert_eq!(Color::RED.blend(Color::BLUE, 0), Color::RED);
+    assert_eq!(Color::RED.blend(Color::BLUE, 255), Color::BLUE);
+    assert_eq!(
+        Color::WHITE.blend(Color::BLACK, 64),
+        Color::new(191, 191, 191)
+    );
+}
+
+#[test]
+fn test_add_and_scale() {
+    assert_eq!(Color::RED.saturating_add(Color::BLUE), Color::MAGENTA);
+    assert_eq!(
+        Color::new(200, 10, 0).saturating_add(Color::new(100, 10, 0)),
+        Color::new(255, 20, 0)
+    );
+    assert_eq!(Color::WHITE.scale(255), Color::WHITE);
+    assert_eq!(Color::WHITE.scale(0), Color::BLACK);
+    assert_eq!(Color::ORANGE.scale(128), Color::new(128, 83, 0));
+}
+
+#[test]
+fn test_from_kelvin() {
+    assert_eq!(Color::from_kelvin(6600), Color::WHITE);
+    assert_eq!(Color::from_kelvin(2700), Color::new(255, 167, 87));
+    assert_eq!(Color::from_kelvin(10000), Color::new(202, 218, 255));
+    assert_eq!(Color::from_kelvin(0), Color::from_kelvin(1000));
+}
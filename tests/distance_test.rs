@@ -1,5 +1,10 @@
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::digital::{
+    Mock as PinMock, State as PinState, Transaction as PinTransaction,
+};
 use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
-use modulino::Distance;
+use modulino::{DetectionMode, DetectionThresholds, Distance, Error, InterruptSource, RangeStatus};
 
 #[test]
 fn test_distance_logic() {
@@ -10,7 +15,7 @@ fn test_distance_logic() {
 
         // read_distance()
         // 1. Read STATUS (0x0089)
-        I2cTransaction::write_read(addr, vec![0x00, 0x89], vec![0x04]),
+        I2cTransaction::write_read(addr, vec![0x00, 0x89], vec![0x09]), // valid
         // 2. Read DISTANCE (0x0096)
         I2cTransaction::write_read(addr, vec![0x00, 0x96], vec![0x01, 0xF4]), // 500mm
         // 3. clear_interrupt() -> Write SYSTEM_INTERRUPT_CLEAR (0x0086) = 0x01
@@ -24,3 +29,346 @@ fn test_distance_logic() {
 
     distance.release().done();
 }
+
+#[test]
+fn test_distance_rejects_invalid_reading() {
+    let addr = 0x29;
+    let expectations = [
+        // Raw status 0x04 is ST status 2: signal below threshold
+        I2cTransaction::write_read(addr, vec![0x00, 0x89], vec![0x04]),
+        I2cTransaction::write_read(addr, vec![0x00, 0x96], vec![0x01, 0xF4]),
+        I2cTransaction::write(addr, vec![0x00, 0x86, 0x01]),
+    ];
+
+    let mut distance = Distance::new(I2cMock::new(&expectations));
+    assert_eq!(distance.read_distance().unwrap(), None);
+    distance.release().done();
+}
+
+#[test]
+fn test_distance_read_measurement() {
+    let addr = 0x29;
+    let expectations = [
+        // Result block from RESULT__RANGE_STATUS (0x0089) to the distance (0x0097)
+        I2cTransaction::write_read(
+            addr,
+            vec![0x00, 0x89],
+            vec![
+                0x06, // status: sigma above threshold
+                0x00, 0x00, // unused
+                0x0C, 0x00, // SPAD count 12 (x256)
+                0x01, 0x2C, // signal rate 300 (x8 kcps)
+                0x00, 0x0A, // ambient rate 10 (x8 kcps)
+                0x00, 0x28, // sigma 40 (x4 mm)
+                0x00, 0x00, // unused
+                0x00, 0xC8, // distance 200 mm
+            ],
+        ),
+        I2cTransaction::write(addr, vec![0x00, 0x86, 0x01]),
+    ];
+
+    let mut distance = Distance::new(I2cMock::new(&expectations));
+    let measurement = distance.read_measurement().unwrap();
+    assert_eq!(measurement.distance_mm, 200);
+    assert_eq!(measurement.status, RangeStatus::SigmaAboveThreshold);
+    assert_eq!(measurement.signal_rate_kcps, 2400);
+    assert_eq!(measurement.ambient_rate_kcps, 80);
+    assert_eq!(measurement.sigma_mm, 10);
+    assert_eq!(measurement.spad_count, 12);
+    assert_eq!(measurement.signal_per_spad_kcps(), 200);
+    assert!(!measurement.is_valid());
+    assert_eq!(measurement.distance(), None);
+    distance.release().done();
+}
+
+#[test]
+fn test_range_status_decoding() {
+    assert_eq!(RangeStatus::from_raw(0x09), RangeStatus::Valid);
+    assert_eq!(RangeStatus::from_raw(0x09).code(), 0);
+    // Only the low five bits hold the status
+    assert_eq!(RangeStatus::from_raw(0xE9), RangeStatus::Valid);
+    assert_eq!(RangeStatus::from_raw(0x07), RangeStatus::WrappedTarget);
+    assert_eq!(RangeStatus::from_raw(0x07).code(), 7);
+    assert_eq!(RangeStatus::from_raw(0x03).code(), 5);
+    assert_eq!(RangeStatus::from_raw(0x17), RangeStatus::SignalTooWeak);
+    assert_eq!(RangeStatus::from_raw(0x0D), RangeStatus::Other(13));
+    assert_eq!(RangeStatus::from_raw(0x00), RangeStatus::Other(0));
+    assert_eq!(RangeStatus::Other(0).code(), 255);
+
+    assert!(RangeStatus::Valid.is_valid());
+    assert!(RangeStatus::SigmaAboveThreshold.is_warning());
+    assert!(!RangeStatus::WrappedTarget.is_warning());
+    assert!(!RangeStatus::WrappedTarget.is_valid());
+}
+
+#[test]
+fn test_distance_offset_registers() {
+    let addr = 0x29;
+    let expectations = [
+        // set_offset(-12): RANGE_OFFSET_MM = -48 quarter mm, 13 bits
+        I2cTransaction::write(addr, vec![0x00, 0x1E, 0x1F, 0xD0]),
+        I2cTransaction::write(addr, vec![0x00, 0x20, 0x00, 0x00]),
+        I2cTransaction::write(addr, vec![0x00, 0x22, 0x00, 0x00]),
+        // offset()
+        I2cTransaction::write_read(addr, vec![0x00, 0x1E], vec![0x1F, 0xD0]),
+        I2cTransaction::write_read(addr, vec![0x00, 0x1E], vec![0x00, 0x64]),
+    ];
+
+    let mut distance = Distance::new(I2cMock::new(&expectations));
+    distance.set_offset(-12).unwrap();
+    assert_eq!(distance.offset().unwrap(), -12);
+    assert_eq!(distance.offset().unwrap(), 25);
+    assert_eq!(distance.set_offset(1024), Err(Error::InvalidParameter));
+    distance.release().done();
+}
+
+#[test]
+fn test_distance_crosstalk_registers() {
+    let addr = 0x29;
+    let expectations = [
+        // set_crosstalk(2.5): gradients cleared, plane offset = 2.5 * 512
+        I2cTransaction::write(addr, vec![0x00, 0x18, 0x00, 0x00]),
+        I2cTransaction::write(addr, vec![0x00, 0x1A, 0x00, 0x00]),
+        I2cTransaction::write(addr, vec![0x00, 0x16, 0x05, 0x00]),
+        // crosstalk()
+        I2cTransaction::write_read(addr, vec![0x00, 0x16], vec![0x05, 0x00]),
+    ];
+
+    let mut distance = Distance::new(I2cMock::new(&expectations));
+    distance.set_crosstalk(2.5).unwrap();
+    assert_eq!(distance.crosstalk().unwrap(), 2.5);
+    assert_eq!(distance.set_crosstalk(-1.0), Err(Error::InvalidParameter));
+    assert_eq!(distance.set_crosstalk(128.0), Err(Error::InvalidParameter));
+    distance.release().done();
+}
+
+#[test]
+fn test_distance_calibration_arguments() {
+    let mut distance = Distance::new(I2cMock::new(&[]));
+    let mut delay = NoopDelay::new();
+    assert_eq!(
+        distance.calibrate_offset(&mut delay, 5, 10),
+        Err(Error::InvalidParameter)
+    );
+    assert_eq!(
+        distance.calibrate_offset(&mut delay, 1001, 10),
+        Err(Error::InvalidParameter)
+    );
+    assert_eq!(
+        distance.calibrate_offset(&mut delay, 100, 4),
+        Err(Error::InvalidParameter)
+    );
+    assert_eq!(
+        distance.calibrate_crosstalk(&mut delay, 4),
+        Err(Error::InvalidParameter)
+    );
+    distance.release().done();
+}
+
+#[test]
+fn test_distance_detection_thresholds() {
+    let addr = 0x29;
+    let expectations = [
+        // set_detection_thresholds(100, 300, Inside)
+        I2cTransaction::write(addr, vec![0x00, 0x46, 0x03]),
+        I2cTransaction::write(addr, vec![0x00, 0x72, 0x01, 0x2C]),
+        I2cTransaction::write(addr, vec![0x00, 0x74, 0x00, 0x64]),
+        // detection_thresholds()
+        I2cTransaction::write_read(addr, vec![0x00, 0x46], vec![0x03]),
+        I2cTransaction::write_read(addr, vec![0x00, 0x74], vec![0x00, 0x64]),
+        I2cTransaction::write_read(addr, vec![0x00, 0x72], vec![0x01, 0x2C]),
+        // read_interrupt_source(): GPIO active low and asserted
+        I2cTransaction::write_read(addr, vec![0x00, 0x30], vec![0x11]),
+        I2cTransaction::write_read(addr, vec![0x00, 0x31], vec![0x00]),
+        I2cTransaction::write_read(addr, vec![0x00, 0x46], vec![0x03]),
+        // clear_detection_thresholds()
+        I2cTransaction::write(addr, vec![0x00, 0x46, 0x20]),
+        I2cTransaction::write_read(addr, vec![0x00, 0x46], vec![0x20]),
+        // read_interrupt_source(): not asserted
+        I2cTransaction::write_read(addr, vec![0x00, 0x30], vec![0x11]),
+        I2cTransaction::write_read(addr, vec![0x00, 0x31], vec![0x01]),
+    ];
+
+    let mut distance = Distance::new(I2cMock::new(&expectations));
+    distance
+        .set_detection_thresholds(100, 300, DetectionMode::Inside)
+        .unwrap();
+    assert_eq!(
+        distance.detection_thresholds().unwrap(),
+        Some(DetectionThresholds {
+            low_mm: 100,
+            high_mm: 300,
+            mode: DetectionMode::Inside,
+        })
+    );
+    assert_eq!(
+        distance.read_interrupt_source().unwrap(),
+        Some(InterruptSource::Threshold(DetectionMode::Inside))
+    );
+    distance.clear_detection_thresholds().unwrap();
+    assert_eq!(distance.detection_thresholds().unwrap(), None);
+    assert_eq!(distance.read_interrupt_source().unwrap(), None);
+
+    // A window needs low <= high
+    assert_eq!(
+        distance.set_detection_thresholds(300, 100, DetectionMode::Outside),
+        Err(Error::InvalidParameter)
+    );
+    distance.release().done();
+}
+
+#[test]
+fn test_distance_interrupt_pin() {
+    let distance = Distance::new(I2cMock::new(&[]));
+    let mut gpio1 = PinMock::new(&[
+        PinTransaction::get(PinState::High),
+        PinTransaction::get(PinState::Low),
+    ]);
+
+    assert!(!distance.interrupt_asserted(&mut gpio1).unwrap());
+    assert!(distance.interrupt_asserted(&mut gpio1).unwrap());
+
+    gpio1.done();
+    distance.release().done();
+}
+
+#[test]
+fn test_detection_mode_matches() {
+    assert!(DetectionMode::Below.matches(99, 100, 300));
+    assert!(!DetectionMode::Below.matches(100, 100, 300));
+    assert!(DetectionMode::Above.matches(301, 100, 300));
+    assert!(DetectionMode::Inside.matches(100, 100, 300));
+    assert!(DetectionMode::Inside.matches(300, 100, 300));
+    assert!(!DetectionMode::Inside.matches(301, 100, 300));
+    assert!(DetectionMode::Outside.matches(50, 100, 300));
+    assert!(!DetectionMode::Outside.matches(200, 100, 300));
+}
+
+#[test]
+fn test_distance_range_timing() {
+    let addr = 0x29;
+    let expectations = [
+        // set_range_timing(20, 50): low-power mode
+        I2cTransaction::write_read(addr, vec![0x00, 0x06], vec![0xBE, 0x00]),
+        I2cTransaction::write_read(addr, vec![0x00, 0xDE], vec![0x02, 0x40]),
+        I2cTransaction::write(addr, vec![0x00, 0x6C, 0x00, 0x00, 0x76, 0xB0]),
+        I2cTransaction::write(addr, vec![0x00, 0x5E, 0x00, 0xA1]),
+        I2cTransaction::write(addr, vec![0x00, 0x61, 0x00, 0xD7]),
+        // inter_measurement()
+        I2cTransaction::write_read(addr, vec![0x00, 0x6C], vec![0x00, 0x00, 0x76, 0xB0]),
+        I2cTransaction::write_read(addr, vec![0x00, 0xDE], vec![0x02, 0x40]),
+        // timing_budget()
+        I2cTransaction::write_read(addr, vec![0x00, 0x6C], vec![0x00, 0x00, 0x76, 0xB0]),
+        I2cTransaction::write_read(addr, vec![0x00, 0x06], vec![0xBE, 0x00]),
+        I2cTransaction::write_read(addr, vec![0x00, 0x5E], vec![0x00, 0xA1]),
+        // set_inter_measurement(0): back to back, keeping 20 ms
+        I2cTransaction::write_read(addr, vec![0x00, 0x06], vec![0xBE, 0x00]),
+        I2cTransaction::write(addr, vec![0x00, 0x6C, 0x00, 0x00, 0x00, 0x00]),
+        I2cTransaction::write(addr, vec![0x00, 0x5E, 0x01, 0xB4]),
+        I2cTransaction::write(addr, vec![0x00, 0x61, 0x01, 0xF0]),
+        // timing_budget()
+        I2cTransaction::write_read(addr, vec![0x00, 0x6C], vec![0x00, 0x00, 0x00, 0x00]),
+        I2cTransaction::write_read(addr, vec![0x00, 0x06], vec![0xBE, 0x00]),
+        I2cTransaction::write_read(addr, vec![0x00, 0x5E], vec![0x01, 0xB4]),
+        // set_timing_budget(50) without an oscillator frequency
+        I2cTransaction::write_read(addr, vec![0x00, 0x06], vec![0x00, 0x00]),
+    ];
+
+    let mut distance = Distance::new(I2cMock::new(&expectations));
+    distance.set_range_timing(20, 50).unwrap();
+    assert_eq!(distance.inter_measurement().unwrap(), 50);
+    assert_eq!(distance.timing_budget().unwrap(), 20);
+    distance.set_inter_measurement(0).unwrap();
+    assert_eq!(distance.timing_budget().unwrap(), 20);
+    assert_eq!(distance.set_timing_budget(50), Err(Error::DataError));
+
+    // Invalid combinations are rejected before touching the bus
+    assert_eq!(
+        distance.set_range_timing(9, 0),
+        Err(Error::InvalidParameter)
+    );
+    assert_eq!(
+        distance.set_range_timing(201, 0),
+        Err(Error::InvalidParameter)
+    );
+    assert_eq!(
+        distance.set_range_timing(100, 100),
+        Err(Error::InvalidParameter)
+    );
+    distance.release().done();
+}
+
+#[test]
+fn test_distance_start_ranging_mode() {
+    let addr = 0x29;
+    let expectations = [
+        // start_ranging() with the default period: autonomous
+        I2cTransaction::write(addr, vec![0x00, 0x87, 0x40]),
+        // set_range_timing(20, 0)
+        I2cTransaction::write_read(addr, vec![0x00, 0x06], vec![0xBE, 0x00]),
+        I2cTransaction::write(addr, vec![0x00, 0x6C, 0x00, 0x00, 0x00, 0x00]),
+        I2cTransaction::write(addr, vec![0x00, 0x5E, 0x01, 0xB4]),
+        I2cTransaction::write(addr, vec![0x00, 0x61, 0x01, 0xF0]),
+        // start_ranging(): back to back
+        I2cTransaction::write(addr, vec![0x00, 0x87, 0x21]),
+    ];
+
+    let mut distance = Distance::new(I2cMock::new(&expectations));
+    distance.start_ranging().unwrap();
+    distance.set_range_timing(20, 0).unwrap();
+    distance.start_ranging().unwrap();
+    distance.release().done();
+}
+
+#[test]
+fn test_distance_measure_blocking() {
+    let addr = 0x29;
+    let expectations = [
+        // Stop, clear and start a single shot
+        I2cTransaction::write(addr, vec![0x00, 0x87, 0x00]),
+        I2cTransaction::write(addr, vec![0x00, 0x86, 0x01]),
+        I2cTransaction::write(addr, vec![0x00, 0x87, 0x10]),
+        // Not ready, then ready (active low)
+        I2cTransaction::write_read(addr, vec![0x00, 0x30], vec![0x11]),
+        I2cTransaction::write_read(addr, vec![0x00, 0x31], vec![0x01]),
+        I2cTransaction::write_read(addr, vec![0x00, 0x30], vec![0x11]),
+        I2cTransaction::write_read(addr, vec![0x00, 0x31], vec![0x00]),
+        // read_distance()
+        I2cTransaction::write_read(addr, vec![0x00, 0x89], vec![0x09]),
+        I2cTransaction::write_read(addr, vec![0x00, 0x96], vec![0x00, 0x7B]),
+        I2cTransaction::write(addr, vec![0x00, 0x86, 0x01]),
+        // Stop
+        I2cTransaction::write(addr, vec![0x00, 0x87, 0x00]),
+    ];
+
+    let mut distance = Distance::new(I2cMock::new(&expectations));
+    assert_eq!(
+        distance
+            .measure_blocking(&mut NoopDelay::new(), 100)
+            .unwrap(),
+        Some(123)
+    );
+    distance.release().done();
+}
+
+#[test]
+fn test_distance_measure_blocking_timeout() {
+    let addr = 0x29;
+    let expectations = [
+        I2cTransaction::write(addr, vec![0x00, 0x87, 0x00]),
+        I2cTransaction::write(addr, vec![0x00, 0x86, 0x01]),
+        I2cTransaction::write(addr, vec![0x00, 0x87, 0x10]),
+        // Never ready
+        I2cTransaction::write_read(addr, vec![0x00, 0x30], vec![0x11]),
+        I2cTransaction::write_read(addr, vec![0x00, 0x31], vec![0x01]),
+        // Stopping fails as well
+        I2cTransaction::write(addr, vec![0x00, 0x87, 0x00]).with_error(ErrorKind::Other),
+    ];
+
+    let mut distance = Distance::new(I2cMock::new(&expectations));
+    assert_eq!(
+        distance.measure_blocking(&mut NoopDelay::new(), 0),
+        Err(Error::Timeout)
+    );
+    distance.release().done();
+}
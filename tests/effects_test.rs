@@ -0,0 +1,113 @@
+use modulino::{EffectKind, Sequencer, SoundEffect, ToneSource, ToneStep};
+
+fn frequencies(mut effect: SoundEffect, limit: usize) -> Vec<u16> {
+    core::iter::from_fn(|| effect.next_step())
+        .take(limit)
+        .map(|step| step.frequency)
+        .collect()
+}
+
+#[test]
+fn test_sweep() {
+    let sweep = SoundEffect::new(EffectKind::Sweep, 400, 800, 10).with_steps(5);
+    assert_eq!(
+        frequencies(sweep.clone(), 100),
+        vec![400, 500, 600, 700, 800]
+    );
+    assert_eq!(sweep.duration_ms(), Some(50));
+
+    let down = SoundEffect::new(EffectKind::Sweep, 800, 400, 10).with_steps(3);
+    assert_eq!(frequencies(down, 100), vec![800, 600, 400]);
+}
+
+#[test]
+fn test_wail_and_alternate_repeat() {
+    let wail = SoundEffect::new(EffectKind::Wail, 400, 800, 10)
+        .with_steps(3)
+        .with_repeats(2);
+    assert_eq!(
+        frequencies(wail, 100),
+        vec![400, 600, 800, 600, 400, 600, 800, 600]
+    );
+
+    let trill = SoundEffect::new(EffectKind::Alternate, 1000, 1100, 10).with_steps(4);
+    assert_eq!(frequencies(trill, 100), vec![1000, 1100, 1000, 1100]);
+}
+
+#[test]
+fn test_beeps_with_pause() {
+    let mut alarm = SoundEffect::new(EffectKind::Beep, 2000, 0, 100)
+        .with_steps(2)
+        .with_repeats(2)
+        .with_pause(500);
+    assert_eq!(alarm.duration_ms(), Some(1600));
+
+    let steps: Vec<_> = core::iter::from_fn(|| alarm.next_step()).collect();
+    assert_eq!(
+        steps,
+        vec![
+            ToneStep::new(2000, 100),
+            ToneStep::rest(100),
+            ToneStep::new(2000, 100),
+            ToneStep::rest(500),
+            ToneStep::new(2000, 100),
+            ToneStep::rest(100),
+            ToneStep::new(2000, 100),
+            ToneStep::rest(500),
+        ]
+    );
+
+    alarm.rewind();
+    assert_eq!(alarm.next_step(), Some(ToneStep::new(2000, 100)));
+}
+
+#[test]
+fn test_frequencies_are_clamped_to_buzzer_range() {
+    let low = SoundEffect::new(EffectKind::Sweep, 50, 400, 10).with_steps(2);
+    assert_eq!(frequencies(low, 100), vec![180, 400]);
+}
+
+#[test]
+fn test_presets() {
+    assert_eq!(SoundEffect::siren().duration_ms(), None);
+    assert_eq!(
+        frequencies(SoundEffect::siren(), 5),
+        vec![960, 770, 960, 770, 960]
+    );
+    assert_eq!(frequencies(SoundEffect::beep(), 100), vec![2000]);
+    assert_eq!(frequencies(SoundEffect::rising_sweep(), 100).len(), 24);
+    assert!(SoundEffect::chirp().duration_ms().unwrap() < 100);
+    assert_eq!(SoundEffect::alarm().kind(), EffectKind::Beep);
+    assert_eq!(SoundEffect::wail().repeats(), 0);
+    assert!(frequencies(SoundEffect::falling_sweep(), 100)
+        .windows(2)
+        .all(|w| w[0] > w[1]));
+    assert_eq!(frequencies(SoundEffect::trill(), 100).len(), 20);
+}
+
+#[test]
+fn test_effect_in_sequencer() {
+    let effect = SoundEffect::new(EffectKind::Sweep, 400, 600, 20).with_steps(3);
+    let mut seq = Sequencer::new(effect);
+    assert_eq!(seq.poll(0).unwrap().frequency, 400);
+    assert!(seq.poll(10).is_none());
+    assert_eq!(seq.poll(20).unwrap().frequency, 500);
+    assert_eq!(seq.poll(40).unwrap().frequency, 600);
+    assert!(seq.poll(60).is_none());
+    assert!(seq.is_finished());
+}
+
+#[test]
+fn test_large_step_counts_are_clamped() {
+    let beeps = SoundEffect::new(EffectKind::Beep, 2000, 0, 1).with_steps(u16::MAX);
+    // 32767 beeps with a silence between each
+    assert_eq!(beeps.duration_ms(), Some(65533));
+    assert_eq!(frequencies(beeps, usize::MAX).len(), 65533);
+
+    let wail = SoundEffect::new(EffectKind::Wail, 400, 800, 1).with_steps(u16::MAX);
+    assert_eq!(wail.duration_ms(), Some(65532));
+    let wail = frequencies(wail, usize::MAX);
+    assert_eq!(wail.len(), 65532);
+    assert_eq!(wail[0], 400);
+    assert_eq!(wail[usize::from(SoundEffect::MAX_STEPS) - 1], 800);
+}
@@ -1,5 +1,13 @@
+use core::cell::RefCell;
+
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
 use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
-use modulino::Hub;
+use modulino::{addresses, Buttons, Error, Hub, HubChannel, HubPort, ModulinoKind, SCAN_RANGE};
+
+fn nack(addr: u8) -> I2cTransaction {
+    I2cTransaction::write(addr, vec![])
+        .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))
+}
 
 #[test]
 fn test_hub_select_and_clear() {
@@ -23,3 +31,199 @@ fn test_hub_select_and_clear() {
 
     hub.release().done();
 }
+
+#[test]
+fn test_hub_port_selects_before_transaction() {
+    let hub_addr = 0x70;
+    let buttons_addr = 0x3E;
+
+    let expectations = [
+        // First access selects port 2
+        I2cTransaction::write(hub_addr, vec![0x04]),
+        I2cTransaction::read(buttons_addr, vec![0x7C, 0x00, 0x00, 0x00]),
+        // Port 2 is still selected, so no control write
+        I2cTransaction::write(buttons_addr, vec![0x01, 0x00, 0x00]),
+    ];
+
+    let i2c = I2cMock::new(&expectations);
+    let mut hub = Hub::new(i2c);
+
+    let mut buttons = Buttons::new(hub.port(2).unwrap()).unwrap();
+    buttons.set_leds(true, false, false).unwrap();
+    buttons.release();
+
+    hub.release().done();
+}
+
+#[test]
+fn test_hub_channels_switch_ports() {
+    let hub_addr = 0x70;
+    let buttons_addr = 0x3E;
+
+    let expectations = [
+        // Buttons A on port 0
+        I2cTransaction::write(hub_addr, vec![0x01]),
+        I2cTransaction::read(buttons_addr, vec![0x7C, 0x00, 0x00, 0x00]),
+        // Buttons B on port 1
+        I2cTransaction::write(hub_addr, vec![0x02]),
+        I2cTransaction::read(buttons_addr, vec![0x7C, 0x00, 0x00, 0x00]),
+        // Read B again: already selected
+        I2cTransaction::read(buttons_addr, vec![0x7C, 0x00, 0x01, 0x00]),
+        // Back to A
+        I2cTransaction::write(hub_addr, vec![0x01]),
+        I2cTransaction::read(buttons_addr, vec![0x7C, 0x01, 0x00, 0x00]),
+    ];
+
+    let i2c = I2cMock::new(&expectations);
+    let hub = RefCell::new(Hub::new(i2c));
+
+    let mut buttons_a = Buttons::new(HubChannel::new(&hub, 0).unwrap()).unwrap();
+    let mut buttons_b = Buttons::new(HubChannel::new(&hub, 1).unwrap()).unwrap();
+
+    assert!(buttons_b.read().unwrap().b);
+    assert!(buttons_a.read().unwrap().a);
+
+    buttons_a.release();
+    buttons_b.release();
+    hub.into_inner().release().done();
+}
+
+#[test]
+fn test_hub_port_invalid() {
+    let i2c = I2cMock::new(&[]);
+    let mut hub = Hub::new(i2c);
+
+    assert!(matches!(hub.port(8), Err(Error::InvalidParameter)));
+    assert!(matches!(
+        hub.ensure_selected(8),
+        Err(Error::InvalidParameter)
+    ));
+
+    hub.release().done();
+}
+
+#[test]
+fn test_hub_out_of_range_port_handles() {
+    // Rejected before touching the bus
+    let mut hub = Hub::new(I2cMock::new(&[]));
+    assert!(matches!(
+        HubPort::new(&mut hub, 9),
+        Err(Error::InvalidParameter)
+    ));
+
+    let hub = RefCell::new(hub);
+    assert!(matches!(
+        HubChannel::new(&hub, 8),
+        Err(Error::InvalidParameter)
+    ));
+
+    hub.into_inner().release().done();
+}
+
+#[test]
+fn test_hub_select_mask_and_read_control() {
+    let addr = 0x70;
+
+    let expectations = [
+        I2cTransaction::write(addr, vec![0b1000_0101]),
+        I2cTransaction::read(addr, vec![0b1000_0101]),
+        // Mask has other ports enabled, so port 0 is re-selected alone
+        I2cTransaction::write(addr, vec![0x01]),
+    ];
+
+    let i2c = I2cMock::new(&expectations);
+    let mut hub = Hub::new(i2c);
+
+    hub.select_mask(0b1000_0101).unwrap();
+    assert_eq!(hub.read_control().unwrap(), 0b1000_0101);
+    hub.ensure_selected(0).unwrap();
+
+    hub.release().done();
+}
+
+#[test]
+fn test_hub_address_pins() {
+    let i2c = I2cMock::new(&[]);
+    let hub = Hub::new_with_pins(i2c, false, false, false);
+    assert_eq!(hub.address(), 0x70);
+    hub.release().done();
+
+    let i2c = I2cMock::new(&[]);
+    let hub = Hub::new_with_pins(i2c, true, false, true);
+    assert_eq!(hub.address(), 0x75);
+    hub.release().done();
+
+    assert_eq!(addresses::HUB[7], 0x77);
+}
+
+#[test]
+fn test_hub_scan_port() {
+    let hub_addr = 0x70;
+    let mut expectations = vec![I2cTransaction::write(hub_addr, vec![0x00])];
+
+    // Baseline: only the hub and a Thermo on the main bus
+    for addr in SCAN_RANGE {
+        match addr {
+            0x44 | 0x70 => expectations.push(I2cTransaction::write(addr, vec![])),
+            _ => expectations.push(nack(addr)),
+        }
+    }
+
+    // Port 3 enabled: a Buttons module shows up as well
+    expectations.push(I2cTransaction::write(hub_addr, vec![0x08]));
+    for addr in SCAN_RANGE {
+        match addr {
+            0x3E => {
+                expectations.push(I2cTransaction::write(addr, vec![]));
+                expectations.push(I2cTransaction::read(addr, vec![0x7C]));
+            }
+            0x44 | 0x70 => expectations.push(I2cTransaction::write(addr, vec![])),
+            _ => expectations.push(nack(addr)),
+        }
+    }
+    expectations.push(I2cTransaction::write(hub_addr, vec![0x00]));
+
+    let i2c = I2cMock::new(&expectations);
+    let mut hub = Hub::new(i2c);
+
+    let found = hub.scan_port(3).unwrap();
+    assert_eq!(
+        found.iter().collect::<Vec<_>>(),
+        vec![(0x3E, ModulinoKind::Buttons)]
+    );
+    assert!(matches!(hub.scan_port(8), Err(Error::InvalidParameter)));
+
+    hub.release().done();
+}
+
+#[test]
+fn test_chained_hubs() {
+    let upstream = 0x70;
+    let downstream = 0x71;
+    let buttons_addr = 0x3E;
+
+    let expectations = [
+        // Upstream selects port 7, downstream selects port 0
+        I2cTransaction::write(upstream, vec![0x80]),
+        I2cTransaction::write(downstream, vec![0x01]),
+        I2cTransaction::read(buttons_addr, vec![0x7C, 0x00, 0x00, 0x00]),
+        // Both already selected
+        I2cTransaction::read(buttons_addr, vec![0x7C, 0x00, 0x00, 0x01]),
+    ];
+
+    let i2c = I2cMock::new(&expectations);
+    let hub = RefCell::new(Hub::new(i2c));
+    let chained = RefCell::new(Hub::new_with_pins(
+        HubChannel::new(&hub, 7).unwrap(),
+        true,
+        false,
+        false,
+    ));
+
+    let mut buttons = Buttons::new(HubChannel::new(&chained, 0).unwrap()).unwrap();
+    assert!(buttons.read().unwrap().c);
+
+    buttons.release();
+    chained.into_inner().release();
+    hub.into_inner().release().done();
+}
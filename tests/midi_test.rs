@@ -0,0 +1,225 @@
+use modulino::{MidiError, MidiFile, MidiPlayer, RangePolicy, Sequencer, ToneSource, ToneStep};
+
+/// Build a file from a header and raw track bodies.
+fn smf(format: u16, division: u16, tracks: &[&[u8]]) -> Vec<u8> {
+    let mut data = b"MThd".to_vec();
+    data.extend_from_slice(&6u32.to_be_bytes());
+    data.extend_from_slice(&format.to_be_bytes());
+    data.extend_from_slice(&(tracks.len() as u16).to_be_bytes());
+    data.extend_from_slice(&division.to_be_bytes());
+    for track in tracks {
+        data.extend_from_slice(b"MTrk");
+        data.extend_from_slice(&(track.len() as u32).to_be_bytes());
+        data.extend_from_slice(track);
+    }
+    data
+}
+
+fn steps(player: MidiPlayer) -> Vec<ToneStep> {
+    let mut player = player;
+    core::iter::from_fn(|| player.next_step()).collect()
+}
+
+/// An empty track: just the end-of-track event.
+const END: [u8; 4] = [0x00, 0xFF, 0x2F, 0x00];
+
+// The files below use 96 ticks per quarter note, so at the default 120 BPM
+// 0x60 ticks last 500 ms.
+
+#[test]
+fn test_format0_melody_with_rest() {
+    #[rustfmt::skip]
+    let track = [
+        0x00, 0x90, 69, 100,    // A4 on
+        0x60, 0x80, 69, 0,      // off after a quarter
+        0x30, 0x90, 72, 100,    // C5 on after an eighth rest
+        0x60, 72, 0,            // running status, velocity 0 = off
+        0x00, 0xFF, 0x2F, 0x00,
+    ];
+    let data = smf(0, 96, &[&track]);
+    let file = MidiFile::parse(&data).unwrap();
+    assert_eq!(file.format(), 0);
+    assert_eq!(file.ticks_per_quarter(), 96);
+    assert_eq!(file.track_count(), 1);
+
+    assert_eq!(
+        steps(file.player()),
+        vec![
+            ToneStep::new(440, 500),
+            ToneStep::rest(250),
+            ToneStep::new(523, 500),
+        ]
+    );
+}
+
+#[test]
+fn test_polyphony_keeps_highest_note() {
+    #[rustfmt::skip]
+    let track = [
+        0x00, 0x90, 60 + 12, 100, // C5
+        0x00, 0x90, 64 + 12, 100, // E5 (chord, same tick)
+        0x60, 0x80, 76, 0,        // E5 off: back to C5
+        0x60, 0x80, 72, 0,
+        0x00, 0xFF, 0x2F, 0x00,
+    ];
+    let data = smf(0, 96, &[&track]);
+    let file = MidiFile::parse(&data).unwrap();
+    assert_eq!(
+        steps(file.player().with_gap(20)),
+        vec![
+            ToneStep::new(659, 500).with_gap(20),
+            ToneStep::new(523, 500).with_gap(20),
+        ]
+    );
+}
+
+#[test]
+fn test_format1_tempo_track_selection_and_channels() {
+    #[rustfmt::skip]
+    let conductor = [
+        0x00, 0xFF, 0x51, 0x03, 0x0F, 0x42, 0x40, // 1 000 000 us per quarter (60 BPM)
+        0x00, 0xFF, 0x2F, 0x00,
+    ];
+    #[rustfmt::skip]
+    let melody = [
+        0x00, 0x90, 81, 100,     // A5 on channel 1
+        0x00, 0x91, 93, 100,     // A6 on channel 2
+        0x00, 0x99, 100, 100,    // drum on channel 10
+        0x60, 0x80, 81, 0,
+        0x00, 0x81, 93, 0,
+        0x00, 0x89, 100, 0,
+        0x00, 0xFF, 0x2F, 0x00,
+    ];
+    let data = smf(1, 96, &[&conductor, &melody]);
+    let file = MidiFile::parse(&data).unwrap();
+    assert_eq!(file.track_count(), 2);
+
+    // The drum channel is skipped by default
+    assert_eq!(steps(file.player()), vec![ToneStep::new(1760, 1000)]);
+    assert_eq!(
+        steps(file.player().with_channel(0)),
+        vec![ToneStep::new(880, 1000)]
+    );
+    assert_eq!(
+        steps(file.player().with_track(1).with_channel(9)),
+        vec![ToneStep::new(2637, 1000)]
+    );
+    // No notes on the conductor track, so the song is one long rest
+    assert_eq!(
+        steps(file.player().with_track(0)),
+        vec![ToneStep::rest(1000)]
+    );
+}
+
+#[test]
+fn test_range_policy() {
+    #[rustfmt::skip]
+    let track = [
+        0x00, 0x90, 45, 100, // A2, below the buzzer's range
+        0x60, 0x80, 45, 0,
+        0x00, 0x90, 69, 100, // A4
+        0x60, 0x80, 69, 0,
+        0x00, 0xFF, 0x2F, 0x00,
+    ];
+    let data = smf(0, 96, &[&track]);
+    let file = MidiFile::parse(&data).unwrap();
+
+    assert_eq!(
+        steps(file.player()),
+        vec![ToneStep::rest(500), ToneStep::new(440, 500)]
+    );
+    assert_eq!(
+        steps(file.player().with_range_policy(RangePolicy::Fold)),
+        vec![ToneStep::new(220, 500), ToneStep::new(440, 500)]
+    );
+    assert_eq!(
+        steps(
+            file.player()
+                .with_range(MidiPlayer::LOWEST_NOTE, 66)
+                .with_range_policy(RangePolicy::Fold)
+        ),
+        vec![ToneStep::new(220, 1000)]
+    );
+}
+
+#[test]
+fn test_sequencer_plays_midi() {
+    #[rustfmt::skip]
+    let track = [
+        0x00, 0x90, 69, 100,
+        0x60, 0x80, 69, 0,
+        0x00, 0xFF, 0x2F, 0x00,
+    ];
+    let data = smf(0, 96, &[&track]);
+    let file = MidiFile::parse(&data).unwrap();
+    let mut seq = Sequencer::new(file.player()).with_looping(true);
+    assert_eq!(seq.poll(0).unwrap().frequency, 440);
+    assert!(seq.poll(499).is_none());
+    assert_eq!(seq.poll(500).unwrap().frequency, 440);
+}
+
+#[test]
+fn test_invalid_files() {
+    assert_eq!(
+        MidiFile::parse(b"RIFF....").unwrap_err(),
+        MidiError::NotMidi
+    );
+    assert_eq!(
+        MidiFile::parse(&smf(2, 96, &[&END])).unwrap_err(),
+        MidiError::UnsupportedFormat
+    );
+    assert_eq!(
+        MidiFile::parse(&smf(0, 0xE728, &[&END])).unwrap_err(),
+        MidiError::UnsupportedTiming
+    );
+
+    let mut truncated = smf(0, 96, &[&END]);
+    truncated.truncate(truncated.len() - 2);
+    assert_eq!(
+        MidiFile::parse(&truncated).unwrap_err(),
+        MidiError::Truncated
+    );
+
+    // Data byte without a previous status
+    assert_eq!(
+        MidiFile::parse(&smf(0, 96, &[&[0x00, 0x40, 0x40]])).unwrap_err(),
+        MidiError::InvalidEvent
+    );
+
+    // Track cut off inside a delta time
+    assert_eq!(
+        MidiFile::parse(&smf(0, 96, &[&[0x00, 0x90, 69, 100, 0x81]])).unwrap_err(),
+        MidiError::Truncated
+    );
+    assert_eq!(
+        MidiFile::parse(&smf(0, 96, &[&[0x81]])).unwrap_err(),
+        MidiError::Truncated
+    );
+    // Delta time longer than 4 bytes
+    assert_eq!(
+        MidiFile::parse(&smf(0, 96, &[&[0x81, 0x81, 0x81, 0x81, 0x00]])).unwrap_err(),
+        MidiError::InvalidEvent
+    );
+
+    // Meta and sysex events cancel running status
+    #[rustfmt::skip]
+    let after_meta = [
+        0x00, 0x90, 69, 100,
+        0x00, 0xFF, 0x01, 0x00,
+        0x60, 69, 0,
+    ];
+    assert_eq!(
+        MidiFile::parse(&smf(0, 96, &[&after_meta])).unwrap_err(),
+        MidiError::InvalidEvent
+    );
+    #[rustfmt::skip]
+    let after_sysex = [
+        0x00, 0x90, 69, 100,
+        0x00, 0xF0, 0x01, 0xF7,
+        0x60, 69, 0,
+    ];
+    assert_eq!(
+        MidiFile::parse(&smf(0, 96, &[&after_sysex])).unwrap_err(),
+        MidiError::InvalidEvent
+    );
+}
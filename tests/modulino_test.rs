@@ -0,0 +1,57 @@
+use embedded_hal::i2c::I2c;
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+use modulino::{Buttons, Error, Knob, Light, Modulino, ModulinoKind, Thermo};
+
+/// Generic bring-up: probe, construct and initialize any module.
+fn connect<I2C: I2c, M: Modulino<I2C>>(mut i2c: I2C) -> modulino::Result<M, I2C::Error> {
+    let address = M::probe(&mut i2c)?;
+    let mut module = M::new_with_address(i2c, address)?;
+    module.init(&mut NoopDelay::new())?;
+    Ok(module)
+}
+
+#[test]
+fn test_generic_connect_buttons() {
+    let addr = 0x3E;
+    let expectations = [
+        I2cTransaction::write(addr, vec![]),
+        I2cTransaction::read(addr, vec![0x7C, 0x01, 0x00, 0x00]),
+    ];
+    let buttons: Buttons<_> = connect(I2cMock::new(&expectations)).unwrap();
+    assert!(buttons.button_a_pressed());
+    assert_eq!(Modulino::address(&buttons), addr);
+    Modulino::release(buttons).done();
+}
+
+#[test]
+fn test_generic_connect_light_runs_init() {
+    let addr = 0x53;
+    let expectations = [
+        I2cTransaction::write(addr, vec![]),
+        I2cTransaction::write_read(addr, vec![0x06], vec![0xC2]),
+        I2cTransaction::write_read(addr, vec![0x07], vec![0x00]),
+        I2cTransaction::write(addr, vec![0x05, 0x04]),
+        I2cTransaction::write(addr, vec![0x04, 0x40]),
+        I2cTransaction::write(addr, vec![0x00, 0x06]),
+    ];
+    let light: Light<_> = connect(I2cMock::new(&expectations)).unwrap();
+    Modulino::release(light).done();
+}
+
+#[test]
+fn test_thermo_rejects_other_addresses() {
+    let mut i2c = I2cMock::new(&[]);
+    let result = <Thermo<_> as Modulino<_>>::new_with_address(i2c.clone(), 0x45);
+    assert!(matches!(result, Err(Error::InvalidAddress)));
+    i2c.done();
+}
+
+#[test]
+fn test_associated_constants() {
+    type K = Knob<I2cMock>;
+    assert_eq!(<K as Modulino<I2cMock>>::KIND, ModulinoKind::Knob);
+    assert_eq!(<K as Modulino<I2cMock>>::DEFAULT_ADDRESSES, &[0x3A, 0x3B]);
+    assert_eq!(<K as Modulino<I2cMock>>::PINSTRAP, &[0x76, 0x74]);
+    assert!(<Light<I2cMock> as Modulino<I2cMock>>::PINSTRAP.is_empty());
+}
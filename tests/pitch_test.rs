@@ -0,0 +1,91 @@
+use modulino::{Note, Pitch};
+
+#[test]
+fn test_midi_conversion() {
+    let c4 = Pitch::from_midi(60).unwrap();
+    assert_eq!(c4.octave(), 4);
+    assert_eq!(c4.pitch_class(), 0);
+    assert_eq!(c4.frequency(), 262);
+    assert_eq!(c4.to_string(), "C4");
+    assert_eq!(Pitch::from_midi(128), None);
+    assert_eq!(Pitch::from_octave(9, 4), Some(Pitch::A4));
+    assert_eq!(Pitch::from_midi(0).unwrap().to_string(), "C-1");
+}
+
+#[test]
+fn test_note_compatibility() {
+    // Every Note agrees with the equal-tempered pitch of its MIDI number
+    for midi in 0..=127 {
+        if let Some(note) = Note::from_midi(midi) {
+            assert_eq!(note.midi(), Some(midi));
+            assert_eq!(
+                note.frequency(),
+                Pitch::from_midi(midi).unwrap().frequency()
+            );
+            assert_eq!(note.pitch().unwrap().to_note(), Some(note));
+        }
+    }
+    assert_eq!(Note::FS3.midi(), Some(54));
+    assert_eq!(Note::DS8.midi(), Some(111));
+    assert_eq!(Note::Rest.midi(), None);
+    assert_eq!(Note::Rest.pitch(), None);
+}
+
+#[test]
+fn test_parse_names() {
+    let parse = |s: &str| s.parse::<Pitch>().map(|p| p.midi());
+    assert_eq!(parse("A4"), Ok(69));
+    assert_eq!(parse("C#5"), Ok(73));
+    assert_eq!(parse("Db5"), Ok(73));
+    assert_eq!(parse("CS5"), Ok(73));
+    assert_eq!(parse("bb3"), Ok(58));
+    assert_eq!(parse("Cb4"), Ok(59));
+    assert_eq!(parse("B#3"), Ok(60));
+    assert_eq!(parse("C-1"), Ok(0));
+    assert_eq!(parse("G9"), Ok(127));
+    assert!(parse("G#9").is_err());
+    assert!(parse("H2").is_err());
+    assert!(parse("C").is_err());
+    assert!(parse("Cx4").is_err());
+}
+
+#[test]
+fn test_transpose() {
+    let a4 = Pitch::A4;
+    assert_eq!(a4.transpose(3).unwrap().to_note(), Some(Note::C5));
+    assert_eq!(a4.transpose_octaves(-1).unwrap().frequency(), 220);
+    assert_eq!(a4.transpose_octaves(2).unwrap().frequency(), 1760);
+    assert_eq!(a4.transpose(127), None);
+    assert_eq!(a4.transpose_octaves(-6), None);
+}
+
+#[test]
+fn test_cents_and_fixed_point() {
+    let a4 = Pitch::A4;
+    assert_eq!(a4.frequency_millihertz(), 440_000);
+    assert_eq!(
+        Pitch::from_midi(60).unwrap().frequency_millihertz(),
+        261_626
+    );
+
+    let sharp = a4.with_cents(10);
+    assert_eq!(sharp.cents(), 10);
+    assert_eq!(sharp.frequency(), 443);
+    assert_eq!(sharp.to_note(), None);
+    assert_eq!(sharp.to_string(), "A4+10c");
+    assert_eq!(a4.with_cents(-120).cents(), -100);
+
+    let tuned = Pitch::from_frequency(445.0).unwrap();
+    assert_eq!(tuned.midi(), 69);
+    assert_eq!(tuned.cents(), 20);
+    assert_eq!(Pitch::from_frequency(0.0), None);
+}
+
+#[test]
+fn test_buzzer_range() {
+    let low = Pitch::from_midi(40).unwrap();
+    assert!(!low.is_playable());
+    assert_eq!(low.clamped_frequency(), 180);
+    assert!(Pitch::from_midi(54).unwrap().is_playable());
+    assert_eq!(Pitch::A4.clamped_frequency(), 440);
+}
@@ -0,0 +1,120 @@
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+use modulino::{Animation, AnimationKind, Color, Error, PixelStrip, Pixels};
+
+/// The bytes for one module with `lit` LEDs set to full-brightness white.
+fn module(lit: &[usize]) -> Vec<u8> {
+    module_with_unlit(lit, 0xE0)
+}
+
+/// As [`module`], with `unlit` as the brightness byte of black LEDs.
+fn module_with_unlit(lit: &[usize], unlit: u8) -> Vec<u8> {
+    let mut data = Vec::new();
+    for i in 0..8 {
+        if lit.contains(&i) {
+            data.extend_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF]);
+        } else {
+            data.extend_from_slice(&[unlit, 0x00, 0x00, 0x00]);
+        }
+    }
+    data
+}
+
+fn pixels(addr: u8, writes: &[Vec<u8>]) -> Pixels<I2cMock> {
+    let expectations: Vec<_> = writes
+        .iter()
+        .map(|data| I2cTransaction::write(addr, data.clone()))
+        .collect();
+    Pixels::new_with_address(I2cMock::new(&expectations), addr).unwrap()
+}
+
+fn done<const M: usize>(strip: PixelStrip<I2cMock, M>) {
+    for pixels in strip.release() {
+        pixels.release().done();
+    }
+}
+
+#[test]
+fn test_strip_maps_indices_across_modules() {
+    let mut strip = PixelStrip::new([
+        pixels(0x36, &[module(&[0, 7])]),
+        pixels(0x37, &[module(&[0, 1, 2])]),
+        pixels(0x38, &[module(&[7])]),
+    ]);
+    assert_eq!(strip.len(), 24);
+
+    strip.set_color(0, Color::WHITE, 100).unwrap();
+    strip.set_range_color(7, 10, Color::WHITE, 100);
+    strip.set_color(23, Color::WHITE, 100).unwrap();
+    assert!(matches!(
+        strip.set_color(24, Color::WHITE, 100),
+        Err(Error::OutOfRange)
+    ));
+    strip.show().unwrap();
+    done(strip);
+}
+
+#[test]
+fn test_reversed_segment() {
+    let mut strip = PixelStrip::new([
+        pixels(0x36, &[module(&[6, 7])]),
+        pixels(0x37, &[module(&[6, 7])]),
+    ])
+    .with_reversed(1, true);
+    assert!(strip.is_reversed(1));
+    assert!(!strip.is_reversed(0));
+
+    // The second module runs backwards, so strip LEDs 8 and 9 are its 7 and 6
+    strip.set_range_color(6, 9, Color::WHITE, 100);
+    strip.show().unwrap();
+    done(strip);
+}
+
+#[test]
+fn test_animation_across_strip() {
+    let mut strip = PixelStrip::new([
+        // The animation sets the brightness of every LED, black or not
+        pixels(0x36, &[module_with_unlit(&[0, 3, 6], 0xFF)]),
+        pixels(0x37, &[module_with_unlit(&[1, 4, 7], 0xFF)]),
+    ]);
+    let mut chase = Animation::<16>::for_strip(AnimationKind::TheaterChase)
+        .with_palette(Color::WHITE)
+        .with_brightness(100);
+    assert!(chase.tick_strip(&mut strip, 0).unwrap());
+    assert!(!chase.tick_strip(&mut strip, 1).unwrap());
+    done(strip);
+}
+
+#[test]
+fn test_strip_readback_and_show_if_changed() {
+    let mut strip = PixelStrip::new([
+        pixels(0x36, &[module(&[])]),
+        pixels(0x37, &[module(&[]), module(&[0])]),
+    ])
+    .with_reversed(1, true);
+
+    assert!(strip.show_if_changed().unwrap());
+    assert!(!strip.show_if_changed().unwrap());
+
+    // Only the second module changed
+    strip.set_color(15, Color::WHITE, 100).unwrap();
+    assert_eq!(strip.get_color(15), Some(Color::WHITE));
+    assert_eq!(strip.get_brightness(15), Some(100));
+    assert_eq!(strip.segment(1).unwrap().get_color(0), Some(Color::WHITE));
+    assert_eq!(strip.get_color(16), None);
+    assert!(strip.show_if_changed().unwrap());
+    done(strip);
+}
+
+#[test]
+fn test_strip_power_budget() {
+    let scaled = vec![[0xFF, 129, 129, 129].repeat(8)];
+    let mut strip = PixelStrip::new([pixels(0x36, &scaled), pixels(0x37, &scaled)]);
+    strip.set_all_color(Color::WHITE, 100);
+    assert_eq!(strip.estimated_current_ma(), 973);
+
+    // The budget covers both modules together
+    strip.set_power_budget(Some(500));
+    assert!(strip.show_if_changed().unwrap());
+    assert!(!strip.show_if_changed().unwrap());
+    done(strip);
+}
@@ -1,5 +1,5 @@
 use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
-use modulino::{Color, Pixels};
+use modulino::{Color, ColorCorrection, Pixels, PowerModel};
 
 #[test]
 fn test_pixels_formatting() {
@@ -16,3 +16,146 @@ fn test_pixels_formatting() {
     pixels.show().unwrap();
     pixels.release().done();
 }
+
+fn frame(first: [u8; 4]) -> Vec<u8> {
+    let mut data = first.to_vec();
+    for _ in 1..8 {
+        data.extend_from_slice(&[0xE0, 0x00, 0x00, 0x00]);
+    }
+    data
+}
+
+#[test]
+fn test_pixels_color_correction() {
+    let addr = 0x36;
+    let expectations = [
+        // Pass-through correction matches the uncorrected output
+        I2cTransaction::write(addr, frame([0xEF, 0x00, 0x00, 0xFF])),
+        // Gamma 2.2: 128 becomes 56
+        I2cTransaction::write(addr, frame([0xFF, 56, 56, 56])),
+        // White balance scales each channel
+        I2cTransaction::write(addr, frame([0xFF, 0, 128, 255])),
+    ];
+    let mut pixels = Pixels::new(I2cMock::new(&expectations)).unwrap();
+    pixels.set_correction(Some(ColorCorrection::new()));
+    pixels.set_color(0, Color::RED, 50).unwrap();
+    pixels.show().unwrap();
+
+    pixels.set_correction(Some(ColorCorrection::new().with_gamma(2.2)));
+    pixels.set_color(0, Color::new(128, 128, 128), 100).unwrap();
+    pixels.show().unwrap();
+
+    pixels.set_correction(Some(
+        ColorCorrection::new().with_white_balance(Color::new(255, 128, 0)),
+    ));
+    pixels.set_color(0, Color::WHITE, 100).unwrap();
+    pixels.show().unwrap();
+    pixels.release().done();
+}
+
+#[test]
+fn test_pixels_high_resolution_brightness() {
+    let addr = 0x36;
+    let expectations = [
+        // 1% is off with the plain 5-bit mapping...
+        I2cTransaction::write(addr, frame([0xE0, 0x00, 0x00, 0xFF])),
+        // ...but global 1 with PWM 79 in high resolution
+        I2cTransaction::write(addr, frame([0xE1, 0x00, 0x00, 79])),
+        // 10%: global 4, PWM 198 instead of global 3, PWM 255
+        I2cTransaction::write(addr, frame([0xE4, 0x00, 0x00, 198])),
+        I2cTransaction::write(addr, frame([0xFF, 0xFF, 0xFF, 0xFF])),
+    ];
+    let mut pixels = Pixels::new(I2cMock::new(&expectations)).unwrap();
+    pixels.set_color(0, Color::RED, 1).unwrap();
+    pixels.show().unwrap();
+
+    pixels.set_correction(Some(ColorCorrection::new().with_high_resolution(true)));
+    assert!(pixels.correction().unwrap().is_high_resolution());
+    pixels.show().unwrap();
+    pixels.set_brightness(0, 10).unwrap();
+    pixels.show().unwrap();
+    pixels.set_color(0, Color::WHITE, 100).unwrap();
+    pixels.show().unwrap();
+    pixels.release().done();
+}
+
+#[test]
+fn test_pixels_readback() {
+    let mut pixels = Pixels::new(I2cMock::new(&[])).unwrap();
+    assert_eq!(pixels.get_color(0), Some(Color::BLACK));
+    assert_eq!(pixels.get_brightness(0), Some(0));
+
+    pixels.set_color(3, Color::ORANGE, 40).unwrap();
+    pixels.set_brightness(4, 150).unwrap();
+    assert_eq!(pixels.get_color(3), Some(Color::ORANGE));
+    assert_eq!(pixels.get_brightness(3), Some(40));
+    // Brightness is clamped to 100
+    assert_eq!(pixels.get_brightness(4), Some(100));
+    assert_eq!(pixels.get_color(8), None);
+    assert_eq!(pixels.get_brightness(8), None);
+    pixels.release().done();
+}
+
+#[test]
+fn test_pixels_show_if_changed() {
+    let addr = 0x36;
+    let expectations = [
+        I2cTransaction::write(addr, frame([0xE0, 0x00, 0x00, 0x00])),
+        I2cTransaction::write(addr, frame([0xEF, 0x00, 0x00, 0xFF])),
+        I2cTransaction::write(addr, frame([0xEF, 0x00, 0x00, 0xFF])),
+    ];
+    let mut pixels = Pixels::new(I2cMock::new(&expectations)).unwrap();
+
+    // The first call always writes
+    assert!(pixels.show_if_changed().unwrap());
+    assert!(!pixels.show_if_changed().unwrap());
+
+    pixels.set_color(0, Color::RED, 50).unwrap();
+    assert!(pixels.show_if_changed().unwrap());
+
+    // Setting the same state again is not a change
+    pixels.set_color(0, Color::RED, 50).unwrap();
+    assert!(!pixels.show_if_changed().unwrap());
+
+    // show() always writes
+    pixels.show().unwrap();
+    assert!(!pixels.show_if_changed().unwrap());
+    pixels.release().done();
+}
+
+#[test]
+fn test_pixels_power_budget() {
+    let addr = 0x36;
+    let all = |led: [u8; 4]| led.repeat(8);
+    let expectations = [
+        I2cTransaction::write(addr, all([0xFF, 0xFF, 0xFF, 0xFF])),
+        I2cTransaction::write(addr, all([0xFF, 129, 129, 129])),
+        I2cTransaction::write(addr, all([0xFF, 0, 0, 0])),
+    ];
+    let mut pixels = Pixels::new(I2cMock::new(&expectations)).unwrap();
+    assert_eq!(pixels.power_model(), &PowerModel::APA102);
+    // All off: just the idle current
+    assert_eq!(pixels.estimated_current_ma(), 7);
+    pixels.set_color(1, Color::RED, 50).unwrap();
+    assert_eq!(pixels.estimated_current_ma(), 17);
+
+    // 8 x (3 x 20 mA + 0.8 mA)
+    pixels.set_all_color(Color::WHITE, 100);
+    assert_eq!(pixels.estimated_current_ma(), 487);
+    pixels.show().unwrap();
+
+    pixels.set_power_budget(Some(250));
+    assert_eq!(pixels.power_budget(), Some(250));
+    pixels.show().unwrap();
+    // The estimate and the stored colors ignore the budget
+    assert_eq!(pixels.estimated_current_ma(), 487);
+    assert_eq!(pixels.get_color(0), Some(Color::WHITE));
+
+    // Less than the idle current turns every channel off
+    pixels.set_power_budget(Some(5));
+    pixels.show().unwrap();
+
+    pixels.set_power_model(PowerModel::new(10, 10, 10));
+    assert_eq!(pixels.estimated_current_ma(), 240);
+    pixels.release().done();
+}
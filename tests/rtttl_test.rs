@@ -0,0 +1,93 @@
+use modulino::{Note, Rtttl, RtttlErrorKind, Sequencer, ToneSource};
+
+const TETRIS: &str = "Tetris:d=4,o=5,b=160:e6,8b,8c6,8d6,16e6,16d6,8c6,8b,a,8a,8c6,e6,8d6,8c6,b,8b,8c6,d6,e6,c6,a,2a";
+
+#[test]
+fn test_parse_header_and_notes() {
+    let tune = Rtttl::parse(TETRIS).unwrap();
+    assert_eq!(tune.name(), "Tetris");
+    assert_eq!(tune.default_duration(), 4);
+    assert_eq!(tune.default_octave(), 5);
+    assert_eq!(tune.tempo(), 160);
+    assert_eq!(tune.notes().count(), 22);
+
+    let notes: Vec<_> = tune.notes().take(5).collect();
+    assert_eq!(notes[0].note(), Some(Note::E6));
+    assert_eq!(notes[0].duration_ms, 375);
+    assert_eq!(notes[1].note(), Some(Note::B5));
+    assert_eq!(notes[1].duration_ms, 187);
+    assert_eq!(notes[4].duration_ms, 93);
+}
+
+#[test]
+fn test_note_syntax() {
+    let tune = Rtttl::parse("x:d=8,o=6,b=120: c#, 4p, 2d.5, f#.7 ,h4,8g#6.,").unwrap();
+    let notes: Vec<_> = tune.notes().collect();
+    assert_eq!(notes.len(), 6);
+    assert_eq!(notes[0].note(), Some(Note::CS6));
+    assert_eq!(notes[0].duration_ms, 250);
+    assert!(notes[1].is_rest());
+    assert_eq!(notes[1].note(), Some(Note::Rest));
+    assert_eq!(notes[1].duration_ms, 500);
+    assert_eq!(notes[2].note(), Some(Note::D5));
+    assert_eq!(notes[2].duration_ms, 1500);
+    assert_eq!(notes[3].note(), Some(Note::FS7));
+    assert_eq!(notes[3].duration_ms, 375);
+    assert_eq!(notes[4].note(), Some(Note::B4));
+    assert_eq!(notes[5].note(), Some(Note::GS6));
+    assert_eq!(notes[5].duration_ms, 375);
+}
+
+#[test]
+fn test_defaults_and_raw_frequencies() {
+    // Standard defaults: d=4, o=6, b=63
+    let tune = Rtttl::parse("Beep::c,b8").unwrap();
+    let notes: Vec<_> = tune.notes().collect();
+    assert_eq!(notes[0].note(), Some(Note::C6));
+    assert_eq!(notes[0].duration_ms, 952);
+
+    // B8 is above the `Note` enum, so only the frequency is known
+    assert_eq!(notes[1].frequency, 7902);
+    assert_eq!(notes[1].note(), None);
+}
+
+#[test]
+fn test_invalid_input() {
+    let err = |text| Rtttl::parse(text).unwrap_err();
+
+    assert_eq!(err("no sections").kind, RtttlErrorKind::MissingSection);
+    assert_eq!(err("x:d=3:c").kind, RtttlErrorKind::InvalidDuration);
+    assert_eq!(err("x:q=3:c").kind, RtttlErrorKind::InvalidDefault);
+    assert_eq!(err("x:b=0:c").kind, RtttlErrorKind::InvalidTempo);
+    assert_eq!(err("x:o=9:c").kind, RtttlErrorKind::InvalidOctave);
+
+    let e = err("x:d=4,o=5,b=100:c,3d,e");
+    assert_eq!(e.kind, RtttlErrorKind::InvalidDuration);
+    assert_eq!(e.position, 18);
+
+    assert_eq!(err("x::c,x").kind, RtttlErrorKind::InvalidNote);
+    assert_eq!(err("x::c5z").kind, RtttlErrorKind::InvalidNote);
+
+    // F#3 (185 Hz) is the lowest playable note
+    assert!(Rtttl::parse("x::f#3").is_ok());
+    let e = err("x::c,f3");
+    assert_eq!(e.kind, RtttlErrorKind::BelowMinimumFrequency);
+    assert_eq!(e.position, 5);
+}
+
+#[test]
+fn test_rtttl_in_sequencer() {
+    let tune = Rtttl::parse("x:d=4,o=5,b=120:c,p,g").unwrap().with_gap(10);
+    let mut seq = Sequencer::new(tune);
+    let first = seq.poll(0).unwrap();
+    assert_eq!(first.frequency, Note::C5.frequency());
+    assert_eq!(first.sound_ms, 490);
+    assert!(seq.poll(500).unwrap().is_rest());
+    assert_eq!(seq.poll(1000).unwrap().frequency, Note::G5.frequency());
+    assert!(seq.poll(1500).is_none());
+    assert!(seq.is_finished());
+
+    let mut tune = seq.into_source();
+    tune.rewind();
+    assert_eq!(tune.next_step().unwrap().frequency, 523);
+}
@@ -0,0 +1,95 @@
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+use modulino::{scan, ModulinoKind, SCAN_RANGE};
+
+fn nack(addr: u8) -> I2cTransaction {
+    I2cTransaction::write(addr, vec![])
+        .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))
+}
+
+#[test]
+fn test_scan_identifies_modules() {
+    let mut expectations = Vec::new();
+    for addr in SCAN_RANGE {
+        match addr {
+            // Unknown MCU-based device
+            0x10 => {
+                expectations.push(I2cTransaction::write(addr, vec![]));
+                expectations.push(I2cTransaction::read(addr, vec![0x99]));
+            }
+            // Buttons, identified by pinstrap
+            0x3E => {
+                expectations.push(I2cTransaction::write(addr, vec![]));
+                expectations.push(I2cTransaction::read(addr, vec![0x7C]));
+            }
+            // Knob on its second address
+            0x3B => {
+                expectations.push(I2cTransaction::write(addr, vec![]));
+                expectations.push(I2cTransaction::read(addr, vec![0x76]));
+            }
+            // Thermo, identified by address
+            0x44 => expectations.push(I2cTransaction::write(addr, vec![])),
+            // Movement, identified by WHO_AM_I
+            0x6A => {
+                expectations.push(I2cTransaction::write(addr, vec![]));
+                expectations.push(I2cTransaction::write_read(addr, vec![0x0F], vec![0x6C]));
+            }
+            // Hub
+            0x71 => expectations.push(I2cTransaction::write(addr, vec![])),
+            _ => expectations.push(nack(addr)),
+        }
+    }
+
+    let mut i2c = I2cMock::new(&expectations);
+    let found = scan(&mut i2c);
+    i2c.done();
+
+    assert_eq!(
+        found.iter().collect::<Vec<_>>(),
+        vec![
+            (0x10, ModulinoKind::Unknown),
+            (0x3B, ModulinoKind::Knob),
+            (0x3E, ModulinoKind::Buttons),
+            (0x44, ModulinoKind::Thermo),
+            (0x6A, ModulinoKind::Movement),
+            (0x71, ModulinoKind::Hub),
+        ]
+    );
+    assert_eq!(found.find(ModulinoKind::Knob), Some(0x3B));
+    assert!(!found.contains(ModulinoKind::Pixels));
+}
+
+#[test]
+fn test_scan_distance_model_id_mismatch_falls_back_to_pinstrap() {
+    let mut expectations = Vec::new();
+    for addr in SCAN_RANGE {
+        if addr == 0x29 {
+            expectations.push(I2cTransaction::write(addr, vec![]));
+            expectations.push(I2cTransaction::write_read(
+                addr,
+                vec![0x01, 0x0F],
+                vec![0x00, 0x00],
+            ));
+            expectations.push(I2cTransaction::read(addr, vec![0x00]));
+        } else {
+            expectations.push(nack(addr));
+        }
+    }
+
+    let mut i2c = I2cMock::new(&expectations);
+    let found = scan(&mut i2c);
+    i2c.done();
+
+    assert_eq!(found.get(0x29), Some(ModulinoKind::Unknown));
+    assert_eq!(found.len(), 1);
+}
+
+#[test]
+fn test_from_pinstrap() {
+    assert_eq!(
+        ModulinoKind::from_pinstrap(0x3C),
+        Some(ModulinoKind::Buzzer)
+    );
+    assert_eq!(ModulinoKind::from_pinstrap(0x74), Some(ModulinoKind::Knob));
+    assert_eq!(ModulinoKind::from_pinstrap(0x00), None);
+}
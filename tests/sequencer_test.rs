@@ -0,0 +1,83 @@
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+use modulino::{Buzzer, Melody, Note, Sequencer, ToneSource, ToneStep};
+
+const TUNE: &[(Note, u16)] = &[
+    (Note::C5, Melody::QUARTER),
+    (Note::Rest, Melody::EIGHTH),
+    (Note::G5, Melody::DOTTED_QUARTER),
+];
+
+#[test]
+fn test_melody_steps() {
+    let mut melody = Melody::new(TUNE).with_tempo(60).with_gap(50);
+    assert_eq!(melody.duration_ms(), 1000 + 500 + 1500);
+    assert_eq!(
+        melody.next_step(),
+        Some(ToneStep {
+            frequency: 523,
+            sound_ms: 950,
+            duration_ms: 1000
+        })
+    );
+    assert!(melody.next_step().unwrap().is_rest());
+    assert_eq!(melody.next_step().unwrap().duration_ms, 1500);
+    assert_eq!(melody.next_step(), None);
+
+    melody.rewind();
+    assert_eq!(melody.next_step().unwrap().frequency, 523);
+}
+
+#[test]
+fn test_sequencer_schedules_steps() {
+    let mut seq = Sequencer::new(Melody::new(TUNE).with_tempo(60));
+
+    assert_eq!(seq.poll(100).map(|s| s.frequency), Some(523));
+    assert_eq!(seq.poll(500), None);
+    assert_eq!(seq.poll(1099), None);
+    assert_eq!(seq.poll(1100).map(|s| s.frequency), Some(0));
+
+    // A late tick does not delay the following step
+    assert_eq!(seq.poll(1700).map(|s| s.frequency), Some(784));
+    assert_eq!(seq.poll(3099), None);
+    assert!(!seq.is_finished());
+    assert_eq!(seq.poll(3100), None);
+    assert!(seq.is_finished());
+
+    seq.restart();
+    assert_eq!(seq.poll(5000).map(|s| s.frequency), Some(523));
+}
+
+#[test]
+fn test_sequencer_looping_and_wraparound() {
+    let mut seq = Sequencer::new(Melody::new(&TUNE[..1]).with_tempo(60)).with_looping(true);
+    let start = u32::MAX - 500;
+    assert!(seq.poll(start).is_some());
+    assert!(seq.poll(start.wrapping_add(999)).is_none());
+    assert!(seq.poll(start.wrapping_add(1000)).is_some());
+    assert!(seq.poll(start.wrapping_add(2000)).is_some());
+    assert!(!seq.is_finished());
+
+    let mut empty = Sequencer::new(Melody::new(&[])).with_looping(true);
+    assert!(empty.poll(0).is_none());
+    assert!(empty.is_finished());
+}
+
+#[test]
+fn test_sequencer_drives_buzzer() {
+    let addr = 0x1E;
+    let expectations = [
+        I2cTransaction::write(addr, vec![0x00; 8]),
+        // C5 (523 Hz) for 480 ms
+        I2cTransaction::write(addr, vec![0x0B, 0x02, 0x00, 0x00, 0xE0, 0x01, 0x00, 0x00]),
+        // Rest
+        I2cTransaction::write(addr, vec![0x00; 8]),
+    ];
+    let mut buzzer = Buzzer::new(I2cMock::new(&expectations)).unwrap();
+    let mut seq = Sequencer::new(Melody::new(&TUNE[..2]).with_gap(20));
+
+    assert!(seq.tick(&mut buzzer, 0).unwrap());
+    assert!(seq.tick(&mut buzzer, 100).unwrap());
+    assert!(seq.tick(&mut buzzer, 500).unwrap());
+    assert!(!seq.tick(&mut buzzer, 750).unwrap());
+    buzzer.release().done();
+}
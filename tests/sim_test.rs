@@ -0,0 +1,505 @@
+#![cfg(feature = "sim")]
+
+use core::cell::RefCell;
+
+use embedded_hal_mock::eh1::delay::NoopDelay;
+use modulino::sim::{self, SimBus, SimDevice, SimError};
+use modulino::{
+    Buttons, Buzzer, Color, DetectionMode, Distance, Error, Hub, HubChannel, InterruptSource,
+    Joystick, Knob, LatchRelay, LedMatrix, Light, ModulinoKind, Movement, OptoRelay, Pixels,
+    PowerLevel, Pressure, RangeStatus, Thermo, Vibro,
+};
+
+#[test]
+fn test_sim_firmware_modules() {
+    let buttons = RefCell::new(sim::Buttons::new());
+    let buzzer = RefCell::new(sim::Buzzer::new());
+    let pixels = RefCell::new(sim::Pixels::new());
+    let joystick = RefCell::new(sim::Joystick::new());
+    let devices: [&RefCell<dyn SimDevice>; 4] = [&buttons, &buzzer, &pixels, &joystick];
+    let bus = SimBus::new(&devices);
+
+    let mut buttons_driver = Buttons::new(bus).unwrap();
+    buttons.borrow_mut().press(false, true, false);
+    assert!(buttons_driver.read().unwrap().b);
+    buttons_driver.set_leds(true, false, true).unwrap();
+    assert_eq!(buttons.borrow().leds(), [true, false, true]);
+
+    let mut buzzer_driver = Buzzer::new(bus).unwrap();
+    buzzer_driver.tone(440, 250).unwrap();
+    assert_eq!(buzzer.borrow().frequency(), 440);
+    assert_eq!(buzzer.borrow().duration_ms(), 250);
+    buzzer_driver.no_tone().unwrap();
+    assert!(!buzzer.borrow().is_playing());
+
+    let mut pixels_driver = Pixels::new(bus).unwrap();
+    pixels_driver.set_color(2, Color::RED, 100).unwrap();
+    pixels_driver.show().unwrap();
+    assert_eq!(pixels.borrow().color(2), Color::RED);
+    assert_eq!(pixels.borrow().brightness(2), 31);
+    assert!(!pixels.borrow().is_lit(0));
+
+    let mut joystick_driver = Joystick::new(bus).unwrap();
+    joystick.borrow_mut().set_position(60, -40);
+    joystick.borrow_mut().set_pressed(true);
+    joystick_driver.update().unwrap();
+    assert_eq!(joystick_driver.position(), (60, -40));
+    assert!(joystick_driver.button_pressed());
+}
+
+#[test]
+fn test_sim_knob() {
+    let knob = RefCell::new(sim::Knob::new());
+    let devices: [&RefCell<dyn SimDevice>; 1] = [&knob];
+    let bus = SimBus::new(&devices);
+
+    let mut driver = Knob::new(bus).unwrap();
+    knob.borrow_mut().rotate(3);
+    driver.update().unwrap();
+    assert_eq!(driver.value(), 3);
+
+    driver.set_value(42).unwrap();
+    assert_eq!(knob.borrow().value(), 42);
+}
+
+#[test]
+fn test_sim_knob_inverted_set_is_detected() {
+    let knob = RefCell::new(sim::Knob::new().with_inverted_set());
+    let devices: [&RefCell<dyn SimDevice>; 1] = [&knob];
+    let bus = SimBus::new(&devices);
+
+    let mut driver = Knob::new(bus).unwrap();
+
+    // Writes are compensated, so the firmware ends up holding the requested value
+    driver.set_value(10).unwrap();
+    assert_eq!(knob.borrow().value(), 10);
+    driver.update().unwrap();
+    assert_eq!(driver.value(), 10);
+}
+
+#[test]
+fn test_sim_relays_vibro_and_matrix() {
+    let latch = RefCell::new(sim::LatchRelay::new());
+    let opto = RefCell::new(sim::OptoRelay::new());
+    let vibro = RefCell::new(sim::Vibro::new());
+    let matrix = RefCell::new(sim::LedMatrix::new());
+    let devices: [&RefCell<dyn SimDevice>; 4] = [&latch, &opto, &vibro, &matrix];
+    let bus = SimBus::new(&devices);
+
+    let mut latch_driver = LatchRelay::new(bus).unwrap();
+    assert_eq!(latch_driver.is_on().unwrap(), None);
+    latch_driver.toggle().unwrap();
+    assert_eq!(latch.borrow().is_on(), Some(true));
+    assert_eq!(latch_driver.is_on().unwrap(), Some(true));
+    latch_driver.toggle().unwrap();
+    assert_eq!(latch.borrow().is_on(), Some(false));
+
+    let mut opto_driver = OptoRelay::new(bus).unwrap();
+    opto_driver.on().unwrap();
+    assert!(opto.borrow().is_on());
+
+    let mut vibro_driver = Vibro::new(bus).unwrap();
+    assert!(!vibro.borrow().is_on());
+    vibro_driver.on(500, PowerLevel::Intense).unwrap();
+    assert_eq!(vibro.borrow().power(), 40);
+    assert_eq!(vibro.borrow().duration_ms(), 500);
+
+    let mut matrix_driver = LedMatrix::new(bus);
+    matrix_driver.init().unwrap();
+    matrix_driver.set_pixel(3, 5, 255).unwrap();
+    matrix_driver.show().unwrap();
+    assert_eq!(matrix.borrow().pixel(3, 5), 1);
+    assert_eq!(matrix.borrow().pixel(5, 3), 0);
+
+    matrix_driver
+        .set_mode(modulino::DisplayMode::Grayscale)
+        .unwrap();
+    assert!(matrix.borrow().is_grayscale());
+    matrix_driver.set_grayscale_pixel(11, 7, 9).unwrap();
+    matrix_driver.show().unwrap();
+    assert_eq!(matrix.borrow().pixel(11, 7), 9);
+}
+
+#[test]
+fn test_sim_thermo() {
+    let thermo = RefCell::new(sim::Thermo::new());
+    thermo.borrow_mut().set_temperature(21.5);
+    thermo.borrow_mut().set_humidity(40.0);
+    let devices: [&RefCell<dyn SimDevice>; 1] = [&thermo];
+    let bus = SimBus::new(&devices);
+
+    let mut driver = Thermo::new(bus);
+    let measurement = driver.read(&mut NoopDelay::new()).unwrap();
+    assert!((measurement.temperature - 21.5).abs() < 0.05);
+    assert!((measurement.humidity - 40.0).abs() < 0.05);
+}
+
+#[test]
+fn test_sim_distance_boot_and_ranging() {
+    let distance = RefCell::new(
+        sim::Distance::new()
+            .with_boot_polls(3)
+            .with_measurement_polls(2),
+    );
+    let devices: [&RefCell<dyn SimDevice>; 1] = [&distance];
+    let bus = SimBus::new(&devices);
+
+    let mut driver = Distance::new(bus);
+    driver.init(&mut NoopDelay::new()).unwrap();
+    assert!(!distance.borrow().is_ranging());
+
+    distance.borrow_mut().set_distance(250);
+    driver.start_ranging().unwrap();
+    assert!(!driver.data_ready().unwrap());
+    assert!(!driver.data_ready().unwrap());
+    assert!(driver.data_ready().unwrap());
+    assert_eq!(driver.read_distance().unwrap(), Some(250));
+
+    // Clearing the interrupt starts the next measurement
+    assert!(!driver.data_ready().unwrap());
+}
+
+#[test]
+fn test_sim_distance_invalid_status() {
+    let distance = RefCell::new(sim::Distance::new());
+    let devices: [&RefCell<dyn SimDevice>; 1] = [&distance];
+    let bus = SimBus::new(&devices);
+
+    let mut driver = Distance::new(bus);
+    driver.init(&mut NoopDelay::new()).unwrap();
+
+    // Raw 0x07: wrapped target
+    distance.borrow_mut().set_range_status(0x07);
+    driver.start_ranging().unwrap();
+    assert!(driver.data_ready().unwrap());
+    assert_eq!(driver.read_distance().unwrap(), None);
+
+    distance.borrow_mut().set_distance(180);
+    assert!(driver.data_ready().unwrap());
+    let measurement = driver.read_measurement().unwrap();
+    assert_eq!(measurement.status, RangeStatus::Valid);
+    assert_eq!(measurement.distance(), Some(180));
+}
+
+#[test]
+fn test_sim_distance_offset_calibration() {
+    let distance = RefCell::new(sim::Distance::new().with_measurement_polls(1));
+    let devices: [&RefCell<dyn SimDevice>; 1] = [&distance];
+    let bus = SimBus::new(&devices);
+
+    let mut driver = Distance::new(bus);
+    let mut delay = NoopDelay::new();
+    driver.init(&mut delay).unwrap();
+
+    // The target is at 100 mm but the sensor reads 88 mm
+    distance.borrow_mut().set_distance(88);
+    assert_eq!(driver.calibrate_offset(&mut delay, 100, 8).unwrap(), 12);
+    assert!(!distance.borrow().is_ranging());
+    assert_eq!(driver.offset().unwrap(), 12);
+
+    driver.start_ranging().unwrap();
+    while !driver.data_ready().unwrap() {}
+    assert_eq!(driver.read_distance().unwrap(), Some(100));
+    driver.stop_ranging().unwrap();
+
+    // A stored offset is re-applied after init
+    driver.init(&mut delay).unwrap();
+    driver.set_offset(12).unwrap();
+    assert_eq!(driver.offset().unwrap(), 12);
+}
+
+#[test]
+fn test_sim_distance_crosstalk_calibration() {
+    let distance = RefCell::new(sim::Distance::new());
+    let devices: [&RefCell<dyn SimDevice>; 1] = [&distance];
+    let bus = SimBus::new(&devices);
+
+    let mut driver = Distance::new(bus);
+    let mut delay = NoopDelay::new();
+    driver.init(&mut delay).unwrap();
+
+    // No target: 8 SPADs receive 64 kcps from the cover glass
+    {
+        let mut sim = distance.borrow_mut();
+        sim.set_range_status(0x17);
+        sim.set_register(0x008C, 0x08);
+        sim.set_register(0x008F, 0x08);
+    }
+    assert_eq!(driver.calibrate_crosstalk(&mut delay, 10).unwrap(), 8.0);
+    assert_eq!(driver.crosstalk().unwrap(), 8.0);
+    assert_eq!(distance.borrow().register(0x0016), 0x10);
+    assert!(!distance.borrow().is_ranging());
+}
+
+#[test]
+fn test_sim_distance_detection_thresholds() {
+    let distance = RefCell::new(sim::Distance::new());
+    let devices: [&RefCell<dyn SimDevice>; 1] = [&distance];
+    let bus = SimBus::new(&devices);
+
+    let mut driver = Distance::new(bus);
+    driver.init(&mut NoopDelay::new()).unwrap();
+    driver
+        .set_detection_thresholds(150, 0, DetectionMode::Below)
+        .unwrap();
+
+    // Nothing close: measurements do not raise the interrupt
+    distance.borrow_mut().set_distance(400);
+    driver.start_ranging().unwrap();
+    assert_eq!(driver.read_interrupt_source().unwrap(), None);
+    assert_eq!(driver.read_interrupt_source().unwrap(), None);
+    assert!(distance.borrow().measurements() >= 2);
+
+    distance.borrow_mut().set_distance(120);
+    assert_eq!(
+        driver.read_interrupt_source().unwrap(),
+        Some(InterruptSource::Threshold(DetectionMode::Below))
+    );
+    assert_eq!(driver.read_distance().unwrap(), Some(120));
+
+    // Back to an interrupt for every measurement
+    driver.clear_detection_thresholds().unwrap();
+    distance.borrow_mut().set_distance(400);
+    assert_eq!(
+        driver.read_interrupt_source().unwrap(),
+        Some(InterruptSource::DataReady)
+    );
+}
+
+#[test]
+fn test_sim_distance_range_timing() {
+    let distance = RefCell::new(sim::Distance::new());
+    let devices: [&RefCell<dyn SimDevice>; 1] = [&distance];
+    let bus = SimBus::new(&devices);
+
+    let mut driver = Distance::new(bus);
+    driver.init(&mut NoopDelay::new()).unwrap();
+    assert_eq!(driver.timing_budget().unwrap(), 20);
+    assert_eq!(driver.inter_measurement().unwrap(), 50);
+
+    for budget in 10..=200 {
+        for period in [0, 250, 1000] {
+            driver.set_range_timing(budget, period).unwrap();
+            let read = driver.timing_budget().unwrap();
+            assert!(
+                read.abs_diff(budget) <= 1,
+                "{} ms read as {} ms",
+                budget,
+                read
+            );
+            assert_eq!(driver.inter_measurement().unwrap(), period);
+        }
+    }
+
+    // The other setting is kept
+    driver.set_range_timing(33, 0).unwrap();
+    driver.set_inter_measurement(100).unwrap();
+    assert_eq!(driver.timing_budget().unwrap(), 33);
+
+    // A budget longer than the period is rejected
+    assert_eq!(driver.set_timing_budget(100), Err(Error::InvalidParameter));
+    assert_eq!(driver.timing_budget().unwrap(), 33);
+    assert_eq!(driver.inter_measurement().unwrap(), 100);
+}
+
+#[test]
+fn test_sim_distance_timing_budget_after_init() {
+    let distance = RefCell::new(sim::Distance::new());
+    let devices: [&RefCell<dyn SimDevice>; 1] = [&distance];
+    let bus = SimBus::new(&devices);
+
+    let mut driver = Distance::new(bus);
+    driver.init(&mut NoopDelay::new()).unwrap();
+
+    // Longer than the 50 ms period set by init
+    assert_eq!(driver.set_timing_budget(100), Err(Error::InvalidParameter));
+    assert_eq!(driver.inter_measurement().unwrap(), 50);
+
+    // Back-to-back measurements allow any budget
+    driver.set_range_timing(100, 0).unwrap();
+    assert_eq!(driver.timing_budget().unwrap(), 100);
+    assert_eq!(driver.inter_measurement().unwrap(), 0);
+
+    // Shorter budgets keep the period
+    driver.set_inter_measurement(500).unwrap();
+    driver.set_timing_budget(200).unwrap();
+    assert_eq!(driver.inter_measurement().unwrap(), 500);
+}
+
+#[test]
+fn test_sim_distance_init_timeout() {
+    // The sensor never boots
+    let distance = RefCell::new(sim::Distance::new().with_boot_polls(u32::MAX));
+    let devices: [&RefCell<dyn SimDevice>; 1] = [&distance];
+    let mut driver = Distance::new(SimBus::new(&devices));
+    assert_eq!(driver.init(&mut NoopDelay::new()), Err(Error::Timeout));
+
+    // The sensor boots but never completes its first measurement
+    let distance = RefCell::new(sim::Distance::new().with_measurement_polls(u32::MAX));
+    let devices: [&RefCell<dyn SimDevice>; 1] = [&distance];
+    let mut driver = Distance::new(SimBus::new(&devices));
+    assert_eq!(driver.init(&mut NoopDelay::new()), Err(Error::Timeout));
+}
+
+#[test]
+fn test_sim_distance_measure_blocking() {
+    let distance = RefCell::new(sim::Distance::new().with_measurement_polls(5));
+    let devices: [&RefCell<dyn SimDevice>; 1] = [&distance];
+    let bus = SimBus::new(&devices);
+
+    let mut driver = Distance::new(bus);
+    let mut delay = NoopDelay::new();
+    driver.init(&mut delay).unwrap();
+    let before = distance.borrow().measurements();
+
+    distance.borrow_mut().set_distance(420);
+    assert_eq!(driver.measure_blocking(&mut delay, 100).unwrap(), Some(420));
+    assert_eq!(distance.borrow().measurements(), before + 1);
+    assert!(!distance.borrow().is_ranging());
+    assert!(!driver.data_ready().unwrap());
+
+    // Too short to complete the measurement
+    assert_eq!(driver.measure_blocking(&mut delay, 2), Err(Error::Timeout));
+    assert!(!distance.borrow().is_ranging());
+}
+
+#[test]
+fn test_sim_distance_calibration_timeout() {
+    let distance = RefCell::new(sim::Distance::new().with_measurement_polls(u32::MAX));
+    let devices: [&RefCell<dyn SimDevice>; 1] = [&distance];
+    let bus = SimBus::new(&devices);
+
+    let mut driver = Distance::new(bus);
+    assert_eq!(
+        driver.calibrate_offset(&mut NoopDelay::new(), 100, 5),
+        Err(Error::Timeout)
+    );
+}
+
+#[test]
+fn test_sim_movement() {
+    let movement = RefCell::new(sim::Movement::new());
+    let devices: [&RefCell<dyn SimDevice>; 1] = [&movement];
+    let bus = SimBus::new(&devices);
+
+    let mut driver = Movement::new(bus).unwrap();
+    assert_eq!(movement.borrow().register(0x10), 0x40);
+
+    movement.borrow_mut().set_acceleration(0.5, -0.25, 1.0);
+    movement.borrow_mut().set_angular_velocity(0.0, 90.0, 0.0);
+    assert!(driver.data_ready().unwrap());
+
+    let accel = driver.acceleration().unwrap();
+    assert!((accel.x - 0.5).abs() < 0.01);
+    assert!((accel.y + 0.25).abs() < 0.01);
+    assert!((accel.z - 1.0).abs() < 0.01);
+
+    let gyro = driver.angular_velocity().unwrap();
+    assert!((gyro.y - 90.0).abs() < 0.01);
+    assert!(!driver.data_ready().unwrap());
+}
+
+#[test]
+fn test_sim_light() {
+    let light = RefCell::new(sim::Light::new());
+    light.borrow_mut().set_channels(100, 2000, 3000, 1000);
+    let devices: [&RefCell<dyn SimDevice>; 1] = [&light];
+    let bus = SimBus::new(&devices);
+
+    let mut driver = Light::new(bus);
+    // Channels are dark until measurements are enabled
+    assert_eq!(driver.raw_lux().unwrap(), 0);
+
+    driver.init().unwrap();
+    assert!(light.borrow().is_enabled());
+    let measurement = driver.read().unwrap();
+    assert_eq!(measurement.ir, 100);
+    assert_eq!(measurement.green, 2000);
+    assert_eq!(measurement.red, 3000);
+    assert_eq!(measurement.blue, 1000);
+    assert!(measurement.lux > 0.0);
+}
+
+#[test]
+fn test_sim_pressure() {
+    let pressure = RefCell::new(sim::Pressure::new());
+    pressure.borrow_mut().set_pressure(1000.5);
+    pressure.borrow_mut().set_temperature(-3.25);
+    let devices: [&RefCell<dyn SimDevice>; 1] = [&pressure];
+    let bus = SimBus::new(&devices);
+
+    let mut driver = Pressure::new(bus);
+    driver.init().unwrap();
+    assert_eq!(pressure.borrow().register(0x10), 0x22);
+    assert!((driver.pressure().unwrap() - 1000.5).abs() < 0.01);
+    assert!((driver.temperature().unwrap() + 3.25).abs() < 0.01);
+
+    driver.reset().unwrap();
+    assert_eq!(pressure.borrow().register(0x10), 0x00);
+}
+
+#[test]
+fn test_sim_missing_device_nacks() {
+    let devices: [&RefCell<dyn SimDevice>; 0] = [];
+    let bus = SimBus::new(&devices);
+
+    assert!(matches!(
+        Buttons::new(bus),
+        Err(modulino::Error::I2c(SimError::NoAcknowledge))
+    ));
+}
+
+#[test]
+fn test_sim_hub_and_scan() {
+    let buttons_a = RefCell::new(sim::Buttons::new());
+    let buttons_b = RefCell::new(sim::Buttons::new());
+    let port0: [&RefCell<dyn SimDevice>; 1] = [&buttons_a];
+    let port1: [&RefCell<dyn SimDevice>; 1] = [&buttons_b];
+    let hub = RefCell::new(
+        sim::Hub::new()
+            .with_channel(0, &port0)
+            .with_channel(1, &port1),
+    );
+    let thermo = RefCell::new(sim::Thermo::new());
+    let devices: [&RefCell<dyn SimDevice>; 2] = [&hub, &thermo];
+    let mut bus = SimBus::new(&devices);
+
+    let found = modulino::scan(&mut bus);
+    assert_eq!(
+        found.iter().collect::<Vec<_>>(),
+        vec![(0x44, ModulinoKind::Thermo), (0x70, ModulinoKind::Hub)]
+    );
+
+    let hub_driver = RefCell::new(Hub::new(bus));
+    let mut a = Buttons::new(HubChannel::new(&hub_driver, 0).unwrap()).unwrap();
+    let mut b = Buttons::new(HubChannel::new(&hub_driver, 1).unwrap()).unwrap();
+
+    buttons_b.borrow_mut().press(true, false, false);
+    assert!(!a.read().unwrap().a);
+    assert!(b.read().unwrap().a);
+    a.set_leds(false, false, true).unwrap();
+    assert_eq!(buttons_a.borrow().leds(), [false, false, true]);
+    assert_eq!(buttons_b.borrow().leds(), [false, false, false]);
+
+    let found = hub_driver.borrow_mut().scan_port(1).unwrap();
+    assert_eq!(
+        found.iter().collect::<Vec<_>>(),
+        vec![(0x3E, ModulinoKind::Buttons)]
+    );
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_sim_async_bus() {
+    use embassy_futures::block_on;
+
+    let buttons = RefCell::new(sim::Buttons::new());
+    let devices: [&RefCell<dyn SimDevice>; 1] = [&buttons];
+    let bus = SimBus::new(&devices);
+
+    block_on(async {
+        let mut driver = modulino::asynch::Buttons::new(bus).await.unwrap();
+        buttons.borrow_mut().press(false, false, true);
+        assert!(driver.read().await.unwrap().c);
+    });
+}